@@ -0,0 +1,28 @@
+use std::process::Command;
+
+// Embeds the commit and date of the build for `/api/version`. Docker builds have no
+// `.git` directory, so the commit can be passed in as `GIT_HEAD` instead.
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_HEAD");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    let commit = std::env::var("GIT_HEAD")
+        .ok()
+        .filter(|head| !head.trim().is_empty())
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=BIBTEXTER_GIT_COMMIT={}", commit.trim());
+
+    let build_date = chrono::Utc::now().format("%Y-%m-%d");
+    println!("cargo:rustc-env=BIBTEXTER_BUILD_DATE={}", build_date);
+}
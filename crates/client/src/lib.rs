@@ -0,0 +1,110 @@
+//! An async client for a running bibtexter server.
+
+use serde::Deserialize;
+
+pub use bibtexter_core::{ArticleMetadata, BibtexEntryType, ExtractionStrategy};
+
+// Errors returned by `BibtexterClient`.
+#[derive(Debug)]
+pub enum ClientError {
+    Request(reqwest::Error),
+    Server {
+        status: reqwest::StatusCode,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Request(err) => write!(f, "Request to bibtexter failed: {}", err),
+            ClientError::Server { status, message } => {
+                write!(f, "bibtexter returned {}: {}", status, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Request(err)
+    }
+}
+
+// The outcome of one URL in a `BibtexterClient::batch` call.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub url: String,
+    pub bibtex: Result<String, ClientError>,
+}
+
+// Shape of the `/api/preview` response body.
+#[derive(Deserialize)]
+struct PreviewResponse {
+    strategy: ExtractionStrategy,
+    fields: ArticleMetadata,
+}
+
+/// A thin wrapper over the bibtexter HTTP API.
+#[derive(Clone)]
+pub struct BibtexterClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl BibtexterClient {
+    /// Creates a client for the server at `base_url`, e.g. "http://localhost:8080".
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetches the generated BibTeX entry for `url`.
+    pub async fn get_bibtex(&self, url: &str) -> Result<String, ClientError> {
+        let res = self.send("/api/bibtex", url).await?;
+        Ok(res.text().await?)
+    }
+
+    /// Fetches the extracted metadata for `url` without formatting it as BibTeX.
+    pub async fn get_metadata(&self, url: &str) -> Result<ArticleMetadata, ClientError> {
+        let res = self.send("/api/preview", url).await?;
+        let preview = res.json::<PreviewResponse>().await?;
+        Ok(ArticleMetadata {
+            strategy: preview.strategy,
+            ..preview.fields
+        })
+    }
+
+    /// Fetches BibTeX for each URL in turn. A failure for one URL does not stop the rest.
+    pub async fn batch(&self, urls: &[&str]) -> Result<Vec<BatchResult>, ClientError> {
+        let mut results = Vec::with_capacity(urls.len());
+        for url in urls {
+            results.push(BatchResult {
+                url: url.to_string(),
+                bibtex: self.get_bibtex(url).await,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Issues `GET {base_url}{path}?url=...` and turns non-2xx responses into errors.
+    async fn send(&self, path: &str, url: &str) -> Result<reqwest::Response, ClientError> {
+        let res = self
+            .http
+            .get(format!("{}{}", self.base_url, path))
+            .query(&[("url", url)])
+            .send()
+            .await?;
+
+        let status = res.status();
+        if !status.is_success() {
+            let message = res.text().await.unwrap_or_default();
+            return Err(ClientError::Server { status, message });
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,40 @@
+use bibtexter::AppState;
+use bibtexter_client::{BibtexterClient, ClientError};
+
+/// Starts the real server on an ephemeral local port and returns its base URL.
+async fn spawn_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = bibtexter::app(AppState::new(reqwest::Client::new()));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn server_errors_are_surfaced_with_status() {
+    let client = BibtexterClient::new(&spawn_server().await);
+
+    let err = client.get_bibtex("not a url").await.unwrap_err();
+
+    match err {
+        ClientError::Server { status, message } => {
+            assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+            assert!(message.starts_with("Invalid URL provided"));
+        }
+        other => panic!("expected a server error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn batch_reports_each_url_separately() {
+    let client = BibtexterClient::new(&spawn_server().await);
+
+    let results = client
+        .batch(&["not a url", "also not a url"])
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].url, "also not a url");
+    assert!(results.iter().all(|result| result.bibtex.is_err()));
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use crate::BibtexEntryType;
+
+// --- BibTeX Entry Builder ---
+
+// The order fields are written in, whatever order they were set in. Fields not listed
+// here follow these, in the order they were set.
+const FIELD_ORDER: &[&str] = &[
+    "title",
+    "author",
+    "journal",
+    "volume",
+    "number",
+    "series",
+    "booktitle",
+    "editor",
+    "organization",
+    "institution",
+    "chapter",
+    "howpublished",
+    "url",
+    "pages",
+    "numpages",
+    "note",
+    "year",
+    "urldate",
+    "publisher",
+    "address",
+    "orcid",
+    "version",
+    "type",
+    "doi",
+    "isbn",
+    "issn",
+    "pmid",
+    "pmcid",
+    "eprint",
+    "archivePrefix",
+    "eprinttype",
+    "primaryClass",
+    "eprintclass",
+    "pdf",
+    "license",
+    "creativecommons",
+    "abstract",
+    "keywords",
+    "langid",
+];
+
+/// Why an entry could not be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    MissingCitationKey,
+    MissingField {
+        entry_type: BibtexEntryType,
+        field: &'static str,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::MissingCitationKey => write!(f, "the entry has no citation key"),
+            ValidationError::MissingField { entry_type, field } => {
+                write!(f, "the @{} entry has no {}", entry_type.as_str(), field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A BibTeX entry under construction, e.g.
+/// `BibtexEntry::new(BibtexEntryType::Article, "Doe2025Title").title("...").year(2025).build()`.
+///
+/// Setting a field twice keeps the last value, empty values are skipped, and fields are
+/// written in a fixed order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibtexEntry {
+    entry_type: BibtexEntryType,
+    key: String,
+    comment: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl BibtexEntry {
+    pub fn new(entry_type: BibtexEntryType, key: impl Into<String>) -> Self {
+        Self {
+            entry_type,
+            key: key.into(),
+            comment: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets a field, replacing any earlier value. Names are matched case-insensitively.
+    pub fn field(mut self, name: &str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        if value.trim().is_empty() {
+            return self;
+        }
+        match self
+            .fields
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    /// Sets a field if there is a value for it.
+    pub fn optional_field(self, name: &str, value: Option<impl Into<String>>) -> Self {
+        match value {
+            Some(value) => self.field(name, value),
+            None => self,
+        }
+    }
+
+    pub fn title(self, title: impl Into<String>) -> Self {
+        self.field("title", title)
+    }
+
+    pub fn author(self, author: impl Into<String>) -> Self {
+        self.field("author", author)
+    }
+
+    pub fn year(self, year: impl fmt::Display) -> Self {
+        self.field("year", year.to_string())
+    }
+
+    pub fn journal(self, journal: impl Into<String>) -> Self {
+        self.field("journal", journal)
+    }
+
+    pub fn publisher(self, publisher: impl Into<String>) -> Self {
+        self.field("publisher", publisher)
+    }
+
+    pub fn doi(self, doi: impl Into<String>) -> Self {
+        self.field("doi", doi)
+    }
+
+    pub fn url(self, url: impl Into<String>) -> Self {
+        self.field("url", url)
+    }
+
+    /// A `%` comment line written above the entry.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// The value set for a field, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The fields a standard style expects on this entry type that haven't been set.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        self.entry_type
+            .required_fields()
+            .iter()
+            .copied()
+            .filter(|field| self.get(field).is_none())
+            .collect()
+    }
+
+    /// Writes the entry. Every entry needs a citation key and a title; the entry type's
+    /// other required fields are left to [`BibtexEntry::missing_fields`], since a page
+    /// without, say, a year is still worth citing.
+    pub fn build(self) -> Result<String, ValidationError> {
+        if self.key.trim().is_empty() {
+            return Err(ValidationError::MissingCitationKey);
+        }
+        if self.get("title").is_none() {
+            return Err(ValidationError::MissingField {
+                entry_type: self.entry_type,
+                field: "title",
+            });
+        }
+
+        Ok(serialize_bibtex(&self, true))
+    }
+}
+
+/// Writes an entry with its fields in the standard order. Pretty output puts each field
+/// on its own indented line; compact output puts the whole entry on one line, as in
+/// `@misc{key,title={...},year={2025}}`, for shell pipelines. Either is valid BibTeX.
+pub fn serialize_bibtex(entry: &BibtexEntry, pretty: bool) -> String {
+    // A stable sort, so unlisted fields keep the order they were set in.
+    let mut fields: Vec<&(String, String)> = entry.fields.iter().collect();
+    fields.sort_by_key(|(name, _)| {
+        FIELD_ORDER
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(name))
+            .unwrap_or(FIELD_ORDER.len())
+    });
+
+    let mut bibtex = entry
+        .comment
+        .as_ref()
+        .map(|comment| format!("% {}\n", comment))
+        .unwrap_or_default();
+    bibtex.push_str(&format!("@{}{{{},", entry.entry_type.as_str(), entry.key));
+    if pretty {
+        bibtex.push('\n');
+        for (name, value) in fields {
+            bibtex.push_str(&format!("  {} = {{{}}},\n", name, value));
+        }
+    } else {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| format!("{}={{{}}}", name, value))
+            .collect();
+        bibtex.push_str(&fields.join(","));
+    }
+    bibtex.push('}');
+    bibtex
+}
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+// --- BibTeX Entry Types ---
+
+/// The BibTeX entry types the server knows how to describe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BibtexEntryType {
+    Article,
+    Book,
+    InProceedings,
+    InCollection,
+    Unpublished,
+    Manual,
+    Proceedings,
+    Collection,
+    TechReport,
+    /// BibLaTeX's type for web pages; plain BibTeX styles only know `@misc`.
+    Online,
+    /// BibLaTeX's type for research data, following the FORCE11 data citation
+    /// principles; plain BibTeX gets `@misc` with `type = {Dataset}` instead.
+    Dataset,
+    #[default]
+    Misc,
+}
+
+impl BibtexEntryType {
+    pub const ALL: &'static [BibtexEntryType] = &[
+        BibtexEntryType::Article,
+        BibtexEntryType::Book,
+        BibtexEntryType::InProceedings,
+        BibtexEntryType::InCollection,
+        BibtexEntryType::Unpublished,
+        BibtexEntryType::Manual,
+        BibtexEntryType::Proceedings,
+        BibtexEntryType::Collection,
+        BibtexEntryType::TechReport,
+        BibtexEntryType::Online,
+        BibtexEntryType::Dataset,
+        BibtexEntryType::Misc,
+    ];
+
+    /// The name used after the `@` in an entry, e.g. "misc".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BibtexEntryType::Article => "article",
+            BibtexEntryType::Book => "book",
+            BibtexEntryType::InProceedings => "inproceedings",
+            BibtexEntryType::InCollection => "incollection",
+            BibtexEntryType::Unpublished => "unpublished",
+            BibtexEntryType::Manual => "manual",
+            BibtexEntryType::Proceedings => "proceedings",
+            BibtexEntryType::Collection => "collection",
+            BibtexEntryType::TechReport => "techreport",
+            BibtexEntryType::Online => "online",
+            BibtexEntryType::Dataset => "dataset",
+            BibtexEntryType::Misc => "misc",
+        }
+    }
+
+    /// The entry type named after the `@`, in any case, e.g. `Article`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|entry_type| entry_type.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Fields a standard BibTeX style expects to find on this entry type.
+    pub fn required_fields(&self) -> &'static [&'static str] {
+        match self {
+            BibtexEntryType::Article => &["author", "title", "journal", "year"],
+            BibtexEntryType::Book => &["author", "title", "publisher", "year"],
+            BibtexEntryType::InProceedings => &["author", "title", "booktitle", "year"],
+            BibtexEntryType::InCollection => &["author", "title", "booktitle", "publisher", "year"],
+            BibtexEntryType::Unpublished => &["author", "title", "note"],
+            BibtexEntryType::Manual => &["title"],
+            BibtexEntryType::Proceedings => &["title", "year"],
+            BibtexEntryType::Collection => &["editor", "title", "publisher", "year"],
+            BibtexEntryType::TechReport => &["author", "title", "institution", "year"],
+            BibtexEntryType::Online => &["title", "url"],
+            BibtexEntryType::Dataset => &["author", "title", "year", "publisher"],
+            BibtexEntryType::Misc => &[],
+        }
+    }
+
+    /// Fields that are recognised but not required on this entry type.
+    pub fn optional_fields(&self) -> &'static [&'static str] {
+        match self {
+            BibtexEntryType::Article => &[
+                "volume", "number", "pages", "month", "doi", "note", "version", "address",
+            ],
+            BibtexEntryType::Book => &[
+                "editor", "volume", "series", "address", "edition", "isbn", "numpages",
+            ],
+            BibtexEntryType::InProceedings => &[
+                "editor",
+                "pages",
+                "organization",
+                "publisher",
+                "address",
+                "doi",
+            ],
+            BibtexEntryType::InCollection => {
+                &["editor", "pages", "chapter", "isbn", "address", "doi"]
+            }
+            BibtexEntryType::Unpublished => &["month", "year", "url"],
+            BibtexEntryType::Manual => {
+                &["author", "organization", "year", "url", "version", "note"]
+            }
+            BibtexEntryType::Proceedings => &[
+                "editor",
+                "volume",
+                "series",
+                "address",
+                "publisher",
+                "organization",
+                "isbn",
+                "doi",
+            ],
+            BibtexEntryType::Collection => {
+                &["volume", "series", "address", "isbn", "numpages", "doi"]
+            }
+            BibtexEntryType::TechReport => &["type", "number", "address", "month", "url", "doi"],
+            BibtexEntryType::Online => &[
+                "author",
+                "year",
+                "urldate",
+                "organization",
+                "version",
+                "note",
+                "doi",
+            ],
+            BibtexEntryType::Dataset => &[
+                "editor",
+                "howpublished",
+                "version",
+                "type",
+                "url",
+                "urldate",
+                "doi",
+                "note",
+            ],
+            BibtexEntryType::Misc => &[
+                "author",
+                "title",
+                "howpublished",
+                "year",
+                "note",
+                "urldate",
+                "publisher",
+            ],
+        }
+    }
+}
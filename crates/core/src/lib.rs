@@ -0,0 +1,13 @@
+//! Types shared between the bibtexter server and its client library.
+
+mod entry;
+mod entry_type;
+mod metadata;
+mod output_mode;
+mod strategy;
+
+pub use entry::{BibtexEntry, ValidationError, serialize_bibtex};
+pub use entry_type::BibtexEntryType;
+pub use metadata::{ArticleMetadata, FieldType};
+pub use output_mode::OutputMode;
+pub use strategy::ExtractionStrategy;
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BibtexEntryType, ExtractionStrategy};
+
+// --- Extracted Metadata ---
+
+/// Everything the scrapers managed to find out about a page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArticleMetadata {
+    #[serde(skip)]
+    pub strategy: ExtractionStrategy,
+    // A DOI the server advertised for the page in a `Link` header, worth resolving directly.
+    #[serde(skip)]
+    pub canonical_doi: Option<String>,
+    // A remark written as a `%` comment line above the entry, e.g. about missing data.
+    #[serde(skip)]
+    pub comment: Option<String>,
+    pub entry_type: BibtexEntryType,
+    pub title: String,
+    pub author: String,
+    pub year: String,
+    pub publisher: String,
+    // The publisher's location, for styles that print it.
+    pub address: Option<String>,
+    pub orcid: Option<Vec<String>>,
+    pub doi: Option<String>,
+    // The arXiv identifier and primary category of a preprint, e.g. "1706.03762" and "cs.CL".
+    pub eprint: Option<String>,
+    pub eprint_class: Option<String>,
+    // A link to a free full-text PDF, emitted as the custom `pdf` field.
+    pub pdf: Option<String>,
+    pub license: Option<String>,
+    // The BibTeX `type` field, e.g. "Dataset".
+    pub resource_type: Option<String>,
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    pub keywords: Vec<String>,
+    // Extra context prepended to the access date in the `note` field.
+    pub note: Option<String>,
+    pub journal: Option<String>,
+    pub volume: Option<String>,
+    // The journal issue, emitted as `number`.
+    pub number: Option<String>,
+    pub booktitle: Option<String>,
+    // The series a volume belongs to, e.g. a conference's yearly proceedings.
+    pub series: Option<String>,
+    pub editor: Option<String>,
+    // The body behind a software manual, e.g. the project that publishes the docs.
+    pub organization: Option<String>,
+    // The body that issued a technical report or standard, e.g. "IETF".
+    pub institution: Option<String>,
+    pub chapter: Option<String>,
+    pub isbn: Option<String>,
+    // A journal's print and electronic ISSNs, and the linking ISSN (ISSN-L) that groups
+    // its editions together.
+    pub issn: Option<String>,
+    pub eissn: Option<String>,
+    pub issn_l: Option<String>,
+    // PubMed and PubMed Central IDs, e.g. "31452104" and "PMC6711352".
+    pub pmid: Option<String>,
+    pub pmcid: Option<String>,
+    // Total page count of a book, emitted as `numpages`.
+    pub pages: Option<u32>,
+    // First and last page of an article or paper, e.g. "123--135".
+    pub page_range: Option<String>,
+    // Whether the publisher marks the full text as free to read, when it says either way.
+    pub open_access: Option<bool>,
+    // The page's declared language as a BCP 47 tag, e.g. "en" or "pt-BR".
+    pub language: Option<String>,
+    // Article version on journals that publish revisions under new DOIs, e.g. F1000Research.
+    pub version: Option<String>,
+}
+
+/// The JSON type a metadata field is serialized as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    String,
+    OptionalString,
+    OptionalInteger,
+    OptionalBoolean,
+    Strings,
+    OptionalStrings,
+}
+
+impl ArticleMetadata {
+    /// Every serialized field, by its JSON name, with its type. Descriptions of the
+    /// metadata, such as the API's JSON Schema, are built from this list.
+    pub const FIELDS: &'static [(&'static str, FieldType)] = &[
+        ("entry_type", FieldType::String),
+        ("title", FieldType::String),
+        ("author", FieldType::String),
+        ("year", FieldType::String),
+        ("publisher", FieldType::String),
+        ("address", FieldType::OptionalString),
+        ("orcid", FieldType::OptionalStrings),
+        ("doi", FieldType::OptionalString),
+        ("eprint", FieldType::OptionalString),
+        ("eprint_class", FieldType::OptionalString),
+        ("pdf", FieldType::OptionalString),
+        ("license", FieldType::OptionalString),
+        ("resource_type", FieldType::OptionalString),
+        ("abstract", FieldType::OptionalString),
+        ("keywords", FieldType::Strings),
+        ("note", FieldType::OptionalString),
+        ("journal", FieldType::OptionalString),
+        ("volume", FieldType::OptionalString),
+        ("number", FieldType::OptionalString),
+        ("booktitle", FieldType::OptionalString),
+        ("series", FieldType::OptionalString),
+        ("editor", FieldType::OptionalString),
+        ("organization", FieldType::OptionalString),
+        ("institution", FieldType::OptionalString),
+        ("chapter", FieldType::OptionalString),
+        ("isbn", FieldType::OptionalString),
+        ("issn", FieldType::OptionalString),
+        ("eissn", FieldType::OptionalString),
+        ("issn_l", FieldType::OptionalString),
+        ("pmid", FieldType::OptionalString),
+        ("pmcid", FieldType::OptionalString),
+        ("pages", FieldType::OptionalInteger),
+        ("page_range", FieldType::OptionalString),
+        ("open_access", FieldType::OptionalBoolean),
+        ("language", FieldType::OptionalString),
+        ("version", FieldType::OptionalString),
+    ];
+
+    /// Human-readable notes about fields the scrapers could not find.
+    pub fn warnings(&self) -> Vec<String> {
+        [
+            ("title", &self.title),
+            ("author", &self.author),
+            ("year", &self.year),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.is_empty())
+        .map(|(name, _)| format!("missing {}", name))
+        .collect()
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+// --- Output Modes ---
+
+/// The citation dialect an entry is written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    #[default]
+    Bibtex,
+    Biblatex,
+    /// BibLaTeX, with arXiv preprints cited as `@online` entries.
+    #[serde(rename = "biblatex_arxiv")]
+    BiblatexArxiv,
+}
+
+impl OutputMode {
+    pub const ALL: &'static [OutputMode] = &[
+        OutputMode::Bibtex,
+        OutputMode::Biblatex,
+        OutputMode::BiblatexArxiv,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputMode::Bibtex => "bibtex",
+            OutputMode::Biblatex => "biblatex",
+            OutputMode::BiblatexArxiv => "biblatex_arxiv",
+        }
+    }
+
+    /// Whether entries use BibLaTeX's fields and types rather than classic BibTeX's.
+    pub fn is_biblatex(&self) -> bool {
+        matches!(self, OutputMode::Biblatex | OutputMode::BiblatexArxiv)
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+// --- Extraction Strategies ---
+
+/// The methods the extractor tries, in order of preference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExtractionStrategy {
+    #[serde(rename = "doi")]
+    DoiContentNegotiation,
+    #[serde(rename = "site_api")]
+    SiteApi,
+    #[serde(rename = "json_ld")]
+    JsonLd,
+    #[default]
+    #[serde(rename = "meta_tags")]
+    MetaTags,
+}
+
+impl ExtractionStrategy {
+    pub const ALL: &'static [ExtractionStrategy] = &[
+        ExtractionStrategy::DoiContentNegotiation,
+        ExtractionStrategy::SiteApi,
+        ExtractionStrategy::JsonLd,
+        ExtractionStrategy::MetaTags,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExtractionStrategy::DoiContentNegotiation => "doi",
+            ExtractionStrategy::SiteApi => "site_api",
+            ExtractionStrategy::JsonLd => "json_ld",
+            ExtractionStrategy::MetaTags => "meta_tags",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ExtractionStrategy::DoiContentNegotiation => {
+                "Asks doi.org for BibTeX directly when the URL is a DOI link."
+            }
+            ExtractionStrategy::SiteApi => {
+                "Queries the repository's own API for known hosts such as Figshare and Dryad."
+            }
+            ExtractionStrategy::JsonLd => {
+                "Reads Schema.org Article, NewsArticle and BlogPosting JSON-LD blocks."
+            }
+            ExtractionStrategy::MetaTags => {
+                "Falls back to OpenGraph, author and <title> tags in the page head."
+            }
+        }
+    }
+}
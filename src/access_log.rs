@@ -0,0 +1,242 @@
+use axum::{
+    body::HttpBody,
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Local};
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// --- Access Log ---
+
+/// How `BIBTEXTER_LOG_FORMAT` asks for each request to be logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Json,
+    Combined,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(LogFormat::Json),
+            "combined" => Ok(LogFormat::Combined),
+            other => Err(format!(
+                "Unknown log format '{}', expected json or combined",
+                other
+            )),
+        }
+    }
+}
+
+impl LogFormat {
+    /// Reads `BIBTEXTER_LOG_FORMAT`. Access logging is off when it is unset.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        match std::env::var("BIBTEXTER_LOG_FORMAT") {
+            Ok(format) => format.trim().parse().map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Everything recorded about one request.
+#[derive(Debug, Clone)]
+pub struct AccessLogEntry {
+    pub timestamp: DateTime<Local>,
+    pub client_ip: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub protocol: String,
+    pub status: u16,
+    pub size: Option<u64>,
+    pub duration: Duration,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl AccessLogEntry {
+    /// Formats the entry as an Apache Combined Log Format line, with the response
+    /// time in microseconds appended as Apache's `%D` would.
+    pub fn to_combined(&self) -> String {
+        let target = match &self.query {
+            Some(query) => format!("{}?{}", self.path, query),
+            None => self.path.clone(),
+        };
+        format!(
+            "{} - - [{}] \"{} {} {}\" {} {} \"{}\" \"{}\" {}",
+            self.client_ip.as_deref().unwrap_or("-"),
+            self.timestamp.format("%d/%b/%Y:%H:%M:%S %z"),
+            self.method,
+            target,
+            self.protocol,
+            self.status,
+            self.size.map_or("-".to_string(), |size| size.to_string()),
+            self.referer.as_deref().unwrap_or("-"),
+            self.user_agent.as_deref().unwrap_or("-"),
+            self.duration.as_micros()
+        )
+    }
+
+    /// Formats the entry as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "timestamp": self.timestamp.to_rfc3339(),
+            "client_ip": self.client_ip,
+            "method": self.method,
+            "path": self.path,
+            "query": self.query,
+            "status": self.status,
+            "size": self.size,
+            "duration_ms": self.duration.as_secs_f64() * 1000.0,
+            "referer": self.referer,
+            "user_agent": self.user_agent,
+        })
+        .to_string()
+    }
+}
+
+/// Middleware that writes one access log line per request to stdout.
+pub async fn access_log(State(format): State<LogFormat>, req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let timestamp = Local::now();
+    let client_ip = client_ip(
+        req.headers(),
+        req.extensions().get::<ConnectInfo<SocketAddr>>(),
+        &TrustedProxies::from_env(),
+    );
+    let method = req.method().to_string();
+    let path = req.uri().path().to_string();
+    let query = req.uri().query().map(str::to_string);
+    let protocol = format!("{:?}", req.version());
+    let referer = header_text(req.headers(), header::REFERER);
+    let user_agent = header_text(req.headers(), header::USER_AGENT);
+
+    let response = next.run(req).await;
+
+    let entry = AccessLogEntry {
+        timestamp,
+        client_ip,
+        method,
+        path,
+        query,
+        protocol,
+        status: response.status().as_u16(),
+        size: response.body().size_hint().exact(),
+        duration: started.elapsed(),
+        referer,
+        user_agent,
+    };
+    let line = match format {
+        LogFormat::Json => entry.to_json(),
+        LogFormat::Combined => entry.to_combined(),
+    };
+    // Locking keeps each line intact when requests finish concurrently.
+    let _ = writeln!(std::io::stdout().lock(), "{}", line);
+
+    response
+}
+
+/// The proxies whose `X-Forwarded-For` headers are believed, from
+/// `BIBTEXTER_TRUSTED_PROXIES`: addresses or CIDR ranges separated by commas, e.g.
+/// `10.0.0.0/8, fdaa::/16`. None are trusted when it is unset.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    pub fn from_env() -> Self {
+        std::env::var("BIBTEXTER_TRUSTED_PROXIES")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Reads a list like that in `BIBTEXTER_TRUSTED_PROXIES`. Malformed entries are
+    /// skipped with a warning.
+    pub fn parse(value: &str) -> Self {
+        let networks = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let network = parse_network(entry);
+                if network.is_none() {
+                    eprintln!("-> Warning: ignoring trusted proxy '{}'.", entry);
+                }
+                network
+            })
+            .collect();
+        Self { networks }
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks
+            .iter()
+            .any(|&(network, prefix)| in_network(ip, network, prefix))
+    }
+}
+
+// An address alone is a network of just that address.
+fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = match entry.split_once('/') {
+        Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse().ok()?)),
+        None => (entry.parse::<IpAddr>().ok()?, None),
+    };
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((address, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+/// The originating client. This is the peer address of the connection, unless that
+/// peer is a trusted proxy: then `X-Forwarded-For` is read from the right, skipping
+/// the hops added by trusted proxies, since anything further left may have been
+/// written by the client itself.
+pub fn client_ip(
+    headers: &HeaderMap,
+    connect_info: Option<&ConnectInfo<SocketAddr>>,
+    trusted: &TrustedProxies,
+) -> Option<String> {
+    let peer = connect_info.map(|ConnectInfo(addr)| addr.ip())?;
+    if !trusted.contains(peer) {
+        return Some(peer.to_string());
+    }
+    let forwarded = header_text(headers, "x-forwarded-for").unwrap_or_default();
+    let mut client = peer.to_string();
+    for hop in forwarded.rsplit(',').map(str::trim) {
+        match hop.parse::<IpAddr>() {
+            Ok(ip) if trusted.contains(ip) => client = ip.to_string(),
+            Ok(ip) => return Some(ip.to_string()),
+            // A hop that isn't an address can't be trusted, nor anything left of it.
+            Err(_) => break,
+        }
+    }
+    Some(client)
+}
+
+fn header_text(headers: &HeaderMap, name: impl header::AsHeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
@@ -0,0 +1,887 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::AppState;
+use crate::bib_json::{FieldFormat, JsonEntry, bibtex_to_json};
+use crate::bibtex::{BibtexEntryType, OutputMode, compact_bibtex, filter_bibtex_fields};
+use crate::cite::{CitationStyle, format_citation};
+use crate::crossref::{DoiMetadata, doi_metadata};
+use crate::dedupe::{BatchEntry, deduplicate_entries};
+use crate::doi_check::{DoiCheck, check_doi};
+use crate::doi_from_url::find_doi;
+use crate::enrich::{enrich_bib, max_enrich_entries};
+use crate::entries::{EntryFilter, citation_key};
+use crate::error::AppError;
+use crate::extract::{
+    ArticleMetadata, ExtractionStrategy, FieldType, fetch_and_generate_entry, fetch_metadata,
+    require_real_title,
+};
+use crate::isbn::bibtex_from_isbn;
+use crate::journals::{JournalName, expand_journal_abbreviations, resolve_abbreviation};
+use crate::pdf::bibtex_from_pdf;
+use crate::preflight::{blocked_domains, preflight};
+use crate::rate_limit::require_admin;
+use crate::rfc::bibtex_for_rfc;
+use crate::ris::bibtex_to_ris;
+use crate::routes::BibtexQuery;
+use crate::search::{SearchResult, match_title, search_papers};
+use crate::similar::{SimilarPaper, find_similar};
+use crate::sites::ads::ads_api_token;
+use crate::sites::google_scholar::{
+    DEFAULT_SCHOLAR_LIMIT, scholar_enabled, scholar_profile_bibtex,
+};
+use crate::sites::known::{KNOWN_SITES, KnownSite};
+use crate::sites::ssrn::elsevier_api_key;
+use crate::smoke::{SmokeReport, random_smoke_test_url, run_smoke_test};
+use crate::stats::StatsResponse;
+use crate::wayback::wayback_enabled;
+use crate::wikidata::bibtex_from_wikidata;
+
+// --- JSON API ---
+
+// Formats entries can be exported in besides the output modes, e.g. by
+// `/api/export-ris-batch`.
+const EXPORT_FORMATS: &[&str] = &["ris"];
+
+#[derive(Serialize)]
+pub struct FormatsResponse {
+    version: &'static str,
+    output_formats: Vec<&'static str>,
+    entry_types: Vec<EntryTypeInfo>,
+    strategies: Vec<StrategyInfo>,
+}
+
+#[derive(Serialize)]
+pub struct VersionResponse {
+    version: &'static str,
+    git_commit: &'static str,
+    build_date: &'static str,
+    features: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct EntryTypeInfo {
+    name: &'static str,
+    required_fields: &'static [&'static str],
+    optional_fields: &'static [&'static str],
+}
+
+#[derive(Serialize)]
+struct StrategyInfo {
+    name: &'static str,
+    description: &'static str,
+}
+
+// Query parameters for /api/similar; one of `doi` or `url` is required.
+#[derive(Deserialize)]
+pub struct SimilarQuery {
+    doi: Option<String>,
+    url: Option<String>,
+}
+
+// Query parameters for /api/search; `limit` defaults to 10.
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    q: String,
+    #[serde(default = "default_search_limit")]
+    limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    10
+}
+
+// Query parameters for /api/cite; `style` defaults to APA.
+#[derive(Deserialize)]
+pub struct CiteQuery {
+    url: String,
+    #[serde(default)]
+    style: CitationStyle,
+}
+
+// Query parameters for /api/doi-from-url.
+#[derive(Deserialize)]
+pub struct DoiFromUrlQuery {
+    url: String,
+}
+
+// Query parameters for /api/bibtex-from-isbn; the ISBN itself is the request body.
+#[derive(Deserialize)]
+pub struct IsbnQuery {
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+// Query parameters for GET /api/bibtex-from-isbn, e.g. `isbn=0-262-03384-4`.
+#[derive(Deserialize)]
+pub struct IsbnLookupQuery {
+    isbn: String,
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+// Query parameters for /api/scholar-profile, e.g.
+// `url=https://scholar.google.com/citations?user=abc123&limit=10`.
+#[derive(Deserialize)]
+pub struct ScholarProfileQuery {
+    url: String,
+    #[serde(default = "default_scholar_limit")]
+    limit: usize,
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+fn default_scholar_limit() -> usize {
+    DEFAULT_SCHOLAR_LIMIT
+}
+
+// Query parameters for /api/bibtex-from-wikidata, e.g. `wikidata=Q12345`.
+#[derive(Deserialize)]
+pub struct WikidataQuery {
+    wikidata: String,
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+// Query parameters for /api/bibtex-for-rfc, e.g. `rfc=8574` or `rfc=RFC8574`.
+#[derive(Deserialize)]
+pub struct RfcQuery {
+    rfc: String,
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+// Query parameters for /api/bibtex-from-title; `author` and `year` narrow the search.
+#[derive(Deserialize)]
+pub struct TitleQuery {
+    title: String,
+    author: Option<String>,
+    year: Option<u16>,
+    #[serde(default)]
+    mode: OutputMode,
+}
+
+// Query parameters for /api/bibtex-to-json; `format=plain_text` strips LaTeX markup.
+#[derive(Deserialize)]
+pub struct BibtexToJsonQuery {
+    #[serde(default)]
+    format: FieldFormat,
+}
+
+// Query parameters for /api/export-bibtex-batch and /api/export-ris-batch, e.g.
+// `after=2025-01-01`, `strategy=doi` or `entry_type=article`.
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    after: Option<String>,
+    strategy: Option<ExtractionStrategy>,
+    entry_type: Option<BibtexEntryType>,
+}
+
+impl ExportQuery {
+    fn filter(self) -> Result<EntryFilter, AppError> {
+        let after = self
+            .after
+            .map(|after| {
+                NaiveDate::parse_from_str(&after, "%Y-%m-%d").map_err(|_| {
+                    AppError::InvalidInput(format!("'{}' is not a YYYY-MM-DD date.", after))
+                })
+            })
+            .transpose()?;
+        Ok(EntryFilter {
+            after,
+            strategy: self.strategy,
+            entry_type: self.entry_type,
+        })
+    }
+}
+
+// Query parameters for /api/check-doi.
+#[derive(Deserialize)]
+pub struct CheckDoiQuery {
+    doi: String,
+}
+
+// Query parameters for /api/doi-metadata.
+#[derive(Deserialize)]
+pub struct DoiMetadataQuery {
+    doi: String,
+}
+
+// Query parameters for /api/resolve-abbreviation.
+#[derive(Deserialize)]
+pub struct AbbreviationQuery {
+    journal: String,
+}
+
+#[derive(Serialize)]
+pub struct PreviewResponse {
+    strategy: ExtractionStrategy,
+    fields: ArticleMetadata,
+    warnings: Vec<String>,
+}
+
+/// Lists the output formats, entry types and extraction strategies this server supports.
+pub async fn formats_handler() -> Json<FormatsResponse> {
+    Json(FormatsResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        output_formats: OutputMode::ALL
+            .iter()
+            .map(OutputMode::as_str)
+            .chain(EXPORT_FORMATS.iter().copied())
+            .collect(),
+        entry_types: BibtexEntryType::ALL
+            .iter()
+            .map(|entry_type| EntryTypeInfo {
+                name: entry_type.as_str(),
+                required_fields: entry_type.required_fields(),
+                optional_fields: entry_type.optional_fields(),
+            })
+            .collect(),
+        strategies: ExtractionStrategy::ALL
+            .iter()
+            .map(|strategy| StrategyInfo {
+                name: strategy.as_str(),
+                description: strategy.description(),
+            })
+            .collect(),
+    })
+}
+
+/// Serves a JSON Schema describing the request and response bodies of the API.
+/// The output modes and strategies come from the same lists as `/api/formats`, and
+/// the metadata fields from `ArticleMetadata::FIELDS`.
+pub async fn schema_handler() -> Json<serde_json::Value> {
+    let modes: Vec<&str> = OutputMode::ALL.iter().map(OutputMode::as_str).collect();
+    let strategies: Vec<&str> = ExtractionStrategy::ALL
+        .iter()
+        .map(ExtractionStrategy::as_str)
+        .collect();
+    let metadata_fields: serde_json::Map<String, serde_json::Value> = ArticleMetadata::FIELDS
+        .iter()
+        .map(|&(name, field_type)| (name.to_string(), field_schema(field_type)))
+        .collect();
+    Json(serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "bibtexter API",
+        "$defs": {
+            "GetBibtexQuery": {
+                "description": "Query parameters of GET /get_bibtex, GET /api/bibtex and GET /api/preview.",
+                "type": "object",
+                "properties": {
+                    "url": { "type": "string", "format": "uri" },
+                    "mode": { "type": "string", "enum": modes, "default": OutputMode::default().as_str() },
+                    "expand_journal_abbrevs": { "type": "boolean", "default": false },
+                    "pretty": { "type": "boolean", "default": true },
+                    "strict": { "type": "boolean", "default": false },
+                    "include_fields": {
+                        "description": "Comma-separated fields to keep; all others are dropped.",
+                        "type": "string"
+                    },
+                    "exclude_fields": {
+                        "description": "Comma-separated fields to drop.",
+                        "type": "string"
+                    }
+                },
+                "required": ["url"]
+            },
+            "PreviewResponse": {
+                "description": "Response body of GET /api/preview.",
+                "type": "object",
+                "properties": {
+                    "strategy": { "type": "string", "enum": strategies },
+                    "fields": { "type": "object", "properties": metadata_fields },
+                    "warnings": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    }
+                },
+                "required": ["strategy", "fields", "warnings"]
+            },
+            "DoiMetadata": {
+                "description": "Response body of GET /api/doi-metadata.",
+                "type": "object",
+                "properties": {
+                    "doi": { "type": "string" },
+                    "url": { "type": ["string", "null"] },
+                    "type": { "type": ["string", "null"] },
+                    "title": { "type": ["string", "null"] },
+                    "authors": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "given": { "type": ["string", "null"] },
+                                "family": { "type": ["string", "null"] },
+                                "name": { "type": ["string", "null"] },
+                                "orcid": { "type": ["string", "null"] }
+                            },
+                            "required": ["given", "family", "name", "orcid"]
+                        }
+                    },
+                    "published": { "type": ["string", "null"] },
+                    "year": { "type": ["integer", "null"] },
+                    "container_title": { "type": ["string", "null"] },
+                    "volume": { "type": ["string", "null"] },
+                    "issue": { "type": ["string", "null"] },
+                    "page": { "type": ["string", "null"] },
+                    "publisher": { "type": ["string", "null"] },
+                    "subjects": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "abstract": { "type": ["string", "null"] },
+                    "issn": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    }
+                },
+                "required": [
+                    "doi", "url", "type", "title", "authors", "published", "year",
+                    "container_title", "volume", "issue", "page", "publisher", "subjects",
+                    "abstract", "issn"
+                ]
+            },
+            "FormatsResponse": {
+                "description": "Response body of GET /api/formats.",
+                "type": "object",
+                "properties": {
+                    "version": { "type": "string" },
+                    "output_formats": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "entry_types": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "required_fields": {
+                                    "type": "array",
+                                    "items": { "type": "string" }
+                                },
+                                "optional_fields": {
+                                    "type": "array",
+                                    "items": { "type": "string" }
+                                }
+                            },
+                            "required": ["name", "required_fields", "optional_fields"]
+                        }
+                    },
+                    "strategies": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "description": { "type": "string" }
+                            },
+                            "required": ["name", "description"]
+                        }
+                    }
+                },
+                "required": ["version", "output_formats", "entry_types", "strategies"]
+            }
+        }
+    }))
+}
+
+fn field_schema(field_type: FieldType) -> serde_json::Value {
+    match field_type {
+        FieldType::String => serde_json::json!({ "type": "string" }),
+        FieldType::OptionalString => serde_json::json!({ "type": ["string", "null"] }),
+        FieldType::OptionalInteger => serde_json::json!({ "type": ["integer", "null"] }),
+        FieldType::OptionalBoolean => serde_json::json!({ "type": ["boolean", "null"] }),
+        FieldType::Strings => serde_json::json!({ "type": "array", "items": { "type": "string" } }),
+        FieldType::OptionalStrings => {
+            serde_json::json!({ "type": ["array", "null"], "items": { "type": "string" } })
+        }
+    }
+}
+
+/// Returns the scraped metadata for a URL so it can be reviewed before generating BibTeX.
+pub async fn preview_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BibtexQuery>,
+) -> Result<Json<PreviewResponse>, AppError> {
+    let started = Instant::now();
+    let result = fetch_metadata(&state.client, &query.url).await;
+    state.stats.lock().unwrap().record(
+        &query.url,
+        result.as_ref().map(|metadata| metadata.strategy),
+        started.elapsed(),
+    );
+    let mut metadata = result?;
+    if query.expand_journal_abbrevs
+        && let Some(journal) = metadata.journal.as_deref().and_then(resolve_abbreviation)
+    {
+        metadata.journal = Some(journal.full);
+    }
+
+    Ok(Json(PreviewResponse {
+        strategy: metadata.strategy,
+        warnings: metadata.warnings(),
+        fields: metadata,
+    }))
+}
+
+/// Returns a formatted reference for a URL as plain text, for pasting into a document.
+pub async fn cite_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CiteQuery>,
+) -> Result<String, AppError> {
+    let started = Instant::now();
+    let result = fetch_metadata(&state.client, &query.url).await;
+    state.stats.lock().unwrap().record(
+        &query.url,
+        result.as_ref().map(|metadata| metadata.strategy),
+        started.elapsed(),
+    );
+    let metadata = result?;
+    if metadata.title.is_empty() {
+        return Err(AppError::ExtractionError(
+            "Could not find a title for the page.".into(),
+        ));
+    }
+    Ok(format_citation(&metadata, Some(&query.url), query.style))
+}
+
+/// Returns the BibTeX entry for a URL as plain text, for scripts and API clients.
+pub async fn bibtex_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BibtexQuery>,
+) -> Result<String, AppError> {
+    let started = Instant::now();
+    let result = fetch_and_generate_entry(&state.client, &query.url, query.mode)
+        .await
+        .and_then(|entry| require_real_title(entry, query.strict));
+    state.stats.lock().unwrap().record(
+        &query.url,
+        result.as_ref().map(|entry| entry.strategy),
+        started.elapsed(),
+    );
+    let entry = result?;
+    let (mut bibtex, strategy) = (entry.bibtex, entry.strategy);
+    if query.expand_journal_abbrevs {
+        bibtex = expand_journal_abbreviations(&bibtex);
+    }
+    bibtex = filter_bibtex_fields(&bibtex, &query.field_filter());
+    if !query.pretty {
+        bibtex = compact_bibtex(&bibtex);
+    }
+    state
+        .entries
+        .lock()
+        .unwrap()
+        .insert_extracted(&bibtex, Some(strategy));
+    Ok(bibtex)
+}
+
+/// Answers `HEAD /api/bibtex` with a quick guess at whether the GET would succeed,
+/// in `X-Extractable`, `X-Recommended-Strategy` and `X-Domain-Blocked` headers.
+pub async fn bibtex_preflight_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BibtexQuery>,
+) -> Result<Response, AppError> {
+    let preflight = preflight(&state.client, &state.robots, &query.url).await?;
+    Ok((
+        [
+            ("x-extractable", preflight.extractable.to_string()),
+            (
+                "x-recommended-strategy",
+                preflight.strategy.as_str().to_string(),
+            ),
+            ("x-domain-blocked", preflight.domain_blocked.to_string()),
+        ],
+        (),
+    )
+        .into_response())
+}
+
+/// Serves a previously generated entry as a downloadable `.bib` file, for build scripts
+/// that `curl .../api/bibtex-file/Doe2025Title.bib >> refs.bib`. Citation keys are easy
+/// to guess and entries may be anyone's, so only the admin token can fetch them.
+pub async fn bibtex_file_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(file): Path<String>,
+) -> Result<Response, AppError> {
+    require_admin(&headers)?;
+    let key = file.strip_suffix(".bib").unwrap_or(&file);
+    let entries = state.entries.lock().unwrap();
+    let Some(bibtex) = entries.get(key) else {
+        return Err(AppError::ExtractionError(format!(
+            "No entry with citation key '{}' has been generated yet.",
+            key
+        )));
+    };
+
+    Ok(attachment(
+        "application/x-bibtex; charset=utf-8",
+        &format!("{}.bib", key),
+        format!("{}\n", bibtex),
+    ))
+}
+
+/// Serves every stored entry that passes the query's filters as one `.bib` file, for
+/// backing up the references generated on a self-hosted server. The entries are
+/// everyone's, so only the admin token can export them.
+pub async fn export_bibtex_batch_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&headers)?;
+    let (bibtex, merged) = exported_bibtex(&state, query)?;
+    Ok(with_duplicates_merged(
+        attachment(
+            "application/x-bibtex; charset=utf-8",
+            "bibtexter-export.bib",
+            bibtex,
+        ),
+        merged,
+    ))
+}
+
+/// Serves the same entries as [`export_bibtex_batch_handler`] as one `.ris` file.
+pub async fn export_ris_batch_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&headers)?;
+    let (bibtex, merged) = exported_bibtex(&state, query)?;
+    let ris = if bibtex.is_empty() {
+        String::new()
+    } else {
+        bibtex_to_ris(&bibtex)?
+    };
+    Ok(with_duplicates_merged(
+        attachment(
+            "application/x-research-info-systems; charset=utf-8",
+            "bibtexter-export.ris",
+            ris,
+        ),
+        merged,
+    ))
+}
+
+/// The stored entries that pass the query's filters, oldest first, as one `.bib` file,
+/// and how many were dropped as duplicates. The same work stored under two citation
+/// keys, say from its DOI and from its publisher's page, is exported once.
+fn exported_bibtex(state: &AppState, query: ExportQuery) -> Result<(String, usize), AppError> {
+    let filter = query.filter()?;
+    let stored: Vec<BatchEntry> = state
+        .entries
+        .lock()
+        .unwrap()
+        .matching(&filter)
+        .iter()
+        .map(|entry| BatchEntry {
+            url: citation_key(&entry.bibtex).unwrap_or_default().to_string(),
+            bibtex: entry.bibtex.clone(),
+        })
+        .collect();
+    let batch = deduplicate_entries(stored);
+    let merged = batch
+        .duplicates_merged
+        .iter()
+        .map(|group| group.len() - 1)
+        .sum();
+    let bibtex = batch
+        .entries
+        .iter()
+        .map(|entry| format!("{}\n", entry.bibtex))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok((bibtex, merged))
+}
+
+// Reports the count in `X-Duplicates-Merged`, since the body is the file itself.
+fn with_duplicates_merged(mut response: Response, merged: usize) -> Response {
+    response
+        .headers_mut()
+        .insert("x-duplicates-merged", merged.into());
+    response
+}
+
+fn attachment(content_type: &'static str, filename: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Lists the sites extraction has been tested against, with how good their entries
+/// usually are, so users know what to expect before submitting a URL.
+pub async fn known_sites_handler() -> Json<&'static [KnownSite]> {
+    Json(KNOWN_SITES)
+}
+
+/// Reports the server's version, the commit and date it was built from, and which
+/// optional features are switched on in its environment.
+pub async fn version_handler() -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("BIBTEXTER_GIT_COMMIT"),
+        build_date: env!("BIBTEXTER_BUILD_DATE"),
+        features: enabled_features(),
+    })
+}
+
+/// The features this server offers: the lookups every instance has, then those that
+/// depend on configuration, each only if its environment variable enables it.
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features = vec![
+        "doi_lookup",
+        "crossref",
+        "semantic_scholar",
+        "europe_pmc",
+        "openalex",
+        "search",
+        "isbn",
+        "rfc",
+        "wikidata",
+        "cite",
+        "oai_pmh",
+        "pdf",
+        "enrich",
+    ];
+    let configured = [
+        (
+            "unpaywall",
+            std::env::var("BIBTEXTER_UNPAYWALL_EMAIL").is_ok(),
+        ),
+        ("elsevier", elsevier_api_key().is_some()),
+        ("ads", ads_api_token().is_some()),
+        ("wayback", wayback_enabled()),
+        ("scholar", scholar_enabled()),
+        ("domain_blocklist", !blocked_domains().is_empty()),
+    ];
+    features.extend(
+        configured
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(feature, _)| feature),
+    );
+    features
+}
+
+/// Extracts a known site's example page, or a DOI link, picked at random, and reports
+/// how it went, so operators can check a deployment end to end. Needs the admin token.
+pub async fn random_bibtex_test_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SmokeReport>, AppError> {
+    require_admin(&headers)?;
+    let url = random_smoke_test_url();
+    eprintln!("-> Smoke testing extraction with {}.", url);
+    Ok(Json(run_smoke_test(&state.client, url).await))
+}
+
+/// Reports request counts, strategy latencies, popular domains and error rates since startup.
+/// The domains show what other users are citing, so only the admin token can read them.
+pub async fn stats_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<StatsResponse>, AppError> {
+    require_admin(&headers)?;
+    Ok(Json(state.stats.lock().unwrap().snapshot()))
+}
+
+/// Generates BibTeX for a PDF posted as the raw request body (`Content-Type: application/pdf`).
+pub async fn extract_pdf_handler(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<String, AppError> {
+    bibtex_from_pdf(&state.client, &body).await
+}
+
+/// Generates a `@book` entry for the ISBN posted as the raw request body.
+pub async fn bibtex_from_isbn_handler(
+    State(state): State<AppState>,
+    Query(query): Query<IsbnQuery>,
+    body: String,
+) -> Result<String, AppError> {
+    bibtex_from_isbn(&state.client, &body, query.mode).await
+}
+
+/// Generates a `@book` entry for the ISBN-10 or ISBN-13 in the `isbn` query parameter.
+pub async fn bibtex_from_isbn_query_handler(
+    State(state): State<AppState>,
+    Query(query): Query<IsbnLookupQuery>,
+) -> Result<String, AppError> {
+    bibtex_from_isbn(&state.client, &query.isbn, query.mode).await
+}
+
+/// Generates a `@techreport` entry for an IETF RFC from its number alone.
+pub async fn bibtex_for_rfc_handler(
+    State(state): State<AppState>,
+    Query(query): Query<RfcQuery>,
+) -> Result<String, AppError> {
+    bibtex_for_rfc(&state.client, &query.rfc, query.mode).await
+}
+
+/// Generates an entry for a Wikidata item from its `Q` ID.
+pub async fn bibtex_from_wikidata_handler(
+    State(state): State<AppState>,
+    Query(query): Query<WikidataQuery>,
+) -> Result<String, AppError> {
+    bibtex_from_wikidata(&state.client, &query.wikidata, query.mode).await
+}
+
+/// Lists a Google Scholar profile's publications as BibTeX entries, when the operator
+/// has opted in with `BIBTEXTER_ENABLE_SCHOLAR=true`.
+pub async fn scholar_profile_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ScholarProfileQuery>,
+) -> Result<String, AppError> {
+    scholar_profile_bibtex(&state.client, &query.url, query.limit, query.mode).await
+}
+
+/// Finds a paper in Crossref by its title and returns its BibTeX, with how closely the
+/// match's title agrees, from 0 to 1, in `X-Match-Confidence`.
+pub async fn bibtex_from_title_handler(
+    State(state): State<AppState>,
+    Query(query): Query<TitleQuery>,
+) -> Result<Response, AppError> {
+    let (paper, score) = match_title(
+        &state.client,
+        &query.title,
+        query.author.as_deref(),
+        query.year,
+    )
+    .await?;
+    let doi = paper.doi.unwrap_or_default();
+    eprintln!("-> Best title match is {} ({:.2}).", doi, score);
+
+    let entry = fetch_and_generate_entry(
+        &state.client,
+        &format!("https://doi.org/{}", doi),
+        query.mode,
+    )
+    .await?;
+    state
+        .entries
+        .lock()
+        .unwrap()
+        .insert_extracted(&entry.bibtex, Some(entry.strategy));
+    Ok((
+        [("x-match-confidence", format!("{:.2}", score))],
+        entry.bibtex,
+    )
+        .into_response())
+}
+
+/// Fills in missing fields of every entry in a `.bib` file posted as the raw request body.
+pub async fn enrich_bib_handler(
+    State(state): State<AppState>,
+    body: String,
+) -> Result<String, AppError> {
+    enrich_bib(&state.client, &body, max_enrich_entries()).await
+}
+
+/// Converts the entries of a BibTeX string posted as the raw request body to JSON.
+pub async fn bibtex_to_json_handler(
+    Query(query): Query<BibtexToJsonQuery>,
+    body: String,
+) -> Result<Json<Vec<JsonEntry>>, AppError> {
+    let entries = bibtex_to_json(&body)?;
+    Ok(Json(match query.format {
+        FieldFormat::Latex => entries,
+        FieldFormat::PlainText => entries
+            .into_iter()
+            .map(JsonEntry::into_plain_text)
+            .collect(),
+    }))
+}
+
+/// Finds just the DOI for a URL, with how confident the match is. Much cheaper than
+/// `/api/bibtex`: the page is fetched at most once and no entry is generated.
+pub async fn doi_from_url_handler(
+    State(state): State<AppState>,
+    Query(query): Query<DoiFromUrlQuery>,
+) -> Result<Response, AppError> {
+    Ok(match find_doi(&state.client, &query.url).await? {
+        Some(found) => Json(found).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no_doi_found" })),
+        )
+            .into_response(),
+    })
+}
+
+/// Reports whether a DOI is well-formed, resolves, is open access and is known to Crossref.
+pub async fn check_doi_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CheckDoiQuery>,
+) -> Json<DoiCheck> {
+    Json(check_doi(&state.client, &query.doi).await)
+}
+
+/// Returns Crossref's metadata for a DOI as JSON, for clients that want the fields
+/// rather than a formatted entry.
+pub async fn doi_metadata_handler(
+    State(state): State<AppState>,
+    Query(query): Query<DoiMetadataQuery>,
+) -> Result<Json<DoiMetadata>, AppError> {
+    doi_metadata(&state.client, &query.doi).await.map(Json)
+}
+
+/// Looks up the full name and ISSN of an abbreviated journal title, e.g. `J. ACM`.
+pub async fn resolve_abbreviation_handler(
+    Query(query): Query<AbbreviationQuery>,
+) -> Result<Json<JournalName>, AppError> {
+    resolve_abbreviation(&query.journal)
+        .map(Json)
+        .ok_or_else(|| {
+            AppError::ExtractionError(format!("Unknown journal abbreviation '{}'.", query.journal))
+        })
+}
+
+/// Lists papers related to the given DOI or URL, using Semantic Scholar's recommendations.
+pub async fn similar_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SimilarQuery>,
+) -> Result<Json<Vec<SimilarPaper>>, AppError> {
+    let paper_id = match (query.doi, query.url) {
+        (Some(doi), _) => format!("DOI:{}", doi),
+        (None, Some(url)) => match url.split_once("doi.org/") {
+            Some((_, doi)) => format!("DOI:{}", doi),
+            None => format!("URL:{}", url),
+        },
+        (None, None) => {
+            return Err(AppError::InvalidInput(
+                "Either a doi or a url query parameter is required.".into(),
+            ));
+        }
+    };
+
+    Ok(Json(find_similar(&state.client, &paper_id).await?))
+}
+
+/// Searches Crossref, Semantic Scholar and OpenAlex for papers matching `q`.
+pub async fn search_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<SearchResult>>, AppError> {
+    Ok(Json(
+        search_papers(&state.client, &query.q, query.limit).await?,
+    ))
+}
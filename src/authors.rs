@@ -0,0 +1,93 @@
+/// Removes duplicate names from an extracted author list.
+///
+/// Names are compared case- and punctuation-insensitively, with "Last, First"
+/// treated the same as "First Last", and an initial matches any given name
+/// starting with that letter. When two entries match, the more complete form
+/// (e.g. "Jane Doe" over "J. Doe") is kept in the position of the first one.
+pub fn normalize_authors(authors: Vec<String>) -> Vec<String> {
+    let mut kept: Vec<(String, NameParts)> = Vec::new();
+
+    for author in authors {
+        let author = author.trim().to_string();
+        let parts = NameParts::parse(&author);
+        if parts.family.is_empty() {
+            continue;
+        }
+
+        match kept
+            .iter_mut()
+            .find(|(_, existing)| existing.matches(&parts))
+        {
+            Some(entry) if parts.completeness() > entry.1.completeness() => {
+                *entry = (author, parts);
+            }
+            Some(_) => {}
+            None => kept.push((author, parts)),
+        }
+    }
+
+    kept.into_iter().map(|(author, _)| author).collect()
+}
+
+// A name broken into lowercase, punctuation-free given names and family name.
+struct NameParts {
+    given: Vec<String>,
+    family: String,
+}
+
+impl NameParts {
+    fn parse(name: &str) -> Self {
+        let words = |s: &str| -> Vec<String> {
+            s.split(|c: char| c.is_whitespace() || c == '.' || c == '-')
+                .map(|word| {
+                    word.chars()
+                        .filter(|c| c.is_alphanumeric())
+                        .flat_map(char::to_lowercase)
+                        .collect::<String>()
+                })
+                .filter(|word| !word.is_empty())
+                .collect()
+        };
+
+        let (given, family) = match name.split_once(',') {
+            Some((family, given)) => (words(given), words(family).join(" ")),
+            None => {
+                let mut given = words(name);
+                let family = given.pop().unwrap_or_default();
+                (given, family)
+            }
+        };
+        Self { given, family }
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.family == other.family
+            && self
+                .given
+                .iter()
+                .zip(&other.given)
+                .all(|(a, b)| a == b || initial_of(a, b) || initial_of(b, a))
+    }
+
+    fn completeness(&self) -> usize {
+        self.given.iter().map(String::len).sum::<usize>() + self.given.len()
+    }
+}
+
+// True when `initial` is a single letter that `name` starts with.
+fn initial_of(initial: &str, name: &str) -> bool {
+    initial.chars().count() == 1 && name.starts_with(initial)
+}
+
+/// Writes a name as "Last, First", the unambiguous BibTeX form. Names that already
+/// contain a comma are assumed to be in that form.
+pub fn last_first(name: &str) -> String {
+    let name = name.trim();
+    if name.contains(',') {
+        return name.to_string();
+    }
+    match name.rsplit_once(char::is_whitespace) {
+        Some((given, family)) => format!("{}, {}", family, given.trim_end()),
+        None => name.to_string(),
+    }
+}
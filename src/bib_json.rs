@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::enrich::{BibItem, parse_bib};
+use crate::error::AppError;
+use crate::latex::strip_latex_commands;
+
+// --- BibTeX to JSON ---
+
+/// A BibTeX entry as JSON, with `@string` macros expanded and `#` concatenations joined.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonEntry {
+    pub entry_type: String,
+    pub key: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+/// How field values are written in the JSON: as the LaTeX in the `.bib` file, or as
+/// plain text for use outside LaTeX.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldFormat {
+    #[default]
+    Latex,
+    PlainText,
+}
+
+// Fields holding identifiers, where `~` and `_` are part of the value rather than markup.
+const VERBATIM_FIELDS: &[&str] = &["url", "doi", "eprint", "file"];
+
+impl JsonEntry {
+    /// The entry with LaTeX markup stripped from its field values.
+    pub fn into_plain_text(mut self) -> Self {
+        for (name, value) in self.fields.iter_mut() {
+            if !VERBATIM_FIELDS.contains(&name.as_str()) {
+                *value = strip_latex_commands(value);
+            }
+        }
+        self
+    }
+}
+
+/// Converts every entry in a BibTeX string to JSON, in the order they appear.
+/// Comments, `@preamble` and `@comment` blocks are dropped.
+pub fn bibtex_to_json(input: &str) -> Result<Vec<JsonEntry>, AppError> {
+    let entries = parse_bib(input)
+        .into_iter()
+        .filter_map(|item| match item {
+            BibItem::Entry(entry) => Some(JsonEntry {
+                entry_type: entry.entry_type,
+                key: entry.key,
+                fields: entry.fields.into_iter().collect(),
+            }),
+            BibItem::Text(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        return Err(AppError::InvalidInput(
+            "No BibTeX entries found in the request body.".into(),
+        ));
+    }
+    Ok(entries)
+}
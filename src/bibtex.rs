@@ -0,0 +1,174 @@
+use crate::enrich::{BibItem, parse_bib};
+
+pub use bibtexter_core::{
+    BibtexEntry, BibtexEntryType, OutputMode, ValidationError, serialize_bibtex,
+};
+
+/// Maps a BCP 47 language tag (`en`, `pt-BR`, `zh_Hant`) to the BibLaTeX `langid`
+/// that selects its hyphenation patterns. Unknown languages fall back to `english`.
+pub fn bcp47_to_biblatex_langid(code: &str) -> &'static str {
+    let primary = code
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match primary.as_str() {
+        "ar" => "arabic",
+        "bg" => "bulgarian",
+        "ca" => "catalan",
+        "cs" => "czech",
+        "cy" => "welsh",
+        "da" => "danish",
+        "de" => "german",
+        "el" => "greek",
+        "es" => "spanish",
+        "et" => "estonian",
+        "eu" => "basque",
+        "fa" => "farsi",
+        "fi" => "finnish",
+        "fr" => "french",
+        "ga" => "irish",
+        "gl" => "galician",
+        "he" => "hebrew",
+        "hi" => "hindi",
+        "hr" => "croatian",
+        "hu" => "hungarian",
+        "id" => "indonesian",
+        "is" => "icelandic",
+        "it" => "italian",
+        "ja" => "japanese",
+        "ko" => "korean",
+        "la" => "latin",
+        "lt" => "lithuanian",
+        "lv" => "latvian",
+        "ms" => "malay",
+        "nb" | "nn" | "no" => "norwegian",
+        "nl" => "dutch",
+        "pl" => "polish",
+        "pt" => "portuguese",
+        "ro" => "romanian",
+        "ru" => "russian",
+        "sk" => "slovak",
+        "sl" => "slovene",
+        "sr" => "serbian",
+        "sv" => "swedish",
+        "th" => "thai",
+        "tr" => "turkish",
+        "uk" => "ukrainian",
+        "vi" => "vietnamese",
+        "zh" => "chinese",
+        _ => "english",
+    }
+}
+
+/// Rewrites finished entries, our own or doi.org's, in the compact one-line form of
+/// [`serialize_bibtex`]. Comment lines are kept on lines of their own, since anything
+/// after a `%` is ignored.
+pub fn compact_bibtex(bibtex: &str) -> String {
+    let mut lines = Vec::new();
+    for item in parse_bib(bibtex) {
+        match item {
+            BibItem::Entry(parsed) => match BibtexEntryType::from_name(&parsed.entry_type) {
+                Some(entry_type) => {
+                    let entry = parsed.fields.into_iter().fold(
+                        BibtexEntry::new(entry_type, parsed.key),
+                        |entry, (name, value)| entry.field(&name, value),
+                    );
+                    lines.push(serialize_bibtex(&entry, false));
+                }
+                // Types we don't build ourselves, such as `@phdthesis`, are only squeezed.
+                None => lines.push(parsed.raw.split_whitespace().collect::<Vec<_>>().join(" ")),
+            },
+            BibItem::Text(text) => lines.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            ),
+        }
+    }
+    lines.join("\n")
+}
+
+/// Which fields a caller wants in their entries, from comma-separated lists such as
+/// `include_fields=title,author,year` and `exclude_fields=abstract,keywords`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldFilter {
+    include: Option<Vec<String>>,
+    exclude: Vec<String>,
+}
+
+impl FieldFilter {
+    pub fn new(include: Option<&str>, exclude: Option<&str>) -> Self {
+        let names = |list: &str| {
+            list.split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>()
+        };
+        Self {
+            include: include.map(names).filter(|names| !names.is_empty()),
+            exclude: exclude.map(names).unwrap_or_default(),
+        }
+    }
+
+    /// Whether the filter lets every field through.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_empty()
+    }
+
+    /// Whether `name` survives: listed in `include_fields`, if given, and not in
+    /// `exclude_fields`.
+    pub fn keeps(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.contains(&name))
+            && !self.exclude.contains(&name)
+    }
+}
+
+/// Drops the fields `filter` leaves out of finished entries, our own or doi.org's.
+/// Entries that lose fields their type requires say which in a `%` comment above
+/// them. Types we don't build ourselves, such as `@phdthesis`, are left as they are.
+pub fn filter_bibtex_fields(bibtex: &str, filter: &FieldFilter) -> String {
+    if filter.is_empty() {
+        return bibtex.to_string();
+    }
+    let mut output = String::new();
+    for item in parse_bib(bibtex) {
+        match item {
+            BibItem::Entry(parsed) => match BibtexEntryType::from_name(&parsed.entry_type) {
+                Some(entry_type) => {
+                    let dropped: Vec<&str> = entry_type
+                        .required_fields()
+                        .iter()
+                        .copied()
+                        .filter(|name| parsed.field(name).is_some() && !filter.keeps(name))
+                        .collect();
+                    let mut entry = parsed
+                        .fields
+                        .into_iter()
+                        .filter(|(name, _)| filter.keeps(name))
+                        .fold(
+                            BibtexEntry::new(entry_type, parsed.key),
+                            |entry, (name, value)| entry.field(&name, value),
+                        );
+                    if !dropped.is_empty() {
+                        entry = entry.comment(format!(
+                            "WARNING: excluded fields required by @{}: {}",
+                            entry_type.as_str(),
+                            dropped.join(", ")
+                        ));
+                    }
+                    output.push_str(&serialize_bibtex(&entry, true));
+                }
+                None => output.push_str(&parsed.raw),
+            },
+            BibItem::Text(text) => output.push_str(&text),
+        }
+    }
+    output
+}
@@ -0,0 +1,411 @@
+use serde::Deserialize;
+
+use crate::bibtex::BibtexEntryType;
+use crate::extract::ArticleMetadata;
+use crate::latex::strip_latex_commands;
+
+// --- Formatted Citations ---
+
+/// Reference styles a citation can be formatted in.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CitationStyle {
+    /// APA, 7th edition.
+    #[default]
+    Apa,
+    /// MLA, 9th edition.
+    Mla,
+    /// The Chicago Manual of Style, 17th edition, notes and bibliography.
+    Chicago,
+    Ieee,
+}
+
+// A contributor's name split for styles that invert or abbreviate it.
+struct Name {
+    given: String,
+    family: String,
+}
+
+impl Name {
+    /// Reads "Last, First" or "First Last". Braced names such as `{World Health
+    /// Organization}` are organizations and have no given names.
+    fn parse(name: &str) -> Self {
+        let name = name.trim();
+        if name.starts_with('{') {
+            return Name {
+                given: String::new(),
+                family: strip_latex_commands(name),
+            };
+        }
+        let name = strip_latex_commands(name);
+        let (given, family) = match name.split_once(',') {
+            Some((family, given)) => (given.trim(), family.trim()),
+            None => name.rsplit_once(' ').unwrap_or(("", name.as_str())),
+        };
+        Name {
+            given: given.to_string(),
+            family: family.to_string(),
+        }
+    }
+
+    /// "J. A." for "Jane Alice"; hyphenated names keep their hyphen, "J.-P.".
+    fn initials(&self) -> String {
+        self.given
+            .split_whitespace()
+            .map(|word| {
+                word.split('-')
+                    .filter_map(|part| part.chars().next())
+                    .map(|initial| format!("{}.", initial))
+                    .collect::<Vec<_>>()
+                    .join("-")
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// "Doe, Jane"
+    fn inverted(&self) -> String {
+        join_nonempty(&[&self.family, &self.given], ", ")
+    }
+
+    /// "Jane Doe"
+    fn natural(&self) -> String {
+        join_nonempty(&[&self.given, &self.family], " ")
+    }
+
+    /// "Doe, J. A."
+    fn inverted_initials(&self) -> String {
+        join_nonempty(&[&self.family, &self.initials()], ", ")
+    }
+
+    /// "J. A. Doe"
+    fn initials_first(&self) -> String {
+        join_nonempty(&[&self.initials(), &self.family], " ")
+    }
+}
+
+fn join_nonempty(parts: &[&str], separator: &str) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// The pieces every style arranges, cleaned of LaTeX markup.
+struct Source {
+    authors: Vec<Name>,
+    year: Option<String>,
+    title: String,
+    // The journal, proceedings or book the work appeared in.
+    container: Option<String>,
+    // Who published a work that has no container, e.g. a book's publisher or a website.
+    publisher: Option<String>,
+    volume: Option<String>,
+    number: Option<String>,
+    pages: Option<String>,
+    // The DOI as a URL, or the page's own URL.
+    link: Option<String>,
+    is_book: bool,
+}
+
+impl Source {
+    fn new(metadata: &ArticleMetadata, url: Option<&str>) -> Self {
+        let text = |value: &str| Some(strip_latex_commands(value)).filter(|v| !v.is_empty());
+        let names = if metadata.author.is_empty() {
+            metadata.editor.as_deref().unwrap_or_default()
+        } else {
+            &metadata.author
+        };
+        Source {
+            authors: names
+                .split(" and ")
+                .filter(|name| !name.trim().is_empty())
+                .map(Name::parse)
+                .collect(),
+            year: text(&metadata.year),
+            title: strip_latex_commands(&metadata.title),
+            container: metadata
+                .journal
+                .as_deref()
+                .or(metadata.booktitle.as_deref())
+                .and_then(text),
+            publisher: text(&metadata.publisher),
+            volume: metadata.volume.as_deref().and_then(text),
+            number: metadata.number.as_deref().and_then(text),
+            pages: metadata
+                .page_range
+                .as_deref()
+                .map(|pages| pages.replace("--", "\u{2013}"))
+                .and_then(|pages| text(&pages)),
+            link: metadata
+                .doi
+                .as_ref()
+                .map(|doi| format!("https://doi.org/{}", doi))
+                .or(url.map(str::to_string)),
+            is_book: matches!(
+                metadata.entry_type,
+                BibtexEntryType::Book | BibtexEntryType::Collection | BibtexEntryType::Proceedings
+            ),
+        }
+    }
+}
+
+/// Formats a reference to the work described by `metadata` in `style`, as plain text.
+/// `url` is linked when the work has no DOI.
+pub fn format_citation(
+    metadata: &ArticleMetadata,
+    url: Option<&str>,
+    style: CitationStyle,
+) -> String {
+    let source = Source::new(metadata, url);
+    match style {
+        CitationStyle::Apa => apa(&source),
+        CitationStyle::Mla => mla(&source),
+        CitationStyle::Chicago => chicago(&source),
+        CitationStyle::Ieee => ieee(&source),
+    }
+}
+
+/// Ends a sentence with a period unless it already ends in punctuation.
+fn sentence(text: &str) -> String {
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{}.", text)
+    }
+}
+
+/// `Doe, J., Smith, A. B., & Lee, C. (2020). Title. Journal, 12(3), 45–67. https://doi.org/…`
+fn apa(source: &Source) -> String {
+    let names: Vec<String> = source.authors.iter().map(Name::inverted_initials).collect();
+    let authors = match names.as_slice() {
+        [] => String::new(),
+        [one] => one.clone(),
+        // APA lists up to 20 authors, then an ellipsis and the last one.
+        [first @ .., last] if names.len() <= 20 => format!("{}, & {}", first.join(", "), last),
+        [first @ .., last] => format!("{}, . . . {}", first[..19].join(", "), last),
+    };
+
+    let date = format!("({})", source.year.as_deref().unwrap_or("n.d."));
+    let mut parts = Vec::new();
+    if authors.is_empty() {
+        parts.push(sentence(&source.title));
+        parts.push(format!("{}.", date));
+    } else {
+        parts.push(sentence(&authors));
+        parts.push(format!("{}.", date));
+        parts.push(sentence(&source.title));
+    }
+
+    if let Some(container) = &source.container {
+        let mut container = container.clone();
+        if let Some(volume) = &source.volume {
+            container.push_str(&format!(", {}", volume));
+            if let Some(number) = &source.number {
+                container.push_str(&format!("({})", number));
+            }
+        }
+        if let Some(pages) = &source.pages {
+            container.push_str(&format!(", {}", pages));
+        }
+        parts.push(sentence(&container));
+    } else if let Some(publisher) = &source.publisher {
+        parts.push(sentence(publisher));
+    }
+    parts.extend(source.link.clone());
+    parts.join(" ")
+}
+
+/// `Doe, Jane, et al. "Title." Journal, vol. 12, no. 3, 2020, pp. 45-67. https://doi.org/…`
+fn mla(source: &Source) -> String {
+    let authors = match source.authors.as_slice() {
+        [] => String::new(),
+        [one] => one.inverted(),
+        [first, second] => format!("{}, and {}", first.inverted(), second.natural()),
+        [first, ..] => format!("{}, et al", first.inverted()),
+    };
+
+    let mut parts = Vec::new();
+    if !authors.is_empty() {
+        parts.push(sentence(&authors));
+    }
+    if source.is_book {
+        parts.push(sentence(&source.title));
+    } else {
+        parts.push(format!("\u{201c}{}\u{201d}", sentence(&source.title)));
+    }
+
+    let mut details = Vec::new();
+    details.extend(source.container.clone());
+    if source.container.is_none() && !source.is_book {
+        details.extend(source.publisher.clone());
+    }
+    details.extend(
+        source
+            .volume
+            .as_ref()
+            .map(|volume| format!("vol. {}", volume)),
+    );
+    details.extend(
+        source
+            .number
+            .as_ref()
+            .map(|number| format!("no. {}", number)),
+    );
+    if source.is_book {
+        details.extend(source.publisher.clone());
+    }
+    details.extend(source.year.clone());
+    details.extend(
+        source
+            .pages
+            .as_ref()
+            .map(|pages| format!("pp. {}", pages.replace('\u{2013}', "-"))),
+    );
+    if !details.is_empty() {
+        parts.push(sentence(&details.join(", ")));
+    }
+    parts.extend(source.link.as_ref().map(|link| sentence(link)));
+    parts.join(" ")
+}
+
+/// `Doe, Jane, and Alan Smith. "Title." Journal 12, no. 3 (2020): 45–67. https://doi.org/…`
+fn chicago(source: &Source) -> String {
+    let mut names: Vec<String> = source
+        .authors
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if i == 0 {
+                name.inverted()
+            } else {
+                name.natural()
+            }
+        })
+        .collect();
+    // Chicago lists up to ten authors; past that, the first seven and "et al."
+    if names.len() > 10 {
+        names.truncate(7);
+        names.push("et al".to_string());
+    }
+    let authors = match names.as_slice() {
+        [] => String::new(),
+        [one] => one.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [first @ .., last] if last == "et al" => format!("{}, {}", first.join(", "), last),
+        [first @ .., last] => format!("{}, and {}", first.join(", "), last),
+    };
+
+    let mut parts = Vec::new();
+    if !authors.is_empty() {
+        parts.push(sentence(&authors));
+    }
+    if source.is_book {
+        parts.push(sentence(&source.title));
+        let imprint = join_nonempty(
+            &[
+                source.publisher.as_deref().unwrap_or_default(),
+                source.year.as_deref().unwrap_or_default(),
+            ],
+            ", ",
+        );
+        if !imprint.is_empty() {
+            parts.push(sentence(&imprint));
+        }
+    } else {
+        parts.push(format!("\u{201c}{}\u{201d}", sentence(&source.title)));
+        let mut container = source
+            .container
+            .clone()
+            .or(source.publisher.clone())
+            .unwrap_or_default();
+        if let Some(volume) = &source.volume {
+            container.push_str(&format!(" {}", volume));
+        }
+        if let Some(number) = &source.number {
+            container.push_str(&format!(", no. {}", number));
+        }
+        if let Some(year) = &source.year {
+            container.push_str(&format!(" ({})", year));
+        }
+        if let Some(pages) = &source.pages {
+            container.push_str(&format!(": {}", pages));
+        }
+        let container = container.trim();
+        if !container.is_empty() {
+            parts.push(sentence(container));
+        }
+    }
+    parts.extend(source.link.as_ref().map(|link| sentence(link)));
+    parts.join(" ")
+}
+
+/// `J. Doe and A. B. Smith, "Title," Journal, vol. 12, no. 3, pp. 45–67, 2020, doi: 10.…`
+fn ieee(source: &Source) -> String {
+    let names: Vec<String> = source.authors.iter().map(Name::initials_first).collect();
+    let authors = match names.as_slice() {
+        [] => String::new(),
+        [one] => one.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        // IEEE lists up to six authors; past that, the first and "et al."
+        [first, ..] if names.len() > 6 => format!("{} et al.", first),
+        [first @ .., last] => format!("{}, and {}", first.join(", "), last),
+    };
+
+    let mut parts = Vec::new();
+    if !authors.is_empty() {
+        parts.push(authors);
+    }
+    if source.is_book {
+        parts.push(source.title.clone());
+        parts.extend(source.publisher.clone());
+    } else {
+        parts.push(format!("\u{201c}{},\u{201d}", source.title));
+        parts.extend(source.container.clone().or(source.publisher.clone()));
+    }
+    parts.extend(
+        source
+            .volume
+            .as_ref()
+            .map(|volume| format!("vol. {}", volume)),
+    );
+    parts.extend(
+        source
+            .number
+            .as_ref()
+            .map(|number| format!("no. {}", number)),
+    );
+    parts.extend(source.pages.as_ref().map(|pages| format!("pp. {}", pages)));
+    parts.extend(source.year.clone());
+    let doi = source
+        .link
+        .as_deref()
+        .and_then(|link| link.strip_prefix("https://doi.org/"));
+    let online = match (doi, &source.link) {
+        (Some(doi), _) => {
+            parts.push(format!("doi: {}", doi));
+            None
+        }
+        (None, url) => url.as_ref(),
+    };
+
+    // The quoted title already ends in a comma.
+    let mut citation = String::new();
+    for part in parts {
+        if !citation.is_empty() {
+            citation.push_str(if citation.ends_with(",\u{201d}") {
+                " "
+            } else {
+                ", "
+            });
+        }
+        citation.push_str(&part);
+    }
+    // A URL ends the reference without a closing period.
+    match online {
+        Some(url) => format!("{}. [Online]. Available: {}", citation, url),
+        None => sentence(&citation),
+    }
+}
@@ -0,0 +1,106 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::authors::normalize_authors;
+use crate::extract::ArticleMetadata;
+
+// --- Markup Cleanup ---
+
+lazy_static! {
+    // Opening, closing and self-closing tags, e.g. `<em>`, `</i>`, `<br/>`, and
+    // namespaced ones such as JATS's `<jats:p>`. A lone `<`, as in `a < b`, is left alone.
+    static ref HTML_TAG_RE: Regex =
+        Regex::new(r"</?[A-Za-z][A-Za-z0-9:-]*(?:\s[^<>]*)?/?>|<!--.*?-->").unwrap();
+    static ref YEAR_RE: Regex = Regex::new(r"\b\d{4}\b").unwrap();
+}
+
+/// Turns a scraped value into plain text: entities such as `&amp;` and `&#8217;` are
+/// decoded, tags such as `<em>` removed, and runs of whitespace collapsed to one space.
+pub fn clean_metadata_field(s: &str) -> String {
+    let decoded = html_escape::decode_html_entities(s);
+    let stripped = HTML_TAG_RE.replace_all(&decoded, " ");
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cleans every free-text field of `entry` with [`clean_metadata_field`]. Identifiers
+/// and URLs are left as they are.
+pub fn clean_metadata_fields(entry: &mut ArticleMetadata) {
+    for field in [&mut entry.title, &mut entry.author, &mut entry.publisher] {
+        *field = clean_metadata_field(field);
+    }
+    for field in [
+        &mut entry.address,
+        &mut entry.abstract_text,
+        &mut entry.note,
+        &mut entry.journal,
+        &mut entry.booktitle,
+        &mut entry.series,
+        &mut entry.editor,
+        &mut entry.organization,
+        &mut entry.institution,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *field = clean_metadata_field(field);
+    }
+    for keyword in &mut entry.keywords {
+        *keyword = clean_metadata_field(keyword);
+    }
+    entry.keywords.retain(|keyword| !keyword.is_empty());
+}
+
+// --- Duplicate Values ---
+
+/// Removes values repeated when strategies' results overlap, e.g. an author given by
+/// both JSON-LD and Highwire tags. Of two forms of a name, the fuller one is kept
+/// ("Jane Doe" over "J. Doe"); of several years, the earliest, with a warning when
+/// they disagree.
+pub fn strip_bibtex_duplicates(entry: &mut ArticleMetadata) {
+    entry.author = dedupe_names(&entry.author);
+    if let Some(editor) = &mut entry.editor {
+        *editor = dedupe_names(editor);
+    }
+
+    let mut years: Vec<&str> = YEAR_RE
+        .find_iter(&entry.year)
+        .map(|year| year.as_str())
+        .collect();
+    if years.len() > 1 {
+        years.sort_unstable();
+        years.dedup();
+        if years.len() > 1 {
+            eprintln!(
+                "-> WARNING: sources disagree on the year ({}); keeping {}.",
+                years.join(", "),
+                years[0]
+            );
+        }
+        entry.year = years[0].to_string();
+    }
+
+    let mut seen = Vec::new();
+    entry.keywords.retain(|keyword| {
+        let keyword = keyword.to_lowercase();
+        !seen.contains(&keyword) && {
+            seen.push(keyword);
+            true
+        }
+    });
+    if let Some(orcid) = &mut entry.orcid {
+        let mut seen = Vec::new();
+        orcid.retain(|id| {
+            !seen.contains(id) && {
+                seen.push(id.clone());
+                true
+            }
+        });
+    }
+}
+
+fn dedupe_names(names: &str) -> String {
+    if names.is_empty() {
+        return String::new();
+    }
+    normalize_authors(names.split(" and ").map(str::to_string).collect()).join(" and ")
+}
@@ -0,0 +1,196 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::clean::clean_metadata_field;
+use crate::doi_check::is_valid_doi;
+use crate::error::AppError;
+use crate::http::{HttpClient, encode_path_segment};
+use crate::sites::fetch_json;
+
+// --- Crossref Work Metadata ---
+
+lazy_static! {
+    // Crossref records rarely change, so each DOI is fetched once per process.
+    static ref WORK_CACHE: Mutex<HashMap<String, DoiMetadata>> = Mutex::new(HashMap::new());
+}
+
+// Structs for parsing the Crossref works API (`/works/{doi}`).
+#[derive(Deserialize, Debug)]
+struct CrossrefWorkResponse {
+    message: CrossrefWork,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefWork {
+    #[serde(rename = "DOI")]
+    doi: String,
+    #[serde(rename = "URL")]
+    url: Option<String>,
+    #[serde(rename = "type")]
+    work_type: Option<String>,
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<CrossrefWorkAuthor>,
+    published: Option<CrossrefWorkDate>,
+    issued: Option<CrossrefWorkDate>,
+    #[serde(rename = "container-title", default)]
+    container_title: Vec<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    page: Option<String>,
+    publisher: Option<String>,
+    #[serde(default)]
+    subject: Vec<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    #[serde(rename = "ISSN", default)]
+    issn: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefWorkAuthor {
+    given: Option<String>,
+    family: Option<String>,
+    // Organisations are credited by `name` alone.
+    name: Option<String>,
+    #[serde(rename = "ORCID")]
+    orcid: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefWorkDate {
+    #[serde(rename = "date-parts", default)]
+    date_parts: Vec<Vec<Option<u16>>>,
+}
+
+/// A work's metadata as Crossref records it, in a shape that does not depend on
+/// Crossref's: every field is always present, empty lists and `null` standing in for
+/// what Crossref leaves out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoiMetadata {
+    pub doi: String,
+    pub url: Option<String>,
+    /// Crossref's work type, e.g. `journal-article` or `proceedings-article`.
+    #[serde(rename = "type")]
+    pub work_type: Option<String>,
+    pub title: Option<String>,
+    pub authors: Vec<DoiAuthor>,
+    /// The publication date to whatever precision Crossref has it: `2019`, `2019-08`
+    /// or `2019-08-26`.
+    pub published: Option<String>,
+    pub year: Option<u16>,
+    /// The journal, proceedings or book the work appeared in.
+    pub container_title: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub page: Option<String>,
+    pub publisher: Option<String>,
+    /// Crossref's subject headings, usually those of the journal.
+    pub subjects: Vec<String>,
+    /// The abstract as plain text, without the JATS markup Crossref wraps it in.
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    pub issn: Vec<String>,
+}
+
+/// An author as Crossref credits them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoiAuthor {
+    pub given: Option<String>,
+    pub family: Option<String>,
+    /// The name of an organisation credited as author.
+    pub name: Option<String>,
+    /// The bare ORCID iD, e.g. `0000-0002-1825-0097`.
+    pub orcid: Option<String>,
+}
+
+/// Looks a DOI up in the Crossref works API. Results are cached for the life of the
+/// process; malformed DOIs are refused without a request.
+pub async fn doi_metadata(client: &impl HttpClient, doi: &str) -> Result<DoiMetadata, AppError> {
+    let doi = doi
+        .trim()
+        .trim_start_matches("doi:")
+        .trim_start_matches("https://doi.org/");
+    if !is_valid_doi(doi) {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' is not a valid DOI.",
+            doi
+        )));
+    }
+    let cache_key = doi.to_lowercase();
+    if let Some(cached) = WORK_CACHE.lock().unwrap().get(&cache_key) {
+        return Ok(cached.clone());
+    }
+
+    let api_url = format!(
+        "https://api.crossref.org/works/{}",
+        encode_path_segment(doi)
+    );
+    let response: CrossrefWorkResponse = fetch_json(client, &api_url).await?;
+    let metadata = work_metadata(response.message);
+    WORK_CACHE
+        .lock()
+        .unwrap()
+        .insert(cache_key, metadata.clone());
+    Ok(metadata)
+}
+
+fn work_metadata(work: CrossrefWork) -> DoiMetadata {
+    let date_parts = work
+        .published
+        .or(work.issued)
+        .and_then(|date| date.date_parts.into_iter().next())
+        .unwrap_or_default()
+        .into_iter()
+        .map_while(|part| part)
+        .collect::<Vec<_>>();
+    let published = match date_parts.as_slice() {
+        [year] => Some(year.to_string()),
+        [year, month] => Some(format!("{}-{:02}", year, month)),
+        [year, month, day, ..] => Some(format!("{}-{:02}-{:02}", year, month, day)),
+        [] => None,
+    };
+    let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+
+    DoiMetadata {
+        doi: work.doi,
+        url: work.url,
+        work_type: work.work_type,
+        title: non_empty(work.title.into_iter().next()),
+        authors: work
+            .author
+            .into_iter()
+            .map(|author| DoiAuthor {
+                given: author.given,
+                family: author.family,
+                name: author.name,
+                orcid: author.orcid.map(|orcid| {
+                    orcid
+                        .trim_start_matches("http://orcid.org/")
+                        .trim_start_matches("https://orcid.org/")
+                        .to_string()
+                }),
+            })
+            .collect(),
+        published,
+        year: date_parts.first().copied(),
+        container_title: non_empty(work.container_title.into_iter().next()),
+        volume: work.volume,
+        issue: work.issue,
+        page: work.page,
+        publisher: work.publisher,
+        subjects: work.subject,
+        abstract_text: non_empty(work.abstract_text.map(|abstract_text| {
+            let text = clean_metadata_field(&abstract_text);
+            // JATS abstracts usually open with an "Abstract" heading.
+            match text.strip_prefix("Abstract ") {
+                Some(rest) => rest.to_string(),
+                None => text,
+            }
+        })),
+        issn: work.issn,
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Datelike, NaiveDate};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// Years outside this range are almost certainly page numbers, IDs or typos.
+const MIN_YEAR: u16 = 1000;
+const MAX_YEAR: u16 = 2100;
+
+// Full-date formats tried in order. Day-first comes before month-first, but
+// either way the year is the same, so an ambiguous `03/04/2024` is harmless.
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y%m%d",
+    "%d/%m/%Y",
+    "%m/%d/%Y",
+    "%d-%m-%Y",
+    "%m-%d-%Y",
+    "%d.%m.%Y",
+    "%B %d, %Y",
+    "%B %d %Y",
+    "%b %d, %Y",
+    "%b. %d, %Y",
+    "%b %d %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+    "%d/%m/%y",
+    "%m/%d/%y",
+];
+
+lazy_static! {
+    static ref QUARTER_RE: Regex =
+        Regex::new(r"(?i)\bQ[1-4]\s*[,/-]?\s*(\d{4})\b|\b(\d{4})\s*[/-]?\s*Q[1-4]\b").unwrap();
+    static ref SEASON_RE: Regex =
+        Regex::new(r"(?i)\b(?:spring|summer|fall|autumn|winter)\s*[,/-]?\s*(\d{4})\b").unwrap();
+    static ref YEAR_RE: Regex = Regex::new(r"(?:^|\D)(\d{4})(?:\D|$)").unwrap();
+}
+
+/// Extracts a publication year from a date string in any of the formats sites
+/// commonly use: ISO 8601, RFC 2822, day- or month-first numeric dates,
+/// written-out months, quarters (`Q1 2024`) and seasons (`Spring 2024`).
+pub fn parse_year(s: &str) -> Option<u16> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    parse_full_date(s)
+        .or_else(|| first_capture(&QUARTER_RE, s))
+        .or_else(|| first_capture(&SEASON_RE, s))
+        .or_else(|| first_capture(&YEAR_RE, s))
+        .filter(is_plausible)
+}
+
+fn is_plausible(year: &u16) -> bool {
+    (MIN_YEAR..=MAX_YEAR).contains(year)
+}
+
+fn parse_full_date(s: &str) -> Option<u16> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+        return u16::try_from(date.year()).ok();
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(s) {
+        return u16::try_from(date.year()).ok();
+    }
+    // chrono happily reads `24` as the year 24 for `%Y`, so keep trying
+    // formats until one yields a plausible year.
+    DATE_FORMATS.iter().find_map(|format| {
+        NaiveDate::parse_from_str(s, format)
+            .ok()
+            .and_then(|date| u16::try_from(date.year()).ok())
+            .filter(is_plausible)
+    })
+}
+
+fn first_capture(re: &Regex, s: &str) -> Option<u16> {
+    re.captures(s)?
+        .iter()
+        .skip(1)
+        .flatten()
+        .find_map(|m| m.as_str().parse().ok())
+}
@@ -0,0 +1,143 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+// --- Batch Deduplication ---
+
+// One extracted entry together with what it is known by: the URL it was requested
+// for, or its citation key.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+    pub url: String,
+    pub bibtex: String,
+}
+
+// The surviving entries plus the groups of URLs that were folded together.
+#[derive(Debug, Serialize)]
+pub struct DedupedBatch {
+    pub entries: Vec<BatchEntry>,
+    pub duplicates_merged: Vec<Vec<String>>,
+}
+
+/// Collapses entries that describe the same work, matched by DOI or title fingerprint.
+/// From each group, the entry with the most fields is kept.
+pub fn deduplicate_entries(entries: Vec<BatchEntry>) -> DedupedBatch {
+    let mut groups: Vec<Vec<BatchEntry>> = Vec::new();
+    let mut group_by_key: HashMap<String, usize> = HashMap::new();
+
+    for entry in entries {
+        let keys: Vec<String> = [
+            field_value(&entry.bibtex, "doi").map(|doi| format!("doi:{}", doi.to_lowercase())),
+            field_value(&entry.bibtex, "title")
+                .map(|title| format!("title:{}", title_fingerprint(&title))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let index = match keys.iter().find_map(|key| group_by_key.get(key)) {
+            Some(&index) => index,
+            None => {
+                groups.push(Vec::new());
+                groups.len() - 1
+            }
+        };
+        for key in keys {
+            group_by_key.entry(key).or_insert(index);
+        }
+        groups[index].push(entry);
+    }
+
+    let mut duplicates_merged = Vec::new();
+    let entries = groups
+        .into_iter()
+        .map(|group| {
+            if group.len() > 1 {
+                duplicates_merged.push(group.iter().map(|entry| entry.url.clone()).collect());
+            }
+            group
+                .into_iter()
+                .rev() // max_by_key keeps the last maximum; prefer the earliest entry on ties.
+                .max_by_key(|entry| field_count(&entry.bibtex))
+                .unwrap()
+        })
+        .collect();
+
+    DedupedBatch {
+        entries,
+        duplicates_merged,
+    }
+}
+
+/// Builds a fuzzy key for a title: lowercase, no punctuation, first 80 characters.
+pub fn title_fingerprint(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(80)
+        .collect()
+}
+
+/// Reads the braced value of a field from a BibTeX entry, e.g. `title = {...}`. The
+/// name is matched ignoring ASCII case only, so offsets always refer to `bibtex` itself;
+/// full lowercasing can change a string's length, e.g. for the Kelvin sign.
+fn field_value(bibtex: &str, name: &str) -> Option<String> {
+    let matches = bibtex
+        .as_bytes()
+        .windows(name.len())
+        .enumerate()
+        .filter(|(_, window)| window.eq_ignore_ascii_case(name.as_bytes()))
+        .map(|(start, _)| start);
+
+    for start in matches {
+        // Skip matches inside longer names such as `booktitle`.
+        let preceded_by_name = bibtex[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let rest = bibtex[start + name.len()..].trim_start();
+        if preceded_by_name || !rest.starts_with('=') {
+            continue;
+        }
+
+        let value_start = bibtex.len() - rest[1..].trim_start().len();
+        let mut chars = bibtex[value_start..].char_indices();
+        if chars.next().map(|(_, c)| c) != Some('{') {
+            continue;
+        }
+
+        let mut depth = 1;
+        for (i, c) in chars {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                let value = &bibtex[value_start + 1..value_start + i];
+                return Some(value.replace(['{', '}'], "").trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Counts the `name = value` fields in an entry as a rough measure of richness.
+fn field_count(bibtex: &str) -> usize {
+    let mut depth = 0;
+    let mut count = 0;
+    for c in bibtex.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '=' if depth == 1 => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
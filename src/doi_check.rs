@@ -0,0 +1,91 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::extract::fetch_unpaywall_record;
+use crate::http::{HttpClient, encode_path_segment};
+use crate::relations::{Relations, parse_relations};
+
+// --- DOI Pre-flight Check ---
+
+lazy_static! {
+    // The DOI syntax: the "10" directory, a registrant code of dot-separated digits,
+    // then a non-empty suffix of printable characters.
+    static ref DOI_SYNTAX_RE: Regex = Regex::new(r"^10\.\d+(\.\d+)*/\S+$").unwrap();
+}
+
+/// What a quick look at a DOI found, before committing to a full extraction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoiCheck {
+    pub doi: String,
+    pub valid_format: bool,
+    /// The status doi.org's redirect ended in, if the request got that far.
+    pub status: Option<u16>,
+    pub resolves: bool,
+    /// Unpaywall's verdict; `None` when Unpaywall is not configured or has no record.
+    pub open_access: Option<bool>,
+    pub crossref_indexed: bool,
+    /// Retractions, expressions of concern and published versions Crossref records.
+    pub relations: Relations,
+}
+
+/// Whether `doi` follows the DOI syntax, e.g. `10.1145/3158154`.
+pub fn is_valid_doi(doi: &str) -> bool {
+    DOI_SYNTAX_RE.is_match(doi)
+}
+
+/// Validates a DOI, then asks doi.org, Unpaywall and Crossref about it.
+/// Malformed DOIs are reported without any network requests.
+pub async fn check_doi(client: &impl HttpClient, doi: &str) -> DoiCheck {
+    let doi = doi
+        .trim()
+        .trim_start_matches("doi:")
+        .trim_start_matches("https://doi.org/")
+        .to_string();
+
+    if !is_valid_doi(&doi) {
+        return DoiCheck {
+            doi,
+            valid_format: false,
+            status: None,
+            resolves: false,
+            open_access: None,
+            crossref_indexed: false,
+            relations: Relations::default(),
+        };
+    }
+
+    let encoded = encode_path_segment(&doi);
+    let status = client
+        .head(&format!("https://doi.org/{}", encoded))
+        .await
+        .ok()
+        .map(|res| res.status);
+    let open_access = fetch_unpaywall_record(client, &doi)
+        .await
+        .map(|record| record.is_oa);
+    let crossref_work = client
+        .get(
+            &format!("https://api.crossref.org/works/{}", encoded),
+            Some("application/json"),
+        )
+        .await
+        .ok()
+        .filter(|res| res.status.is_success());
+    let relations = crossref_work
+        .as_ref()
+        .and_then(|res| serde_json::from_str::<Value>(&res.body).ok())
+        .map(|work| parse_relations(&work))
+        .unwrap_or_default();
+
+    DoiCheck {
+        valid_format: true,
+        status: status.map(|status| status.as_u16()),
+        resolves: status.is_some_and(|status| status.is_success()),
+        open_access,
+        crossref_indexed: crossref_work.is_some(),
+        relations,
+        doi,
+    }
+}
@@ -0,0 +1,146 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::{Html as ScraperHtml, Selector};
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::doi_check::is_valid_doi;
+use crate::error::AppError;
+use crate::extract::{select_all_text, select_text};
+use crate::http::HttpClient;
+
+// --- DOI Lookup ---
+
+lazy_static! {
+    // A DOI anywhere in a longer string, up to whitespace, quotes, or a query or fragment.
+    static ref EMBEDDED_DOI_RE: Regex = Regex::new(r#"10\.\d{4,9}/[^\s"'<>?#]+"#).unwrap();
+}
+
+/// How much to trust a DOI found for a URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// A doi.org link, or the DOI the page declares in its Highwire tags.
+    High,
+    /// A DOI in the publisher's URL path, Dublin Core identifier or Schema.org markup.
+    Medium,
+    /// The first doi.org link in the page, which may belong to a cited work.
+    Low,
+}
+
+/// The DOI found for a URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FoundDoi {
+    pub doi: String,
+    pub confidence: Confidence,
+}
+
+/// Finds the DOI a URL refers to, without generating an entry. Bare DOIs and doi.org
+/// links are answered without a request; otherwise the page is fetched once and its
+/// Highwire tags, URL path, Dublin Core and Schema.org markup are tried in that order.
+/// DOI content negotiation is never attempted.
+pub async fn find_doi(client: &impl HttpClient, input: &str) -> Result<Option<FoundDoi>, AppError> {
+    let input = input.trim();
+    let found = |doi: String, confidence| Some(FoundDoi { doi, confidence });
+
+    // A bare DOI, `doi:` DOI or doi.org link needs no further work.
+    let bare = input.strip_prefix("doi:").unwrap_or(input);
+    if is_valid_doi(bare) {
+        return Ok(found(bare.to_string(), Confidence::High));
+    }
+    let url = Url::parse(input).map_err(AppError::UrlParseError)?;
+    let host = url.host_str().unwrap_or_default();
+    if host == "doi.org" || host == "dx.doi.org" {
+        let doi = percent_decode(url.path().trim_start_matches('/'));
+        if is_valid_doi(&doi) {
+            return Ok(found(doi, Confidence::High));
+        }
+    }
+
+    let res = client.get(url.as_str(), None).await?;
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "URL returned status {}",
+            res.status
+        )));
+    }
+    let document = ScraperHtml::parse_document(&res.body);
+
+    let highwire = ["citation_doi", "prism.doi", "bepress_citation_doi"]
+        .iter()
+        .find_map(|name| select_text(&document, &format!("meta[name='{}']", name), "content"))
+        .and_then(|value| embedded_doi(&value));
+    if let Some(doi) = highwire {
+        return Ok(found(doi, Confidence::High));
+    }
+
+    if let Some(doi) = embedded_doi(&percent_decode(url.path())) {
+        return Ok(found(doi, Confidence::Medium));
+    }
+
+    let dublin_core = ["DC.identifier", "dc.identifier", "DC.Identifier"]
+        .iter()
+        .flat_map(|name| select_all_text(&document, &format!("meta[name='{}']", name), "content"))
+        .find_map(|value| embedded_doi(&value));
+    if let Some(doi) = dublin_core.or_else(|| schema_org_doi(&document)) {
+        return Ok(found(doi, Confidence::Medium));
+    }
+
+    let linked = select_all_text(&document, "a[href*='doi.org/10.']", "href")
+        .into_iter()
+        .find_map(|href| embedded_doi(&percent_decode(&href)));
+    Ok(linked.and_then(|doi| found(doi, Confidence::Low)))
+}
+
+/// Reads the DOI out of a Schema.org node's `identifier`, `sameAs` or `@id`.
+fn schema_org_doi(document: &ScraperHtml) -> Option<String> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    document.select(&selector).find_map(|element| {
+        let json = serde_json::from_str::<Value>(&element.inner_html()).ok()?;
+        let nodes = match json.get("@graph") {
+            Some(Value::Array(graph)) => graph.clone(),
+            _ => match json {
+                Value::Array(nodes) => nodes,
+                node => vec![node],
+            },
+        };
+        nodes.iter().find_map(|node| {
+            ["identifier", "sameAs", "@id"]
+                .iter()
+                .filter_map(|key| node.get(*key))
+                .flat_map(json_strings)
+                .find_map(|value| embedded_doi(&value))
+        })
+    })
+}
+
+/// Every string in a JSON-LD value: plain strings, list items and the `value`, `@value`
+/// or `@id` of `PropertyValue` nodes.
+fn json_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(values) => values.iter().flat_map(json_strings).collect(),
+        Value::Object(node) => ["value", "@value", "@id"]
+            .iter()
+            .filter_map(|key| node.get(*key))
+            .flat_map(json_strings)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The first valid DOI in `text`, without trailing punctuation.
+fn embedded_doi(text: &str) -> Option<String> {
+    EMBEDDED_DOI_RE.find_iter(text).find_map(|doi| {
+        let doi = doi.as_str().trim_end_matches(['.', ',', ';', ')', ']']);
+        is_valid_doi(doi).then(|| doi.to_string())
+    })
+}
+
+/// Decodes `%2F` and friends, which publishers use for the slash in DOIs within paths.
+fn percent_decode(text: &str) -> String {
+    percent_encoding::percent_decode_str(text)
+        .decode_utf8_lossy()
+        .into_owned()
+}
@@ -0,0 +1,407 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::bibtex::OutputMode;
+use crate::error::AppError;
+use crate::extract::fetch_and_generate_entry;
+use crate::http::HttpClient;
+
+// --- Bibliography Enrichment ---
+
+// Each entry is looked up on its own, so one request may only ask for this many
+// lookups unless `BIBTEXTER_MAX_ENRICH_ENTRIES` says otherwise.
+const DEFAULT_MAX_ENRICH_ENTRIES: usize = 50;
+// Uploaded `.bib` files larger than this are rejected unless `BIBTEXTER_MAX_BIB_SIZE`
+// says otherwise.
+const DEFAULT_MAX_BIB_SIZE: usize = 256 * 1024;
+
+lazy_static! {
+    static ref URL_RE: Regex = Regex::new(r#"https?://[^\s{}"\\]+"#).unwrap();
+}
+
+/// Fields worth copying from a fresh extraction into an existing entry. Access notes
+/// and dates are left out: they describe this lookup, not the work.
+const ENRICHABLE_FIELDS: &[&str] = &[
+    "author",
+    "title",
+    "year",
+    "journal",
+    "booktitle",
+    "publisher",
+    "editor",
+    "volume",
+    "number",
+    "pages",
+    "doi",
+    "isbn",
+    "abstract",
+    "keywords",
+];
+
+// What BibTeX's predefined `jan` ... `dec` macros expand to.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// One `@type{key, ...}` entry from a `.bib` file, with its original text kept so
+/// that untouched entries can be written back exactly as they came in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibEntry {
+    pub entry_type: String,
+    pub key: String,
+    /// Field names lowercased, values with their outer braces or quotes removed,
+    /// `@string` macros expanded and `#` concatenations joined.
+    pub fields: Vec<(String, String)>,
+    pub raw: String,
+}
+
+impl BibEntry {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A piece of a `.bib` file: an entry, or anything between entries (comments,
+/// `@string` and `@preamble` blocks) passed through verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BibItem {
+    Entry(BibEntry),
+    Text(String),
+}
+
+/// Splits a `.bib` file into entries and the text around them.
+pub fn parse_bib(input: &str) -> Vec<BibItem> {
+    let mut items = Vec::new();
+    let mut macros = HashMap::new();
+    let mut rest = input;
+
+    while let Some(at) = rest.find('@') {
+        let Some((len, entry)) = parse_entry(&rest[at..], &mut macros) else {
+            // Not an entry after all; keep the `@` as plain text and move on.
+            push_text(&mut items, &rest[..at + 1]);
+            rest = &rest[at + 1..];
+            continue;
+        };
+        match entry {
+            Some(entry) => {
+                push_text(&mut items, &rest[..at]);
+                items.push(BibItem::Entry(entry));
+            }
+            None => push_text(&mut items, &rest[..at + len]),
+        }
+        rest = &rest[at + len..];
+    }
+    push_text(&mut items, rest);
+
+    items
+}
+
+fn push_text(items: &mut Vec<BibItem>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match items.last_mut() {
+        Some(BibItem::Text(previous)) => previous.push_str(text),
+        _ => items.push(BibItem::Text(text.to_string())),
+    }
+}
+
+/// Parses the block starting at `@`, returning its length and the entry, or `None`
+/// as the entry for `@string`, `@preamble` and `@comment` blocks. `@string` definitions
+/// are added to `macros` for the entries that follow.
+fn parse_entry(
+    text: &str,
+    macros: &mut HashMap<String, String>,
+) -> Option<(usize, Option<BibEntry>)> {
+    let type_len = text[1..]
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .map(|len| len + 1)?;
+    let entry_type = text[1..type_len].to_lowercase();
+    let open_at = type_len + (text[type_len..].len() - text[type_len..].trim_start().len());
+    let close = match text[open_at..].chars().next()? {
+        '{' => '}',
+        '(' => ')',
+        _ => return None,
+    };
+
+    // Find the matching delimiter, treating braces inside the body as nested.
+    let mut depth = 0;
+    let mut end = None;
+    for (i, c) in text[open_at + 1..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            c if c == close && depth == 0 => {
+                end = Some(open_at + 1 + i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let end = end?;
+    let len = end + 1;
+
+    let body = &text[open_at + 1..end];
+    if entry_type == "string" {
+        let definitions = parse_fields(body, macros);
+        macros.extend(definitions);
+        return Some((len, None));
+    }
+    if entry_type.is_empty() || ["preamble", "comment"].contains(&entry_type.as_str()) {
+        return Some((len, None));
+    }
+
+    let (key, fields) = body.split_once(',').unwrap_or((body, ""));
+    Some((
+        len,
+        Some(BibEntry {
+            entry_type,
+            key: key.trim().to_string(),
+            fields: parse_fields(fields, macros),
+            raw: text[..len].to_string(),
+        }),
+    ))
+}
+
+/// Reads `name = value` pairs, where a value is braced, quoted or a bare word.
+fn parse_fields(mut body: &str, macros: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+
+    while let Some((name, rest)) = body.split_once('=') {
+        let name = name.trim().trim_start_matches(',').trim().to_lowercase();
+        let (value, consumed) = field_value(rest, macros);
+        if !name.is_empty() {
+            fields.push((name, value.trim().to_string()));
+        }
+        body = &rest[consumed..];
+    }
+
+    fields
+}
+
+/// Reads one field value, made of braced or quoted strings, numbers and macro names
+/// joined with `#`, returning it along with the number of bytes consumed.
+fn field_value(text: &str, macros: &HashMap<String, String>) -> (String, usize) {
+    let mut value = String::new();
+    let mut pos = 0;
+
+    loop {
+        let part = text[pos..].trim_start();
+        pos = text.len() - part.len();
+        let (piece, consumed) = match part.chars().next() {
+            Some('{') => delimited_value(part, '}'),
+            Some('"') => delimited_value(part, '"'),
+            _ => {
+                let end = part.find([',', '#']).unwrap_or(part.len());
+                (expand_macro(part[..end].trim(), macros), end)
+            }
+        };
+        value.push_str(&piece);
+        pos += consumed;
+
+        match text[pos..].trim_start().strip_prefix('#') {
+            Some(next) => pos = text.len() - next.len(),
+            None => return (value, pos),
+        }
+    }
+}
+
+/// Reads a value from its opening delimiter up to `close`, returning it unwrapped
+/// along with the number of bytes consumed, including the delimiters.
+fn delimited_value(text: &str, close: char) -> (String, usize) {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            c if c == close && depth == 0 => return (text[1..i].to_string(), i + 1),
+            _ => {}
+        }
+    }
+    (text[1..].to_string(), text.len())
+}
+
+/// Expands a bare word: a macro from an earlier `@string`, one of BibTeX's built-in
+/// month abbreviations, or otherwise the word itself (numbers and unknown macros).
+fn expand_macro(word: &str, macros: &HashMap<String, String>) -> String {
+    let lower = word.to_lowercase();
+    if let Some(value) = macros.get(&lower) {
+        return value.clone();
+    }
+    MONTHS
+        .iter()
+        .find(|month| month[..3].to_lowercase() == lower)
+        .map_or_else(|| word.to_string(), |month| month.to_string())
+}
+
+/// Where to look an entry up: its DOI if it has one, otherwise the first URL found
+/// in `url`, `howpublished` or `note`.
+pub fn lookup_target(entry: &BibEntry) -> Option<String> {
+    if let Some(doi) = entry.field("doi").filter(|doi| !doi.is_empty()) {
+        let doi = doi
+            .trim_start_matches("https://doi.org/")
+            .trim_start_matches("http://dx.doi.org/");
+        return Some(format!("https://doi.org/{}", doi));
+    }
+    ["url", "howpublished", "note"].iter().find_map(|name| {
+        let value = entry.field(name)?;
+        URL_RE
+            .find(value)
+            .map(|url| url.as_str().trim_end_matches(['.', ',', ';']).to_string())
+    })
+}
+
+/// The result of enriching one entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enriched {
+    pub bibtex: String,
+    pub added: Vec<String>,
+}
+
+/// Adds any of `ENRICHABLE_FIELDS` that `found` has and `entry` lacks, appending them
+/// to the entry's original text. Returns the entry unchanged if nothing is new.
+pub fn merge_fields(entry: &BibEntry, found: &BibEntry) -> Enriched {
+    let additions = ENRICHABLE_FIELDS
+        .iter()
+        .filter(|name| entry.field(name).is_none_or(str::is_empty))
+        .filter_map(|name| {
+            found
+                .field(name)
+                .filter(|value| !value.is_empty())
+                .map(|value| (name.to_string(), value.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    if additions.is_empty() {
+        return Enriched {
+            bibtex: entry.raw.clone(),
+            added: Vec::new(),
+        };
+    }
+
+    // Reopen the entry before its closing delimiter and add the new fields at the end.
+    let raw = entry.raw.trim_end();
+    let close = raw.chars().next_back().unwrap_or('}');
+    let body = raw[..raw.len() - close.len_utf8()].trim_end();
+    let mut bibtex = body.to_string();
+    if !bibtex.ends_with(',') {
+        bibtex.push(',');
+    }
+    for (name, value) in &additions {
+        bibtex.push_str(&format!("\n  {} = {{{}}},", name, value));
+    }
+    bibtex.push('\n');
+    bibtex.push(close);
+
+    Enriched {
+        bibtex,
+        added: additions.into_iter().map(|(name, _)| name).collect(),
+    }
+}
+
+/// The most entries `/api/enrich-bib` looks up per request, from
+/// `BIBTEXTER_MAX_ENRICH_ENTRIES`.
+pub fn max_enrich_entries() -> usize {
+    std::env::var("BIBTEXTER_MAX_ENRICH_ENTRIES")
+        .ok()
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_ENRICH_ENTRIES)
+}
+
+/// The largest `.bib` file `/api/enrich-bib` accepts, from `BIBTEXTER_MAX_BIB_SIZE`
+/// (in bytes).
+pub fn max_bib_size() -> usize {
+    std::env::var("BIBTEXTER_MAX_BIB_SIZE")
+        .ok()
+        .and_then(|size| size.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_BIB_SIZE)
+}
+
+/// Looks up every entry in a `.bib` file and fills in missing fields. Entries that
+/// cannot be looked up are returned unchanged; a comment header summarises the changes.
+/// Files with more than `max_lookups` entries to look up are refused outright, since
+/// each lookup is a fetch of its own.
+pub async fn enrich_bib(
+    client: &impl HttpClient,
+    input: &str,
+    max_lookups: usize,
+) -> Result<String, AppError> {
+    let items = parse_bib(input);
+    let total = items
+        .iter()
+        .filter(|item| matches!(item, BibItem::Entry(_)))
+        .count();
+    let lookups = items
+        .iter()
+        .filter(|item| matches!(item, BibItem::Entry(entry) if lookup_target(entry).is_some()))
+        .count();
+    if lookups > max_lookups {
+        return Err(AppError::InvalidInput(format!(
+            "The file has {} entries to look up; at most {} are looked up per request.",
+            lookups, max_lookups
+        )));
+    }
+
+    let mut body = String::new();
+    let mut summary = Vec::new();
+    for item in items {
+        let entry = match item {
+            BibItem::Entry(entry) => entry,
+            BibItem::Text(text) => {
+                body.push_str(&text);
+                continue;
+            }
+        };
+
+        let Some(target) = lookup_target(&entry) else {
+            body.push_str(&entry.raw);
+            continue;
+        };
+        eprintln!("-> Enriching {} from {}", entry.key, target);
+        let found = match fetch_and_generate_entry(client, &target, OutputMode::Bibtex).await {
+            Ok(generated) => parse_bib(&generated.bibtex)
+                .into_iter()
+                .find_map(|item| match item {
+                    BibItem::Entry(found) => Some(found),
+                    BibItem::Text(_) => None,
+                }),
+            Err(err) => {
+                eprintln!("-> Could not enrich {}: {:?}", entry.key, err);
+                None
+            }
+        };
+
+        match found {
+            Some(found) => {
+                let enriched = merge_fields(&entry, &found);
+                if !enriched.added.is_empty() {
+                    summary.push(format!("{}: {}", entry.key, enriched.added.join(", ")));
+                }
+                body.push_str(&enriched.bibtex);
+            }
+            None => body.push_str(&entry.raw),
+        }
+    }
+
+    let mut header = format!("% Enriched {} of {} entries.\n", summary.len(), total);
+    for line in &summary {
+        header.push_str(&format!("% {}\n", line));
+    }
+    header.push('\n');
+    Ok(header + body.trim_start_matches('\n'))
+}
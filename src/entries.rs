@@ -0,0 +1,138 @@
+use chrono::{DateTime, Local, NaiveDate};
+use std::collections::HashMap;
+
+use crate::bibtex::BibtexEntryType;
+use crate::extract::ExtractionStrategy;
+
+// --- Extracted Entry Store ---
+
+// How many entries are kept unless `BIBTEXTER_MAX_STORED_ENTRIES` says otherwise.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// The entries this process has generated, by citation key, so they can be fetched
+/// again as `.bib` files. Later entries with the same key replace earlier ones. When
+/// full, the entry stored longest ago is forgotten.
+#[derive(Debug)]
+pub struct EntryStore {
+    capacity: usize,
+    entries: HashMap<String, StoredEntry>,
+}
+
+/// A generated entry together with how and when it was made.
+#[derive(Debug, Clone)]
+pub struct StoredEntry {
+    pub bibtex: String,
+    pub strategy: Option<ExtractionStrategy>,
+    pub stored_at: DateTime<Local>,
+}
+
+/// Narrows an export down to some of the stored entries. Unset conditions match all.
+#[derive(Debug, Clone, Default)]
+pub struct EntryFilter {
+    // Only entries stored on or after this day.
+    pub after: Option<NaiveDate>,
+    pub strategy: Option<ExtractionStrategy>,
+    pub entry_type: Option<BibtexEntryType>,
+}
+
+impl EntryFilter {
+    fn matches(&self, entry: &StoredEntry) -> bool {
+        self.after
+            .is_none_or(|after| entry.stored_at.date_naive() >= after)
+            && self
+                .strategy
+                .is_none_or(|strategy| entry.strategy == Some(strategy))
+            && self.entry_type.is_none_or(|entry_type| {
+                entry_type_name(&entry.bibtex)
+                    .is_some_and(|name| name.eq_ignore_ascii_case(entry_type.as_str()))
+            })
+    }
+}
+
+impl Default for EntryStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EntryStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// A store holding at most `BIBTEXTER_MAX_STORED_ENTRIES` entries.
+    pub fn from_env() -> Self {
+        let capacity = std::env::var("BIBTEXTER_MAX_STORED_ENTRIES")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(DEFAULT_CAPACITY);
+        Self::new(capacity)
+    }
+
+    /// Remembers an entry under the citation key written in it.
+    pub fn insert(&mut self, bibtex: &str) {
+        self.insert_extracted(bibtex, None);
+    }
+
+    /// Remembers an entry along with the strategy that extracted it.
+    pub fn insert_extracted(&mut self, bibtex: &str, strategy: Option<ExtractionStrategy>) {
+        if let Some(key) = citation_key(bibtex) {
+            if self.entries.len() >= self.capacity
+                && !self.entries.contains_key(key)
+                && let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored_at)
+                    .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+            self.entries.insert(
+                key.to_string(),
+                StoredEntry {
+                    bibtex: bibtex.to_string(),
+                    strategy,
+                    stored_at: Local::now(),
+                },
+            );
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|entry| entry.bibtex.as_str())
+    }
+
+    /// The stored entries that pass `filter`, oldest first.
+    pub fn matching(&self, filter: &EntryFilter) -> Vec<&StoredEntry> {
+        let mut entries: Vec<&StoredEntry> = self
+            .entries
+            .values()
+            .filter(|entry| filter.matches(entry))
+            .collect();
+        entries.sort_by(|a, b| {
+            a.stored_at
+                .cmp(&b.stored_at)
+                .then_with(|| citation_key(&a.bibtex).cmp(&citation_key(&b.bibtex)))
+        });
+        entries
+    }
+}
+
+/// Reads the key of an entry, e.g. `Doe2025Title` from `@misc{Doe2025Title,`.
+/// Leading `%` comment lines are skipped.
+pub fn citation_key(bibtex: &str) -> Option<&str> {
+    let entry = &bibtex[bibtex.find('@')?..];
+    let (_, rest) = entry.split_once(['{', '('])?;
+    let key = rest.split(',').next()?.trim();
+    (!key.is_empty()).then_some(key)
+}
+
+/// Reads the type of an entry, e.g. `misc` from `@misc{Doe2025Title,`.
+fn entry_type_name(bibtex: &str) -> Option<&str> {
+    let entry = &bibtex[bibtex.find('@')? + 1..];
+    let (name, _) = entry.split_once(['{', '('])?;
+    Some(name.trim())
+}
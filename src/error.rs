@@ -0,0 +1,82 @@
+use axum::response::{IntoResponse, Response};
+
+// Custom error type for better error handling.
+#[derive(Debug)]
+pub enum AppError {
+    RequestError(reqwest::Error),
+    UrlParseError(url::ParseError),
+    ExtractionError(String),
+    InvalidInput(String),
+    // An ISBN with the wrong length or a bad check digit.
+    InvalidIsbn(String),
+    PaywallDetected {
+        url: String,
+        open_access_url: Option<String>,
+    },
+    RateLimited,
+    // Admin endpoints without the admin token.
+    Unauthorized,
+}
+
+impl AppError {
+    /// The variant's name, for grouping errors in statistics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::RequestError(_) => "RequestError",
+            AppError::UrlParseError(_) => "UrlParseError",
+            AppError::ExtractionError(_) => "ExtractionError",
+            AppError::InvalidInput(_) => "InvalidInput",
+            AppError::InvalidIsbn(_) => "InvalidIsbn",
+            AppError::PaywallDetected { .. } => "PaywallDetected",
+            AppError::RateLimited => "RateLimited",
+            AppError::Unauthorized => "Unauthorized",
+        }
+    }
+}
+
+// Implement IntoResponse for our custom error, so Axum can convert it into an HTTP response.
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AppError::RequestError(err) => (
+                reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch the URL: {}", err),
+            ),
+            AppError::UrlParseError(err) => (
+                reqwest::StatusCode::BAD_REQUEST,
+                format!("Invalid URL provided: {}", err),
+            ),
+            AppError::ExtractionError(msg) => (
+                reqwest::StatusCode::NOT_FOUND,
+                format!("Could not extract BibTeX data: {}", msg),
+            ),
+            AppError::InvalidInput(msg) => (reqwest::StatusCode::BAD_REQUEST, msg),
+            AppError::InvalidIsbn(isbn) => (
+                reqwest::StatusCode::BAD_REQUEST,
+                format!("'{}' is not a valid ISBN-10 or ISBN-13.", isbn),
+            ),
+            AppError::PaywallDetected {
+                url,
+                open_access_url,
+            } => {
+                let mut message = format!("The page at {} appears to be behind a paywall.", url);
+                if let Some(open_access_url) = open_access_url {
+                    message.push_str(&format!(
+                        " An open-access version is available at {}",
+                        open_access_url
+                    ));
+                }
+                (reqwest::StatusCode::PAYMENT_REQUIRED, message)
+            }
+            AppError::RateLimited => (
+                reqwest::StatusCode::TOO_MANY_REQUESTS,
+                "Too many requests; please wait a minute and try again.".to_string(),
+            ),
+            AppError::Unauthorized => (
+                reqwest::StatusCode::UNAUTHORIZED,
+                "This endpoint needs the admin token.".to_string(),
+            ),
+        };
+        (status, error_message).into_response()
+    }
+}
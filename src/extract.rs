@@ -0,0 +1,1733 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::{StatusCode, header};
+use scraper::{ElementRef, Html as ScraperHtml, Selector};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+use crate::authors::{last_first, normalize_authors};
+use crate::bibtex::{
+    BibtexEntry, BibtexEntryType, OutputMode, ValidationError, bcp47_to_biblatex_langid,
+};
+use crate::clean::{clean_metadata_fields, strip_bibtex_duplicates};
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::http::{HttpClient, HttpResponse};
+use crate::issn::page_issns;
+use crate::license::{is_creative_commons, normalize_license};
+use crate::note::{NoteContext, note_template};
+use crate::preflight::check_not_blocked;
+use crate::publisher::{publisher_address, resolve_journal};
+use crate::relations::{fetch_relations, prepend_note};
+use crate::sites;
+use crate::titles::{fallback_title_from_url, normalize_title};
+use crate::trim::{TrimConfig, trim_bibtex_fields};
+use crate::wayback::{find_snapshot, wayback_enabled};
+pub use bibtexter_core::{ArticleMetadata, ExtractionStrategy, FieldType};
+
+// Use lazy_static to compile the regex once.
+lazy_static! {
+    static ref DOI_RE: Regex = Regex::new(r"^(?:https?://)?(?:dx\.)?doi\.org/(.+)").unwrap();
+    static ref ARXIV_CLASS_RE: Regex = Regex::new(r"\(([a-z-]+(?:\.[A-Za-z-]+)?)\)\s*$").unwrap();
+    static ref PAGE_COUNT_RE: Regex =
+        Regex::new(r"(?i)^\s*(\d+)\s*(?:pages?|pp\.?|p\.)?\s*$").unwrap();
+    // One `<target>; param=value; ...` entry of a `Link` header.
+    static ref LINK_RE: Regex = Regex::new(r"<([^>]*)>((?:\s*;\s*[^,;]+)*)").unwrap();
+    static ref LINK_REL_RE: Regex = Regex::new(r#"(?i)\brel\s*=\s*"?([^";]+)"?"#).unwrap();
+    static ref ORCID_RE: Regex = Regex::new(r"\b(\d{4}-\d{4}-\d{4}-\d{3}[\dX])\b").unwrap();
+}
+
+// Journals running on the F1000 open peer-review platform.
+const F1000_HOSTS: &[&str] = &[
+    "f1000research.com",
+    "wellcomeopenresearch.org",
+    "gatesopenresearch.org",
+    "hrbopenresearch.org",
+    "aasopenresearch.org",
+    "amrcopenresearch.org",
+    "open-research-europe.ec.europa.eu",
+];
+
+// The comment above entries whose title had to be made up from the URL.
+pub const URL_TITLE_WARNING: &str = "WARNING: Title derived from URL path \u{2014} please verify";
+
+// --- Structs for Deserializing Metadata ---
+
+// Structs for parsing Schema.org JSON-LD data.
+#[derive(Deserialize, Debug)]
+struct SchemaArticle {
+    #[serde(rename = "@type")]
+    type_of: String,
+    headline: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    author: Vec<SchemaAuthor>,
+    #[serde(default)]
+    editor: serde_json::Value,
+    #[serde(rename = "numberOfPages", default)]
+    number_of_pages: serde_json::Value,
+    #[serde(rename = "isPartOf", default)]
+    is_part_of: serde_json::Value,
+    #[serde(default)]
+    position: serde_json::Value,
+    #[serde(rename = "pageStart", default)]
+    page_start: serde_json::Value,
+    #[serde(rename = "pageEnd", default)]
+    page_end: serde_json::Value,
+    #[serde(rename = "datePublished")]
+    date_published: Option<String>,
+    // `ScholarlyArticle` pages list other addresses of the paper here, often its DOI.
+    #[serde(rename = "sameAs", default)]
+    same_as: serde_json::Value,
+    #[serde(rename = "isAccessibleForFree", default)]
+    is_accessible_for_free: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug)]
+struct SchemaAuthor {
+    name: String,
+    #[serde(rename = "sameAs", default)]
+    same_as: serde_json::Value,
+}
+
+// Structs for parsing the Unpaywall API response.
+#[derive(Deserialize, Debug)]
+pub(crate) struct UnpaywallRecord {
+    #[serde(default)]
+    pub is_oa: bool,
+    best_oa_location: Option<UnpaywallLocation>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UnpaywallLocation {
+    url: String,
+}
+
+// --- Extraction Logic ---
+
+/// Core logic: Fetches URL content and tries various methods to generate BibTeX.
+pub async fn fetch_and_generate_bibtex(
+    client: &impl HttpClient,
+    url_str: &str,
+) -> Result<String, AppError> {
+    fetch_and_generate_entry(client, url_str, OutputMode::Bibtex)
+        .await
+        .map(|entry| entry.bibtex)
+}
+
+/// A finished entry, along with the strategy that produced it.
+#[derive(Debug, Clone)]
+pub struct GeneratedEntry {
+    pub bibtex: String,
+    pub strategy: ExtractionStrategy,
+}
+
+/// Like `fetch_and_generate_bibtex`, but writes the entry in the requested dialect.
+/// Entries from DOI content negotiation are returned as doi.org sends them.
+pub async fn fetch_and_generate_entry(
+    client: &impl HttpClient,
+    url_str: &str,
+    mode: OutputMode,
+) -> Result<GeneratedEntry, AppError> {
+    let negotiated = |bibtex| GeneratedEntry {
+        bibtex,
+        strategy: ExtractionStrategy::DoiContentNegotiation,
+    };
+
+    // --- Strategy 1: Check for DOI ---
+    if let Some(caps) = DOI_RE.captures(url_str)
+        && let Some(doi) = caps.get(1)
+        && let Some(bibtex) = negotiate_doi(client, doi.as_str()).await?
+    {
+        let bibtex = flag_retraction(client, doi.as_str(), bibtex).await;
+        return Ok(negotiated(bibtex));
+    }
+
+    // NASA ADS writes its own BibTeX, which beats anything assembled from its search API.
+    if let Ok(url) = Url::parse(url_str)
+        && let Some(bibcode) = sites::ads::bibcode(&url)
+        && let Some(token) = sites::ads::ads_api_token()
+    {
+        match sites::ads::export_bibtex(client, &bibcode, &token).await {
+            Ok(bibtex) => {
+                eprintln!("-> Found BibTeX via the ADS export API.");
+                return Ok(GeneratedEntry {
+                    bibtex,
+                    strategy: ExtractionStrategy::SiteApi,
+                });
+            }
+            Err(err) => eprintln!("-> ADS export failed: {:?}", err),
+        }
+    }
+
+    eprintln!("-> DOI method failed or not applicable. Falling back to metadata extraction.");
+    let metadata = fetch_metadata(client, url_str).await?;
+
+    // The server may have pointed at a DOI for the page, which beats anything scraped.
+    if let Some(doi) = &metadata.canonical_doi
+        && let Some(bibtex) = negotiate_doi(client, doi).await?
+    {
+        let bibtex = flag_retraction(client, doi, bibtex).await;
+        return Ok(negotiated(bibtex));
+    }
+
+    let strategy = metadata.strategy;
+    let doi = metadata.doi.clone();
+    let mut bibtex = format_bibtex(metadata, Some(url_str), mode)?;
+    if let Some(doi) = doi {
+        bibtex = flag_retraction(client, &doi, bibtex).await;
+    }
+    Ok(GeneratedEntry { bibtex, strategy })
+}
+
+/// Refuses an entry whose title was made up from its URL when the caller asked for
+/// `strict` extraction.
+pub fn require_real_title(entry: GeneratedEntry, strict: bool) -> Result<GeneratedEntry, AppError> {
+    if strict && entry.bibtex.contains(URL_TITLE_WARNING) {
+        return Err(AppError::ExtractionError(
+            "Could not find a title for the page.".into(),
+        ));
+    }
+    Ok(entry)
+}
+
+/// Warns in the entry's `note` when Crossref records the work as retracted or under an
+/// expression of concern, so it isn't cited unawares.
+async fn flag_retraction(client: &impl HttpClient, doi: &str, bibtex: String) -> String {
+    match fetch_relations(client, doi).await.warning_note() {
+        Some(warning) => {
+            eprintln!("-> Crossref reports: {}", warning);
+            prepend_note(&bibtex, &warning)
+        }
+        None => bibtex,
+    }
+}
+
+/// Asks doi.org for a ready-made BibTeX entry for `doi`.
+async fn negotiate_doi(client: &impl HttpClient, doi: &str) -> Result<Option<String>, AppError> {
+    let doi_url = format!("https://doi.org/{}", doi);
+    let res = client
+        .get(&doi_url, Some("application/x-bibtex; charset=utf-8"))
+        .await?;
+
+    if res.status.is_success() {
+        let text = res.body;
+        if !text.trim().is_empty() && text.starts_with('@') {
+            eprintln!("-> Found BibTeX via DOI content negotiation.");
+            return Ok(Some(text));
+        }
+    }
+    Ok(None)
+}
+
+/// Looks for a `Link` header with `rel="canonical"`, `"describedby"` or `"cite-as"`
+/// that points at a doi.org URL, as some repositories and REST APIs send.
+pub fn discover_doi_from_headers(response: &HttpResponse) -> Option<String> {
+    response
+        .headers
+        .get_all(header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| LINK_RE.captures_iter(value))
+        .filter(|caps| {
+            caps.get(2)
+                .and_then(|params| LINK_REL_RE.captures(params.as_str()))
+                .is_some_and(|rel| {
+                    rel[1].split_whitespace().any(|rel| {
+                        ["canonical", "describedby", "cite-as"]
+                            .iter()
+                            .any(|wanted| rel.eq_ignore_ascii_case(wanted))
+                    })
+                })
+        })
+        .find_map(|caps| {
+            DOI_RE
+                .captures(caps[1].trim())
+                .map(|doi| doi[1].to_string())
+        })
+}
+
+/// Formats extracted metadata as a BibTeX entry. `url_str` is omitted for
+/// sources that have no web address, such as uploaded PDFs.
+pub fn format_bibtex(
+    metadata: ArticleMetadata,
+    url_str: Option<&str>,
+    mode: OutputMode,
+) -> Result<String, AppError> {
+    let ArticleMetadata {
+        title,
+        author,
+        year,
+        publisher,
+        address,
+        orcid,
+        doi,
+        license,
+        resource_type,
+        abstract_text,
+        keywords,
+        entry_type,
+        journal,
+        volume,
+        number,
+        booktitle,
+        series,
+        editor,
+        organization,
+        institution,
+        chapter,
+        isbn,
+        issn,
+        eissn,
+        pmid,
+        pmcid,
+        eprint,
+        eprint_class,
+        pdf,
+        pages,
+        page_range,
+        note,
+        version,
+        language,
+        comment,
+        ..
+    } = metadata;
+
+    // BibLaTeX cites web resources that aren't part of a journal, book or conference as `@online`.
+    let entry_type = match (entry_type, url_str) {
+        (BibtexEntryType::Misc, Some(_)) if mode.is_biblatex() => BibtexEntryType::Online,
+        // Plain BibTeX has no `@dataset`; `type = {Dataset}` says what it is instead.
+        (BibtexEntryType::Dataset, _) if !mode.is_biblatex() => BibtexEntryType::Misc,
+        (BibtexEntryType::Unpublished, _)
+            if mode == OutputMode::BiblatexArxiv && eprint.is_some() =>
+        {
+            BibtexEntryType::Online
+        }
+        (entry_type, _) => entry_type,
+    };
+
+    // --- Assemble the BibTeX entry ---
+    // Edited volumes have no authors, so their editors name the key instead.
+    let key_names = match (&author, &editor) {
+        (author, Some(editor)) if author.is_empty() => editor,
+        (author, _) => author,
+    };
+    let citation_key = generate_citation_key(key_names, &year, &title);
+
+    let mut entry = BibtexEntry::new(entry_type, citation_key)
+        .title(title)
+        .author(author)
+        .optional_field("journal", journal)
+        .optional_field("volume", volume)
+        .optional_field("number", number)
+        .optional_field("series", series)
+        .optional_field("booktitle", booktitle)
+        .optional_field("editor", editor)
+        .optional_field("organization", organization)
+        .optional_field("institution", institution)
+        .optional_field("chapter", chapter)
+        .year(year)
+        .field(
+            "urldate",
+            chrono::Local::now().format("%Y-%m-%d").to_string(),
+        )
+        .publisher(publisher)
+        .optional_field("address", address)
+        .optional_field("orcid", orcid.map(|orcid| orcid.join(", ")))
+        .optional_field("version", version)
+        .optional_field(
+            "type",
+            resource_type.filter(|_| entry_type != BibtexEntryType::Dataset),
+        )
+        .optional_field("isbn", isbn)
+        .optional_field("issn", eissn.or(issn))
+        .optional_field("pmid", pmid)
+        .optional_field("pmcid", pmcid)
+        .optional_field("pdf", pdf)
+        .optional_field("abstract", abstract_text)
+        .field("keywords", keywords.join(", "));
+    if let Some(comment) = comment {
+        entry = entry.comment(comment);
+    }
+    entry = match (entry_type, url_str) {
+        (_, None) => entry,
+        (BibtexEntryType::Misc, Some(url_str)) => {
+            entry.field("howpublished", format!("\\url{{{}}}", url_str))
+        }
+        (_, Some(url_str)) => entry.url(url_str),
+    };
+    entry = match entry_type {
+        BibtexEntryType::Book | BibtexEntryType::Collection | BibtexEntryType::Proceedings => {
+            entry.optional_field("numpages", pages.map(|pages| pages.to_string()))
+        }
+        _ => entry.optional_field("pages", page_range),
+    };
+    let site_name = url_str
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    let accessed = note_template().render(&NoteContext {
+        accessed: chrono::Local::now(),
+        site_name: &site_name,
+        doi: doi.as_deref(),
+    });
+    entry = match (note, entry_type) {
+        // `@online` records the access date in `urldate` alone.
+        (note, BibtexEntryType::Online) => entry.optional_field("note", note),
+        (Some(note), _) => entry.field("note", format!("{}; {}", note, accessed)),
+        (None, _) => entry.field("note", accessed),
+    };
+    if let Some(doi) = doi {
+        entry = entry.doi(doi);
+    }
+    // BibLaTeX has its own eprint fields; classic BibTeX styles read arXiv's conventions.
+    if let Some(eprint) = eprint {
+        let (prefix, class) = if mode.is_biblatex() {
+            ("eprinttype", "eprintclass")
+        } else {
+            ("archivePrefix", "primaryClass")
+        };
+        entry = entry
+            .field("eprint", eprint)
+            .field(prefix, "arXiv")
+            .optional_field(class, eprint_class);
+    }
+    entry = match license {
+        // BibLaTeX styles print Creative Commons licenses from a dedicated field.
+        Some(license) if mode.is_biblatex() && is_creative_commons(&license) => {
+            entry.field("creativecommons", license)
+        }
+        license => entry.optional_field("license", license),
+    };
+    if mode.is_biblatex() {
+        let langid = bcp47_to_biblatex_langid(language.as_deref().unwrap_or("en"));
+        entry = entry.field("langid", langid);
+    }
+
+    entry.build().map_err(|err| match err {
+        ValidationError::MissingField { field: "title", .. } => {
+            AppError::ExtractionError("Could not find a title for the page.".into())
+        }
+        err => AppError::ExtractionError(err.to_string()),
+    })
+}
+
+/// Runs every strategy that yields structured metadata, in order of preference.
+pub async fn fetch_metadata(
+    client: &impl HttpClient,
+    url_str: &str,
+) -> Result<ArticleMetadata, AppError> {
+    // --- Strategy 2: Ask a known repository's API directly ---
+    let parsed_url = Url::parse(url_str).map_err(AppError::UrlParseError)?;
+    check_not_blocked(&parsed_url)?;
+    let mut metadata = match sites::extract_site_specific(client, &parsed_url).await {
+        Some(metadata) => metadata?,
+        // --- Strategy 3: Scrape the webpage for metadata ---
+        None => scrape_page(client, url_str, true).await?,
+    };
+
+    clean_metadata_fields(&mut metadata);
+    strip_bibtex_duplicates(&mut metadata);
+    if metadata.title.is_empty()
+        && let Some(title) = fallback_title_from_url(&parsed_url)
+    {
+        eprintln!(
+            "-> WARNING: no title found anywhere on the page; using \"{}\", made up from the URL path.",
+            title
+        );
+        metadata.title = title;
+        metadata.comment = Some(match metadata.comment {
+            Some(comment) => format!("{}; {}", URL_TITLE_WARNING, comment),
+            None => URL_TITLE_WARNING.to_string(),
+        });
+    }
+    trim_bibtex_fields(&mut metadata, &TrimConfig::from_env());
+    if let Some(license) = &metadata.license {
+        let spdx = normalize_license(license);
+        // A Creative Commons license means the work is free to read.
+        if metadata.open_access.is_none() && is_creative_commons(&spdx) {
+            metadata.open_access = Some(true);
+        }
+        metadata.license = Some(spdx);
+    }
+    if metadata.entry_type == BibtexEntryType::Article && metadata.address.is_none() {
+        metadata.address =
+            publisher_address(&metadata.publisher, parsed_url.host_str()).map(str::to_string);
+    }
+    Ok(metadata)
+}
+
+/// Fetches a web page and scrapes whatever metadata it exposes, without formatting it.
+pub async fn scrape_metadata(
+    client: &impl HttpClient,
+    url_str: &str,
+) -> Result<ArticleMetadata, AppError> {
+    scrape_page(client, url_str, true).await
+}
+
+// `follow_canonical` is cleared on the canonical page itself, so two pages naming each
+// other as canonical are not fetched in turn forever.
+async fn scrape_page(
+    client: &impl HttpClient,
+    url_str: &str,
+    follow_canonical: bool,
+) -> Result<ArticleMetadata, AppError> {
+    // Dead links can still be cited from an archived copy, if the user opted in.
+    let mut archived_at = None;
+    let res = match client.get(url_str, None).await {
+        Ok(res) if res.status != StatusCode::NOT_FOUND && res.status != StatusCode::GONE => res,
+        outcome => {
+            let snapshot = if wayback_enabled() {
+                find_snapshot(client, url_str).await
+            } else {
+                None
+            };
+            match snapshot {
+                Some(snapshot_url) => {
+                    eprintln!("-> Page unreachable, using Wayback Machine snapshot.");
+                    let res = client.get(&snapshot_url, None).await?;
+                    archived_at = Some(snapshot_url);
+                    res
+                }
+                None => outcome?,
+            }
+        }
+    };
+
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "URL returned status {}",
+            res.status
+        )));
+    }
+
+    // Use the parsed URL to get the hostname for the BibTeX entry.
+    let parsed_url = Url::parse(url_str).map_err(AppError::UrlParseError)?;
+    let site_name = parsed_url.host_str().unwrap_or_default();
+
+    // The parsed document is not `Send`, so it must be dropped before the next `.await`.
+    let (mut metadata, paywall_doi, ssrn_id, canonical_url) = {
+        let document = ScraperHtml::parse_document(&res.body);
+
+        // --- Extract metadata in order of preference ---
+        // Overrides correct what the generic extractors misread, so a site's own
+        // scraper takes precedence over them.
+        let mut metadata = match sites::extract_from_page(&document, &parsed_url) {
+            Some(metadata) => metadata,
+            None => {
+                let mut metadata = extract_metadata(&document, &parsed_url);
+                if let Some(site) = sites::overrides::find_override(site_name) {
+                    eprintln!("-> Applying {} site override.", site.name);
+                    sites::overrides::apply(site, &document, &mut metadata);
+                }
+                metadata
+            }
+        };
+        // Page titles often end with the site's name, e.g. "Some Post | Blog Name".
+        let display_name = select_text(&document, "meta[property='og:site_name']", "content")
+            .filter(|name| !name.is_empty());
+        metadata.title = normalize_title(
+            &metadata.title,
+            display_name.as_deref().unwrap_or(site_name),
+        );
+
+        // Open peer-review journals mint a new DOI for every revision, so record which one this is.
+        if let Some(version) = f1000_version(&document, &parsed_url) {
+            if metadata.doi.is_none() {
+                metadata.doi = select_text(&document, "meta[name='citation_doi']", "content");
+            }
+            if metadata.note.is_none() {
+                metadata.note = Some(format!("Version {}; peer reviewed", version));
+            }
+            metadata.version = Some(version);
+        }
+
+        // Data repositories tag the exact version on the page, which makes it a dataset.
+        if let Some(version) =
+            select_text(&document, "meta[name='citation_data_version']", "content")
+                .filter(|version| !version.is_empty())
+        {
+            metadata.version.get_or_insert(version);
+            if metadata.resource_type.is_none() {
+                metadata.resource_type = Some("Dataset".to_string());
+                if metadata.entry_type == BibtexEntryType::Misc {
+                    metadata.entry_type = BibtexEntryType::Dataset;
+                }
+            }
+        }
+        // A `#fragment` cites one section of a long document, such as an RFC or a spec.
+        if metadata.chapter.is_none()
+            && let Some(fragment) = parsed_url.fragment()
+        {
+            metadata.chapter = section_heading(&document, fragment);
+        }
+
+        if metadata.entry_type == BibtexEntryType::Misc
+            && let Some(docs) = documentation_page(&document, &parsed_url)
+        {
+            eprintln!("-> Detected software documentation page.");
+            metadata.entry_type = BibtexEntryType::Manual;
+            metadata.organization =
+                select_text(&document, "meta[property='og:site_name']", "content")
+                    .filter(|name| !name.is_empty())
+                    .or(docs.project);
+            if metadata.version.is_none() {
+                metadata.version = docs.version;
+            }
+        } else if let Some(status) = unpublished_status(&document, &parsed_url, &metadata) {
+            metadata.entry_type = BibtexEntryType::Unpublished;
+            metadata.note = Some(status);
+        }
+
+        let is_paywall = metadata.open_access != Some(true)
+            && !sites::springer::full_text_available(&document)
+            && is_paywall_page(&document, &metadata.title);
+        let paywall_doi = is_paywall.then(|| {
+            DOI_RE
+                .captures(url_str)
+                .and_then(|caps| caps.get(1))
+                .map(|doi| doi.as_str().to_string())
+                .or_else(|| select_text(&document, "meta[name='citation_doi']", "content"))
+        });
+        let (issn, eissn) = page_issns(&document);
+        if metadata.issn.is_none() && metadata.eissn.is_none() {
+            metadata.issn = issn;
+            metadata.eissn = eissn;
+        }
+        // SSRN pages without embedded paper data can be looked up in Elsevier's API.
+        let ssrn_id = sites::ssrn::abstract_id(&parsed_url)
+            .filter(|_| site_name.ends_with("ssrn.com"))
+            .filter(|_| metadata.strategy != ExtractionStrategy::SiteApi);
+        let canonical_url = canonical_link(&document, &parsed_url);
+        (metadata, paywall_doi, ssrn_id, canonical_url)
+    };
+
+    if let Some(doi) = paywall_doi {
+        eprintln!("-> Page looks like a paywall landing page.");
+        // SpringerLink shows subscribers-only articles in full through SharedIt.
+        if let Some(shared_url) = sites::springer::shared_it_url(&parsed_url) {
+            eprintln!("-> Retrying through Springer Nature SharedIt.");
+            if let Ok(metadata) = Box::pin(scrape_metadata(client, &shared_url)).await {
+                return Ok(metadata);
+            }
+        }
+        let open_access_url = match doi {
+            Some(doi) => find_open_access_url(client, &doi).await,
+            None => None,
+        };
+        return Err(AppError::PaywallDetected {
+            url: url_str.to_string(),
+            open_access_url,
+        });
+    }
+
+    // Section and tag pages carry no metadata of their own, but may name the article
+    // they show as canonical.
+    if follow_canonical
+        && metadata.author.is_empty()
+        && metadata.year.is_empty()
+        && let Some(canonical_url) = canonical_url
+    {
+        // The canonical page may be on another host, which is held to the blocklist too.
+        match Url::parse(&canonical_url)
+            .map_err(AppError::UrlParseError)
+            .and_then(|url| check_not_blocked(&url))
+        {
+            Ok(()) => {
+                eprintln!(
+                    "-> No metadata on the page; trying its canonical URL {}.",
+                    canonical_url
+                );
+                match Box::pin(scrape_page(client, &canonical_url, false)).await {
+                    Ok(canonical) if !canonical.author.is_empty() || !canonical.year.is_empty() => {
+                        return Ok(canonical);
+                    }
+                    Ok(_) => eprintln!("-> The canonical page has no metadata either."),
+                    Err(err) => eprintln!("-> Canonical page failed: {:?}", err),
+                }
+            }
+            Err(err) => eprintln!("-> Not following canonical URL: {:?}", err),
+        }
+    }
+
+    if let Some(id) = ssrn_id
+        && let Some(api_key) = sites::ssrn::elsevier_api_key()
+    {
+        eprintln!("-> Looking up SSRN paper {} in the Elsevier API.", id);
+        match sites::ssrn::extract_elsevier(client, &id, &api_key).await {
+            Ok(api) => metadata = sites::ssrn::merge_elsevier(metadata, api),
+            Err(err) => eprintln!("-> Elsevier API lookup failed: {:?}", err),
+        }
+    }
+
+    if let Some(doi) = discover_doi_from_headers(&res) {
+        eprintln!("-> Found DOI in Link header.");
+        metadata.doi.get_or_insert_with(|| doi.clone());
+        metadata.canonical_doi = Some(doi);
+    }
+
+    // Prefer the journal's official publisher name, then the page's own claim, over the bare hostname.
+    let issns: Vec<&str> = [&metadata.eissn, &metadata.issn]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    let journal = resolve_journal(client, &issns).await;
+    metadata.issn_l = journal.issn_l.or(metadata.issn_l);
+    if let Some(publisher) = journal.publisher {
+        metadata.publisher = publisher;
+    } else if metadata.publisher.is_empty() {
+        metadata.publisher = site_name.to_string();
+    }
+
+    if let Some(snapshot_url) = archived_at {
+        metadata.note = Some(format!(
+            "Originally at \\url{{{}}}; archived at \\url{{{}}}",
+            url_str, snapshot_url
+        ));
+    }
+    if metadata.resource_type.as_deref() == Some("Dataset") && metadata.note.is_none() {
+        metadata.note = Some(format!("Accessed from {}", site_name));
+    }
+
+    Ok(metadata)
+}
+
+/// The page's `<link rel="canonical">`, resolved against its URL, when it names a page
+/// other than this one. A differing `#fragment` or trailing slash is not a different page.
+pub fn canonical_link(document: &ScraperHtml, url: &Url) -> Option<String> {
+    let href = select_text(document, "link[rel~='canonical']", "href")?;
+    let mut canonical = url.join(&href).ok()?;
+    if !matches!(canonical.scheme(), "http" | "https") {
+        return None;
+    }
+    canonical.set_fragment(None);
+    let mut current = url.clone();
+    current.set_fragment(None);
+    let same_page =
+        |a: &Url, b: &Url| a.as_str().trim_end_matches('/') == b.as_str().trim_end_matches('/');
+    (!same_page(&canonical, &current)).then(|| canonical.to_string())
+}
+
+/// The DOI Zenodo mints for a record, e.g. `10.5281/zenodo.1234567` for `/records/1234567`.
+fn zenodo_record_doi(url: &Url) -> Option<String> {
+    if url.host_str()?.trim_start_matches("www.") != "zenodo.org" {
+        return None;
+    }
+    let mut segments = url.path_segments()?;
+    let id = match (segments.next(), segments.next()) {
+        (Some("records" | "record"), Some(id)) => id,
+        _ => return None,
+    };
+    (!id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| format!("10.5281/zenodo.{}", id))
+}
+
+/// Reads the article version on F1000Research and the other journals built on its platform,
+/// from `citation_f1000_version` or, failing that, the `/v2` suffix of the article URL.
+fn f1000_version(document: &ScraperHtml, url: &Url) -> Option<String> {
+    if let Some(version) = select_text(document, "meta[name='citation_f1000_version']", "content")
+        .filter(|version| !version.is_empty())
+    {
+        return Some(version);
+    }
+
+    let host = url.host_str()?.trim_start_matches("www.");
+    if !F1000_HOSTS.contains(&host) {
+        return None;
+    }
+    url.path_segments()?
+        .filter_map(|segment| segment.strip_prefix('v'))
+        .find(|version| !version.is_empty() && version.bytes().all(|b| b.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// Finds the heading of the section a URL fragment points at. The target may be the
+/// heading itself, a `<section>` or other container (named by `aria-label` or its first
+/// heading), or an anchor inside a heading.
+fn section_heading(document: &ScraperHtml, fragment: &str) -> Option<String> {
+    let selector = Selector::parse("[id], a[name]").unwrap();
+    let target = document.select(&selector).find(|element| {
+        let attrs = element.value();
+        attrs.id() == Some(fragment) || attrs.attr("name") == Some(fragment)
+    })?;
+
+    let heading_text = |element: ElementRef| {
+        // Documentation generators append permalink markers such as "¶" or "#".
+        let text = collapse_whitespace(element.text());
+        let text = text
+            .trim_end_matches(['¶', '#', '§'])
+            .trim_end()
+            .to_string();
+        (!text.is_empty()).then_some(text)
+    };
+    let is_heading = |element: &ElementRef| {
+        matches!(
+            element.value().name(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        )
+    };
+
+    if is_heading(&target) {
+        return heading_text(target);
+    }
+    if let Some(label) = target
+        .value()
+        .attr("aria-label")
+        .filter(|label| !label.is_empty())
+    {
+        return Some(label.trim().to_string());
+    }
+    let heading_selector = Selector::parse("h1, h2, h3, h4, h5, h6").unwrap();
+    if let Some(heading) = target.select(&heading_selector).next() {
+        return heading_text(heading);
+    }
+    target
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(is_heading)
+        .and_then(heading_text)
+}
+
+/// What a software documentation site says about the project it documents.
+struct DocsPage {
+    project: Option<String>,
+    version: Option<String>,
+}
+
+/// Recognises software documentation by host (Read the Docs, `docs.*.io`, GitHub Pages
+/// under `/docs`), or by a `website` page that declares a `version` meta tag.
+fn documentation_page(document: &ScraperHtml, url: &Url) -> Option<DocsPage> {
+    let host = url.host_str()?;
+    let meta_version = select_text(document, "meta[name='version']", "content")
+        .or_else(|| select_text(document, "meta[name='docsearch:version']", "content"))
+        .filter(|version| !version.is_empty());
+
+    // Read the Docs serves `{project}.readthedocs.io/{language}/{version}/...`.
+    if host == "readthedocs.io" || host.ends_with(".readthedocs.io") {
+        let project = host.strip_suffix(".readthedocs.io").map(str::to_string);
+        let segments = url.path_segments()?.collect::<Vec<_>>();
+        let version = match segments.as_slice() {
+            [language, version, ..] if is_language_segment(language) && !version.is_empty() => {
+                Some(version.to_string())
+            }
+            _ => None,
+        };
+        return Some(DocsPage {
+            project,
+            version: version.or(meta_version),
+        });
+    }
+
+    let labels = host.split('.').collect::<Vec<_>>();
+    let project = match labels.as_slice() {
+        ["docs", project, "io"] => Some(project.to_string()),
+        [owner, "github", "io"] if url.path().starts_with("/docs") => Some(owner.to_string()),
+        _ => None,
+    };
+    if project.is_some() {
+        return Some(DocsPage {
+            project,
+            version: meta_version,
+        });
+    }
+
+    let is_website = select_text(document, "meta[property='og:type']", "content")
+        .is_some_and(|og_type| og_type.eq_ignore_ascii_case("website"));
+    (is_website && meta_version.is_some()).then_some(DocsPage {
+        project: None,
+        version: meta_version,
+    })
+}
+
+/// Matches Read the Docs language prefixes such as `en` or `pt-br`.
+fn is_language_segment(segment: &str) -> bool {
+    let (primary, region) = segment.split_once(['-', '_']).unwrap_or((segment, ""));
+    primary.len() == 2
+        && primary.bytes().all(|b| b.is_ascii_lowercase())
+        && region.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// Decides whether a page is a manuscript that has not been formally published,
+/// returning the status to put in `note`. Anything with a venue is left alone.
+fn unpublished_status(
+    document: &ScraperHtml,
+    url: &Url,
+    metadata: &ArticleMetadata,
+) -> Option<String> {
+    if metadata.journal.is_some()
+        || metadata.booktitle.is_some()
+        || !matches!(
+            metadata.entry_type,
+            BibtexEntryType::Misc | BibtexEntryType::Unpublished
+        )
+    {
+        return None;
+    }
+
+    if let Some(arxiv_id) = select_text(document, "meta[name='citation_arxiv_id']", "content") {
+        return Some(format!("Preprint, arXiv:{}", arxiv_id));
+    }
+    let marked_manuscript = metadata
+        .note
+        .as_deref()
+        .is_some_and(|note| note.trim().eq_ignore_ascii_case("manuscript"));
+    (marked_manuscript || is_personal_homepage(url)).then(|| "Manuscript".to_string())
+}
+
+/// Recognises academics' personal pages, e.g. `cs.example.edu/~jdoe/` or `people.example.ac.uk`.
+fn is_personal_homepage(url: &Url) -> bool {
+    let host = url.host_str().unwrap_or_default();
+    let academic = host.ends_with(".edu") || host.contains(".ac.") || host.contains(".edu.");
+    let personal_host = ["people.", "homes.", "users.", "staff.", "homepages."]
+        .iter()
+        .any(|prefix| host.starts_with(prefix));
+    let tilde_path = url.path().starts_with("/~") || url.path().starts_with("/%7E");
+
+    academic && (personal_host || tilde_path)
+}
+
+/// Heuristic check for publisher sign-in and purchase pages served with HTTP 200.
+fn is_paywall_page(document: &ScraperHtml, title: &str) -> bool {
+    let title = title.to_lowercase();
+    let paywall_title = ["sign in", "purchase", "access denied"]
+        .iter()
+        .any(|marker| title.contains(marker));
+    let is_product = select_text(document, "meta[property='og:type']", "content")
+        .is_some_and(|og_type| og_type.eq_ignore_ascii_case("product"));
+
+    paywall_title || is_product
+}
+
+/// Asks Unpaywall for a legal open-access copy of `doi`.
+async fn find_open_access_url(client: &impl HttpClient, doi: &str) -> Option<String> {
+    let record = fetch_unpaywall_record(client, doi).await?;
+    record.best_oa_location.map(|location| location.url)
+}
+
+/// Looks `doi` up in Unpaywall. Only runs when `BIBTEXTER_UNPAYWALL_EMAIL` is set,
+/// as Unpaywall requires a contact address.
+pub(crate) async fn fetch_unpaywall_record(
+    client: &impl HttpClient,
+    doi: &str,
+) -> Option<UnpaywallRecord> {
+    let email = std::env::var("BIBTEXTER_UNPAYWALL_EMAIL").ok()?;
+    // DOIs may contain `?`, `#` and `;`, so both parts are encoded.
+    let mut api_url = Url::parse("https://api.unpaywall.org/v2").unwrap();
+    api_url.path_segments_mut().unwrap().push(doi);
+    api_url.query_pairs_mut().append_pair("email", &email);
+
+    let res = client
+        .get(api_url.as_str(), Some("application/json"))
+        .await
+        .ok()?;
+    if !res.status.is_success() {
+        return None;
+    }
+
+    serde_json::from_str::<UnpaywallRecord>(&res.body).ok()
+}
+
+/// A non-empty Twitter Card value, e.g. `twitter:title`. Sites put these in either
+/// `name` or `property` attributes.
+fn twitter_card(document: &ScraperHtml, field: &str) -> Option<String> {
+    ["name", "property"]
+        .iter()
+        .find_map(|attr| {
+            select_text(
+                document,
+                &format!("meta[{}='twitter:{}']", attr, field),
+                "content",
+            )
+        })
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads a page's metadata with the generic strategies. The page's URL fills in what
+/// the markup leaves out, such as DOIs that repositories encode in their paths.
+pub fn extract_metadata(document: &ScraperHtml, url: &Url) -> ArticleMetadata {
+    // Strategy 2a: Look for Schema.org JSON-LD (best source)
+    let mut metadata = if let Some(metadata) = extract_from_schema(document) {
+        eprintln!("-> Extracted metadata from Schema.org JSON-LD.");
+        metadata
+    } else if let Some(metadata) = extract_from_dataset(document) {
+        eprintln!("-> Extracted dataset metadata from DCAT / Schema.org JSON-LD.");
+        metadata
+    } else if let Some(metadata) = extract_from_coins(document) {
+        eprintln!("-> Extracted metadata from COinS.");
+        metadata
+    } else {
+        // Strategy 2b: Look for OpenGraph and other meta tags
+        let title = select_text(document, "meta[property='og:title']", "content")
+            .or_else(|| twitter_card(document, "title"))
+            .or_else(|| select_text(document, "title", "text"))
+            .unwrap_or_default();
+
+        // Sites often repeat the byline in both tags, sometimes in different forms.
+        let mut authors = select_all_text(document, "meta[name='author']", "content");
+        authors.extend(select_all_text(
+            document,
+            "meta[property='article:author']",
+            "content",
+        ));
+        if authors.is_empty() {
+            authors = markup_authors(document);
+        }
+        let author = normalize_authors(authors).join(" and ");
+
+        let year = select_text(
+            document,
+            "meta[property='article:published_time']",
+            "content",
+        )
+        .and_then(|s| parse_year(&s))
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+        eprintln!("-> Extracted metadata from meta tags.");
+        ArticleMetadata {
+            strategy: ExtractionStrategy::MetaTags,
+            title,
+            author,
+            year,
+            ..Default::default()
+        }
+    };
+
+    // Highwire and PRISM tags tell us what kind of publication this is.
+    let isbn = select_text(document, "meta[name='citation_isbn']", "content");
+    let prism_book_section = select_text(document, "meta[name='prism.section']", "content")
+        .and(isbn.as_ref())
+        .and_then(|_| select_text(document, "meta[name='prism.publicationName']", "content"));
+    if let Some(book_title) = select_text(document, "meta[name='citation_inbook_title']", "content")
+        .or(prism_book_section)
+    {
+        metadata.entry_type = BibtexEntryType::InCollection;
+        metadata.booktitle = Some(book_title);
+    } else if let Some(journal) =
+        select_text(document, "meta[name='citation_journal_title']", "content")
+    {
+        metadata.entry_type = BibtexEntryType::Article;
+        metadata.journal = Some(journal);
+    } else if let Some(conference) = select_text(
+        document,
+        "meta[name='citation_conference_title']",
+        "content",
+    ) {
+        // Without authors, the page describes the whole volume rather than one paper in it.
+        if select_text(document, "meta[name='citation_author']", "content").is_none() {
+            metadata.entry_type = BibtexEntryType::Proceedings;
+            if let Some(title) = select_text(document, "meta[name='citation_title']", "content") {
+                metadata.title = title;
+            } else if metadata.title.is_empty() {
+                metadata.title = conference;
+            }
+        } else {
+            metadata.entry_type = BibtexEntryType::InProceedings;
+            metadata.booktitle = Some(conference);
+        }
+    }
+
+    if metadata.isbn.is_none() {
+        metadata.isbn = isbn;
+    }
+    if metadata.editor.is_none() {
+        let editors = select_all_text(document, "meta[name='citation_editor']", "content");
+        metadata.editor = (!editors.is_empty()).then(|| editors.join(" and "));
+    }
+    if metadata.publisher.is_empty() {
+        metadata.publisher = select_text(document, "meta[name='citation_publisher']", "content")
+            // The site's account, e.g. `@NYTimes`, names the outlet when nothing else does.
+            .or_else(|| {
+                twitter_card(document, "site")
+                    .map(|handle| handle.trim_start_matches('@').to_string())
+            })
+            .unwrap_or_default();
+    }
+    // Volume editors take the place of authors, so write them unambiguously.
+    if matches!(
+        metadata.entry_type,
+        BibtexEntryType::Proceedings | BibtexEntryType::Collection
+    ) && let Some(editor) = &metadata.editor
+    {
+        metadata.editor = Some(
+            editor
+                .split(" and ")
+                .map(last_first)
+                .collect::<Vec<_>>()
+                .join(" and "),
+        );
+    }
+
+    let first_page = select_text(document, "meta[name='citation_firstpage']", "content");
+    let last_page = select_text(document, "meta[name='citation_lastpage']", "content");
+    if let Some(page_range) = format_page_range(first_page, last_page) {
+        metadata.page_range = Some(page_range);
+    }
+
+    if metadata.pages.is_none() {
+        metadata.pages = select_text(document, "meta[name='DC.Format']", "content")
+            .or_else(|| select_text(document, "meta[name='dc.format']", "content"))
+            .and_then(|format| parse_page_count(&format));
+    }
+
+    // arXiv abstract pages name the primary subject as e.g. "Machine Learning (cs.LG)".
+    if metadata.eprint.is_none() {
+        metadata.eprint = select_text(document, "meta[name='citation_arxiv_id']", "content")
+            .filter(|id| !id.is_empty());
+        metadata.eprint_class =
+            select_text(document, "span.primary-subject", "text").and_then(|subject| {
+                ARXIV_CLASS_RE
+                    .captures(&subject)
+                    .map(|caps| caps[1].to_string())
+            });
+    }
+
+    // The document's own language, for hyphenation in BibLaTeX output.
+    if metadata.language.is_none() {
+        metadata.language = select_text(document, "html", "lang")
+            .or_else(|| select_text(document, "meta[name='citation_language']", "content"))
+            .or_else(|| select_text(document, "meta[name='DC.language']", "content"))
+            .or_else(|| select_text(document, "meta[property='og:locale']", "content"))
+            .filter(|lang| !lang.is_empty());
+    }
+
+    if metadata.abstract_text.is_none() {
+        metadata.abstract_text = select_text(document, "meta[name='citation_abstract']", "content")
+            .or_else(|| select_text(document, "meta[name='DC.description']", "content"))
+            .or_else(|| select_text(document, "meta[property='og:description']", "content"))
+            .or_else(|| twitter_card(document, "description"))
+            .or_else(|| select_text(document, "meta[name='description']", "content"))
+            .filter(|text| !text.is_empty());
+    }
+
+    // OpenGraph's article extension gives the site's own section and tags. A journal's
+    // section, such as "Research Article", is kept in `chapter`; for news and blog posts,
+    // sections like "Technology" are just more keywords.
+    let section = select_text(document, "meta[property='article:section']", "content")
+        .filter(|section| !section.is_empty());
+    let mut tags = select_all_text(document, "meta[property='article:tag']", "content");
+    match section {
+        Some(section)
+            if metadata.entry_type == BibtexEntryType::Article && metadata.chapter.is_none() =>
+        {
+            metadata.chapter = Some(section)
+        }
+        Some(section) => tags.insert(0, section),
+        None => {}
+    }
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty()
+            && !metadata
+                .keywords
+                .iter()
+                .any(|keyword| keyword.eq_ignore_ascii_case(tag))
+        {
+            metadata.keywords.push(tag.to_string());
+        }
+    }
+
+    if metadata.license.is_none() {
+        metadata.license = [
+            "DC.rights",
+            "dc.rights",
+            "DCTERMS.license",
+            "dcterms.license",
+        ]
+        .iter()
+        .find_map(|name| select_text(document, &format!("meta[name='{}']", name), "content"))
+        .or_else(|| schema_license(document))
+        .or_else(|| select_text(document, "link[rel='license']", "href"))
+        .or_else(|| select_text(document, "a[rel~='license']", "href"))
+        .or_else(|| select_text(document, "a[href*='creativecommons.org']", "href"))
+        .filter(|license| !license.is_empty());
+    }
+    if metadata.open_access.is_none() {
+        metadata.open_access = select_text(document, "meta[name='citation_oa_status']", "content")
+            .filter(|status| !status.is_empty())
+            .map(|status| !status.eq_ignore_ascii_case("closed"));
+    }
+
+    // Highwire-style ORCID tags are used alongside either strategy.
+    if metadata.orcid.is_none() {
+        let orcids = select_all_text(document, "meta[name='citation_author_orcid']", "content")
+            .iter()
+            .filter_map(|value| parse_orcid(value))
+            .collect::<Vec<_>>();
+        metadata.orcid = (!orcids.is_empty()).then_some(orcids);
+    }
+
+    // Every Zenodo record, including each version of one, has its own DOI.
+    if metadata.doi.is_none() {
+        metadata.doi = zenodo_record_doi(url);
+    }
+
+    metadata
+}
+
+/// Reads a Schema.org `license` from any JSON-LD block, including `@graph` nodes.
+fn schema_license(document: &ScraperHtml) -> Option<String> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    document.select(&selector).find_map(|element| {
+        let json = serde_json::from_str::<serde_json::Value>(&element.inner_html()).ok()?;
+        let graph = json.get("@graph").and_then(|graph| graph.as_array());
+        std::iter::once(&json)
+            .chain(graph.into_iter().flatten())
+            .find_map(|node| node.get("license").and_then(ld_text))
+    })
+}
+
+/// Finds bylines marked up in the page body rather than the head, as on personal
+/// blogs and course pages: hCard, Microdata, `rel="author"` links, then `<cite>`.
+fn markup_authors(document: &ScraperHtml) -> Vec<String> {
+    let hcard = select_all_element_text(document, ".vcard .fn");
+    if !hcard.is_empty() {
+        return hcard;
+    }
+
+    // Microdata authors are either a Person scope with its own `name`, or plain text.
+    let author_selector = Selector::parse("[itemprop='author']").unwrap();
+    let name_selector = Selector::parse("[itemprop='name']").unwrap();
+    let microdata = document
+        .select(&author_selector)
+        .map(|author| {
+            let name = author.select(&name_selector).next().unwrap_or(author);
+            collapse_whitespace(name.text())
+        })
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    if !microdata.is_empty() {
+        return microdata;
+    }
+
+    ["a[rel~='author']", "cite"]
+        .iter()
+        .map(|selector| select_all_element_text(document, selector))
+        .find(|authors| !authors.is_empty())
+        .unwrap_or_default()
+}
+
+/// Specific helper for extracting from Schema.org JSON-LD scripts.
+fn extract_from_schema(document: &ScraperHtml) -> Option<ArticleMetadata> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    for element in document.select(&selector) {
+        let json_text = element.inner_html();
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&json_text)
+            && let Ok(article) =
+                serde_json::from_value::<SchemaArticle>(expand_jsonld_context(json))
+            && (&article.type_of == "Article"
+                || &article.type_of == "ScholarlyArticle"
+                || &article.type_of == "NewsArticle"
+                || &article.type_of == "BlogPosting"
+                || &article.type_of == "Book"
+                || &article.type_of == "Chapter")
+        {
+            let title = article.headline.or(article.name).unwrap_or_default();
+            // An edited volume names its editors in place of authors.
+            let volume_editors = article
+                .author
+                .is_empty()
+                .then(|| schema_names(Some(&article.editor)))
+                .flatten();
+            let entry_type = match article.type_of.as_str() {
+                "Book" if volume_editors.is_some() && title.contains("Proceedings") => {
+                    BibtexEntryType::Proceedings
+                }
+                "Book" if volume_editors.is_some() => BibtexEntryType::Collection,
+                "Book" => BibtexEntryType::Book,
+                "Chapter" => BibtexEntryType::InCollection,
+                "ScholarlyArticle" => BibtexEntryType::Article,
+                _ => BibtexEntryType::Misc,
+            };
+            // For chapters, `isPartOf` describes the book the chapter appears in.
+            let book = &article.is_part_of;
+            let booktitle = book.get("name").and_then(json_string);
+            let editor = volume_editors.or_else(|| schema_names(book.get("editor")));
+            let isbn = book.get("isbn").and_then(json_string);
+            let chapter = json_string(&article.position);
+            let page_range = format_page_range(
+                json_string(&article.page_start),
+                json_string(&article.page_end),
+            );
+            let pages = match &article.number_of_pages {
+                serde_json::Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+                serde_json::Value::String(s) => parse_page_count(s),
+                _ => None,
+            };
+            let orcids = article
+                .author
+                .iter()
+                .filter_map(|a| schema_author_orcid(&a.same_as))
+                .collect::<Vec<_>>();
+            let authors = normalize_authors(article.author.into_iter().map(|a| a.name).collect())
+                .join(" and ");
+            let year = article
+                .date_published
+                .and_then(|s| parse_year(&s))
+                .map(|year| year.to_string())
+                .unwrap_or_default();
+            let doi = schema_same_as_doi(&article.same_as);
+            // Sites write the flag both as a boolean and as the string "true".
+            let open_access = match &article.is_accessible_for_free {
+                serde_json::Value::Bool(free) => Some(*free),
+                serde_json::Value::String(free) => Some(free.eq_ignore_ascii_case("true")),
+                _ => None,
+            };
+
+            if !title.is_empty() {
+                return Some(ArticleMetadata {
+                    strategy: ExtractionStrategy::JsonLd,
+                    entry_type,
+                    pages,
+                    booktitle,
+                    editor,
+                    isbn,
+                    chapter,
+                    page_range,
+                    title,
+                    author: authors,
+                    year,
+                    orcid: (!orcids.is_empty()).then_some(orcids),
+                    doi,
+                    note: (open_access == Some(true)).then(|| "Open access".to_string()),
+                    open_access,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Finds a DOI among a Schema.org `sameAs` value, which may be a string or a list.
+fn schema_same_as_doi(same_as: &serde_json::Value) -> Option<String> {
+    match same_as {
+        serde_json::Value::String(link) => {
+            DOI_RE.captures(link.trim()).map(|caps| caps[1].to_string())
+        }
+        serde_json::Value::Array(links) => links.iter().find_map(schema_same_as_doi),
+        _ => None,
+    }
+}
+
+/// Extractor for data portals that describe a `Dataset` (Schema.org or DCAT) in JSON-LD,
+/// topped up from Dublin Core `<meta>` tags.
+fn extract_from_dataset(document: &ScraperHtml) -> Option<ArticleMetadata> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    let dataset = document.select(&selector).find_map(|element| {
+        let json = serde_json::from_str::<serde_json::Value>(&element.inner_html()).ok()?;
+        let nodes = match json.get("@graph") {
+            Some(serde_json::Value::Array(graph)) => graph.clone(),
+            _ => match json {
+                serde_json::Value::Array(nodes) => nodes,
+                node => vec![node],
+            },
+        };
+        nodes.into_iter().find(|node| {
+            let is_dataset =
+                |t: &serde_json::Value| matches!(t.as_str(), Some("Dataset" | "dcat:Dataset"));
+            match node.get("@type") {
+                Some(serde_json::Value::Array(types)) => types.iter().any(is_dataset),
+                Some(t) => is_dataset(t),
+                None => false,
+            }
+        })
+    })?;
+
+    let get = |keys: &[&str]| keys.iter().find_map(|key| dataset.get(*key));
+    let title = get(&["name", "dct:title", "dcterms:title"])
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='DC.title']", "content"))?;
+    let author = schema_names(get(&["creator", "dct:creator", "author"]))
+        .or_else(|| {
+            let creators = select_all_text(document, "meta[name='DC.creator']", "content");
+            (!creators.is_empty()).then(|| creators.join(" and "))
+        })
+        .unwrap_or_default();
+    let year = get(&[
+        "datePublished",
+        "dct:issued",
+        "dcterms:issued",
+        "dateModified",
+    ])
+    .and_then(ld_text)
+    .or_else(|| select_text(document, "meta[name='DC.date']", "content"))
+    .and_then(|date| parse_year(&date))
+    .map(|year| year.to_string())
+    .unwrap_or_default();
+    let publisher = get(&["publisher", "dct:publisher", "dcterms:publisher"])
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='DC.publisher']", "content"))
+        .or_else(|| select_text(document, "meta[property='og:site_name']", "content"))
+        .unwrap_or_default();
+    let license = get(&["license", "dct:license", "dcterms:license"])
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='DC.rights']", "content"));
+    let doi = get(&["identifier", "dct:identifier"])
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='DC.identifier']", "content"))
+        .and_then(|id| id.find("10.").map(|start| id[start..].to_string()));
+    let version = get(&[
+        "version",
+        "schema:version",
+        "dcat:version",
+        "owl:versionInfo",
+    ])
+    .and_then(ld_text);
+    let keywords = match get(&["keywords", "dcat:keyword"]) {
+        Some(serde_json::Value::Array(words)) => words.iter().filter_map(ld_text).collect(),
+        Some(serde_json::Value::String(words)) => words
+            .split(',')
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Some(ArticleMetadata {
+        strategy: ExtractionStrategy::JsonLd,
+        title,
+        author,
+        year,
+        publisher,
+        license,
+        doi,
+        keywords,
+        entry_type: BibtexEntryType::Dataset,
+        resource_type: Some("Dataset".to_string()),
+        version,
+        ..Default::default()
+    })
+}
+
+/// Extractor for OpenURL COinS, the `<span class="Z3988" title="ctx_ver=Z39.88-2004&...">`
+/// spans that library databases and JSTOR embed for Zotero. Only the first span is read,
+/// as later ones usually describe cited works.
+pub fn extract_from_coins(document: &ScraperHtml) -> Option<ArticleMetadata> {
+    let context = select_text(document, "span.Z3988", "title")?;
+    let pairs = url::form_urlencoded::parse(context.as_bytes())
+        .map(|(key, value)| (key.into_owned(), value.trim().to_string()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect::<Vec<_>>();
+    let get = |key: &str| {
+        pairs
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.clone())
+    };
+
+    let genre = get("rft.genre").unwrap_or_default().to_lowercase();
+    let title = get("rft.atitle")
+        .or_else(|| get("rft.title"))
+        .or_else(|| get("rft.btitle"))?;
+
+    let mut authors = pairs
+        .iter()
+        .filter(|(name, _)| name == "rft.au")
+        .map(|(_, author)| author.clone())
+        .collect::<Vec<_>>();
+    if authors.is_empty()
+        && let Some(last) = get("rft.aulast")
+    {
+        authors.push(match get("rft.aufirst") {
+            Some(first) => format!("{}, {}", last, first),
+            None => last,
+        });
+    }
+
+    // DOIs come either as `rft.doi` or as an `info:doi/` referent identifier.
+    let doi = get("rft.doi").or_else(|| {
+        pairs
+            .iter()
+            .filter(|(name, _)| name == "rft_id")
+            .find_map(|(_, id)| id.strip_prefix("info:doi/").map(str::to_string))
+    });
+
+    let journal = get("rft.jtitle").or_else(|| get("rft.stitle"));
+    let (entry_type, journal, booktitle) = match genre.as_str() {
+        "book" => (BibtexEntryType::Book, None, None),
+        "bookitem" => (BibtexEntryType::InCollection, None, get("rft.btitle")),
+        "proceeding" | "conference" => (BibtexEntryType::InProceedings, None, journal),
+        _ if journal.is_some() => (BibtexEntryType::Article, journal, None),
+        _ => (BibtexEntryType::Misc, None, None),
+    };
+
+    Some(ArticleMetadata {
+        strategy: ExtractionStrategy::MetaTags,
+        entry_type,
+        title,
+        author: normalize_authors(authors).join(" and "),
+        year: get("rft.date")
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: get("rft.pub").unwrap_or_default(),
+        journal,
+        booktitle,
+        volume: get("rft.volume"),
+        number: get("rft.issue"),
+        page_range: format_page_range(get("rft.spage"), get("rft.epage"))
+            .or_else(|| get("rft.pages").map(|pages| pages.replace('-', "--"))),
+        doi,
+        isbn: get("rft.isbn"),
+        ..Default::default()
+    })
+}
+
+/// Reads a human-readable string from a JSON-LD value: a plain string, a `{"@value": ...}`
+/// literal, a named node, a bare `{"@id": ...}` reference, or the first of a list.
+pub(crate) fn ld_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Array(values) => values.iter().find_map(ld_text),
+        serde_json::Value::Object(node) => ["@value", "name", "foaf:name", "@id", "url"]
+            .iter()
+            .find_map(|key| node.get(*key))
+            .and_then(ld_text),
+        other => json_string(other),
+    }
+}
+
+/// Joins first and last pages with the BibTeX double dash, e.g. "123--135".
+fn format_page_range(first: Option<String>, last: Option<String>) -> Option<String> {
+    match (first, last) {
+        (Some(first), Some(last)) if first != last => Some(format!("{}--{}", first, last)),
+        (Some(first), _) => Some(first),
+        _ => None,
+    }
+}
+
+/// Rewrites a JSON-LD document whose `@context` gives Schema.org terms local names, as
+/// in `{"@context": {"title": "schema:headline"}, "title": "..."}`, into plain Schema.org
+/// terms. Property names and `@type` values are both renamed, in nested objects too.
+/// A plain `"https://schema.org/"` context needs nothing done.
+pub fn expand_jsonld_context(mut json: serde_json::Value) -> serde_json::Value {
+    let contexts = match json.get("@context") {
+        Some(serde_json::Value::Array(contexts)) => contexts.clone(),
+        Some(context) => vec![context.clone()],
+        None => Vec::new(),
+    };
+    let definitions: Vec<(&String, &serde_json::Value)> = contexts
+        .iter()
+        .filter_map(serde_json::Value::as_object)
+        .flatten()
+        .collect();
+    // Prefixes such as `"schema": "https://schema.org/"` shorten the IRIs that follow.
+    let prefixes: HashSet<&str> = definitions
+        .iter()
+        .filter(|(_, iri)| iri.as_str().and_then(schema_org_term) == Some(""))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let terms: HashMap<String, String> = definitions
+        .iter()
+        .filter(|(name, _)| !name.starts_with('@'))
+        .filter_map(|(name, definition)| {
+            let iri = definition
+                .as_str()
+                .or_else(|| definition.get("@id")?.as_str())?;
+            let term = schema_term(iri, &prefixes)?;
+            (!term.is_empty() && term != name.as_str()).then(|| (name.to_string(), term))
+        })
+        .collect();
+    rename_schema_terms(&mut json, &terms, &prefixes);
+    json
+}
+
+// The Schema.org term an IRI names: `headline` for `https://schema.org/headline`,
+// and the empty string for the vocabulary itself.
+fn schema_org_term(iri: &str) -> Option<&str> {
+    ["https://schema.org/", "http://schema.org/"]
+        .iter()
+        .find_map(|vocabulary| iri.strip_prefix(vocabulary))
+        .or_else(|| {
+            ["https://schema.org", "http://schema.org"]
+                .contains(&iri)
+                .then_some("")
+        })
+}
+
+// Resolves a full or prefixed IRI, e.g. `schema:headline`, to a Schema.org term.
+fn schema_term(iri: &str, prefixes: &HashSet<&str>) -> Option<String> {
+    if let Some(term) = schema_org_term(iri) {
+        return Some(term.to_string());
+    }
+    let (prefix, term) = iri.split_once(':')?;
+    prefixes.contains(prefix).then(|| term.to_string())
+}
+
+fn rename_schema_terms(
+    value: &mut serde_json::Value,
+    terms: &HashMap<String, String>,
+    prefixes: &HashSet<&str>,
+) {
+    let rename = |name: &str| {
+        terms
+            .get(name)
+            .cloned()
+            .or_else(|| schema_term(name, prefixes).filter(|term| !term.is_empty()))
+    };
+    match value {
+        serde_json::Value::Object(object) => {
+            for (name, renamed) in object
+                .keys()
+                .filter(|name| !name.starts_with('@'))
+                .filter_map(|name| Some((name.clone(), rename(name)?)))
+                .collect::<Vec<_>>()
+            {
+                // A property the page also gives under its standard name keeps that value.
+                if !object.contains_key(&renamed)
+                    && let Some(field) = object.remove(&name)
+                {
+                    object.insert(renamed, field);
+                }
+            }
+            if let Some(serde_json::Value::String(type_of)) = object.get_mut("@type")
+                && let Some(renamed) = rename(type_of)
+            {
+                *type_of = renamed;
+            }
+            for (name, field) in object.iter_mut() {
+                if name != "@context" {
+                    rename_schema_terms(field, terms, prefixes);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                rename_schema_terms(item, terms, prefixes);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Reads a JSON-LD value that may be written as either a string or a number.
+fn json_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Joins the names of a JSON-LD person or list of people with " and ".
+pub(crate) fn schema_names(value: Option<&serde_json::Value>) -> Option<String> {
+    let names = match value? {
+        serde_json::Value::Array(people) => people.iter().filter_map(ld_text).collect::<Vec<_>>(),
+        person => ld_text(person).into_iter().collect(),
+    };
+    let names = normalize_authors(names);
+    (!names.is_empty()).then(|| names.join(" and "))
+}
+
+/// Reads a page count written as "274", "274 pages", "274pp" or "274 p.".
+pub fn parse_page_count(s: &str) -> Option<u32> {
+    PAGE_COUNT_RE
+        .captures(s)
+        .and_then(|caps| caps.get(1))
+        .and_then(|count| count.as_str().parse().ok())
+}
+
+/// Finds an ORCID iD among a Schema.org `sameAs` value, which may be a string or a list.
+fn schema_author_orcid(same_as: &serde_json::Value) -> Option<String> {
+    match same_as {
+        serde_json::Value::String(link) if link.contains("orcid.org") => parse_orcid(link),
+        serde_json::Value::Array(links) => links.iter().find_map(schema_author_orcid),
+        _ => None,
+    }
+}
+
+/// Pulls a bare ORCID iD (e.g. "0000-0002-1234-5678") out of an iD or ORCID URL.
+fn parse_orcid(value: &str) -> Option<String> {
+    ORCID_RE
+        .captures(value)
+        .and_then(|caps| caps.get(1))
+        .map(|orcid| orcid.as_str().to_string())
+}
+
+/// Generic helper to select text from an element attribute or inner text.
+pub(crate) fn select_text(
+    document: &ScraperHtml,
+    selector_str: &str,
+    attr: &str,
+) -> Option<String> {
+    let selector = Selector::parse(selector_str).ok()?;
+    document.select(&selector).next().and_then(|element| {
+        if attr == "text" {
+            Some(element.inner_html().trim().to_string())
+        } else {
+            element.value().attr(attr).map(|s| s.trim().to_string())
+        }
+    })
+}
+
+/// Like `select_text`, but returns the attribute of every matching element.
+pub(crate) fn select_all_text(
+    document: &ScraperHtml,
+    selector_str: &str,
+    attr: &str,
+) -> Vec<String> {
+    let Ok(selector) = Selector::parse(selector_str) else {
+        return Vec::new();
+    };
+    document
+        .select(&selector)
+        .filter_map(|element| element.value().attr(attr))
+        .map(|s| s.trim().to_string())
+        .collect()
+}
+
+/// Returns the visible text of every matching element, with whitespace collapsed.
+fn select_all_element_text(document: &ScraperHtml, selector_str: &str) -> Vec<String> {
+    let Ok(selector) = Selector::parse(selector_str) else {
+        return Vec::new();
+    };
+    document
+        .select(&selector)
+        .map(|element| collapse_whitespace(element.text()))
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+fn collapse_whitespace<'a>(text: impl Iterator<Item = &'a str>) -> String {
+    text.flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Generates a BibTeX citation key from the authors' last names, like "Doe2025FirstWord",
+/// "Doe.Smith.Jones2025FirstWord" for up to three authors, or "Doe+2025FirstWord" beyond that.
+pub fn generate_citation_key(author: &str, year: &str, title: &str) -> String {
+    let last_names = author
+        .split(" and ")
+        .map(last_name)
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    let author_part = match last_names.len() {
+        0 => "Unknown".to_string(),
+        1..=3 => last_names.join("."),
+        _ => format!("{}+", last_names[0]),
+    };
+    let year_part = if !year.is_empty() { year } else { "ND" }; // ND for No Date
+    let title_part = title.split_whitespace().next().unwrap_or("NoTitle");
+
+    format!(
+        "{}{}{}",
+        author_part,
+        year_part,
+        title_part
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+    )
+}
+
+/// The family name of one author written as either "First Last" or "Last, First".
+fn last_name(author: &str) -> String {
+    let family = match author.split_once(',') {
+        Some((family, _)) => family,
+        None => author.split_whitespace().last().unwrap_or_default(),
+    };
+    family.chars().filter(|c| c.is_alphanumeric()).collect()
+}
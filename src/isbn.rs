@@ -0,0 +1,261 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::{BibtexEntryType, OutputMode};
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy, format_bibtex};
+use crate::http::HttpClient;
+use crate::sites::fetch_json;
+use crate::sites::google_books::{GoogleBooksVolume, volume_metadata};
+
+// --- ISBN Lookup ---
+
+lazy_static! {
+    static ref ATOM_ENTRY_RE: Regex = Regex::new(r"(?s)<entry\b.*?</entry>").unwrap();
+    static ref ATOM_TITLE_RE: Regex = Regex::new(r"(?s)<title\b[^>]*>(.*?)</title>").unwrap();
+    static ref ATOM_AUTHOR_RE: Regex =
+        Regex::new(r"(?s)<author\b[^>]*>\s*<name>(.*?)</name>").unwrap();
+}
+
+// Structs for parsing the Open Library Books API (`jscmd=data`), keyed by `ISBN:{isbn}`.
+#[derive(Deserialize, Debug)]
+struct OpenLibraryBook {
+    title: String,
+    subtitle: Option<String>,
+    #[serde(default)]
+    authors: Vec<OpenLibraryName>,
+    #[serde(default)]
+    publishers: Vec<OpenLibraryName>,
+    #[serde(default)]
+    publish_places: Vec<OpenLibraryName>,
+    publish_date: Option<String>,
+    number_of_pages: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenLibraryName {
+    name: String,
+}
+
+// Structs for parsing the Google Books volumes API.
+#[derive(Deserialize, Debug)]
+struct GoogleBooksResponse {
+    #[serde(default)]
+    items: Vec<GoogleBooksVolume>,
+}
+
+/// Strips hyphens, spaces and an `ISBN` prefix from an ISBN and checks its check digit,
+/// accepting both ISBN-10 (whose check digit may be `X`) and ISBN-13.
+pub fn normalize_isbn(input: &str) -> Option<String> {
+    let isbn: String = input
+        .trim()
+        .trim_start_matches("ISBN")
+        .trim_start_matches(':')
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let valid = match isbn.len() {
+        // Digits weighted 10 down to 1 must sum to a multiple of 11.
+        10 => isbn
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                'X' if i == 9 => Some(10),
+                c => c.to_digit(10),
+            })
+            .zip((1..=10).rev())
+            .try_fold(0, |sum, (value, weight)| Some(sum + value? * weight))
+            .is_some_and(|sum| sum % 11 == 0),
+        // Digits weighted alternately 1 and 3 must sum to a multiple of 10.
+        13 => isbn
+            .chars()
+            .zip([1, 3].into_iter().cycle())
+            .try_fold(0, |sum, (c, weight)| Some(sum + c.to_digit(10)? * weight))
+            .is_some_and(|sum| sum % 10 == 0),
+        _ => false,
+    };
+    valid.then_some(isbn)
+}
+
+/// The ISBN-13 for an ISBN-10: `978`, the first nine digits, and a new check digit.
+/// Other input is returned unchanged.
+pub fn isbn10_to_isbn13(isbn: &str) -> String {
+    if isbn.len() != 10 {
+        return isbn.to_string();
+    }
+    let digits = format!("978{}", &isbn[..9]);
+    let sum: u32 = digits
+        .chars()
+        .zip([1, 3].into_iter().cycle())
+        .filter_map(|(c, weight)| Some(c.to_digit(10)? * weight))
+        .sum();
+    format!("{}{}", digits, (10 - sum % 10) % 10)
+}
+
+/// The WorldCat Search API key, from `WORLDCAT_WSKEY`.
+pub fn worldcat_wskey() -> Option<String> {
+    std::env::var("WORLDCAT_WSKEY")
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Looks a book up by ISBN in Open Library, which usually has the fuller record, then
+/// Google Books, then WorldCat when a key for it is configured, stopping at the first
+/// that knows the book. ISBN-10s are looked up as their ISBN-13.
+pub async fn lookup_isbn(
+    client: &impl HttpClient,
+    isbn: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let isbn = normalize_isbn(isbn)
+        .map(|isbn| isbn10_to_isbn13(&isbn))
+        .ok_or_else(|| AppError::InvalidIsbn(isbn.trim().to_string()))?;
+
+    match lookup_open_library(client, &isbn).await {
+        Ok(Some(metadata)) => return Ok(metadata),
+        Ok(None) => eprintln!("-> Open Library has no record of ISBN {}.", isbn),
+        Err(err) => eprintln!("-> Open Library lookup failed: {:?}", err),
+    }
+    match lookup_google_books(client, &isbn).await {
+        Ok(Some(metadata)) => return Ok(metadata),
+        Ok(None) => eprintln!("-> Google Books has no record of ISBN {}.", isbn),
+        Err(err) => eprintln!("-> Google Books lookup failed: {:?}", err),
+    }
+    let not_found = || AppError::ExtractionError(format!("No book found with ISBN {}.", isbn));
+    // WorldCat's OpenSearch API only answers requests carrying a key.
+    let Some(wskey) = worldcat_wskey() else {
+        return Err(not_found());
+    };
+    lookup_worldcat(client, &isbn, &wskey)
+        .await?
+        .ok_or_else(not_found)
+}
+
+/// Generates a `@book` entry for an ISBN.
+pub async fn bibtex_from_isbn(
+    client: &impl HttpClient,
+    isbn: &str,
+    mode: OutputMode,
+) -> Result<String, AppError> {
+    let metadata = lookup_isbn(client, isbn).await?;
+    format_bibtex(metadata, None, mode)
+}
+
+async fn lookup_open_library(
+    client: &impl HttpClient,
+    isbn: &str,
+) -> Result<Option<ArticleMetadata>, AppError> {
+    let api_url = format!(
+        "https://openlibrary.org/api/books?bibkeys=ISBN:{}&format=json&jscmd=data",
+        isbn
+    );
+    let mut books: HashMap<String, OpenLibraryBook> = fetch_json(client, &api_url).await?;
+    let Some(book) = books.remove(&format!("ISBN:{}", isbn)) else {
+        return Ok(None);
+    };
+
+    Ok(Some(ArticleMetadata {
+        title: full_title(book.title, book.subtitle),
+        author: normalize_authors(book.authors.into_iter().map(|a| a.name).collect()).join(" and "),
+        year: book
+            .publish_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: book
+            .publishers
+            .into_iter()
+            .next()
+            .map(|publisher| publisher.name)
+            .unwrap_or_default(),
+        address: book
+            .publish_places
+            .into_iter()
+            .next()
+            .map(|place| place.name),
+        pages: book.number_of_pages,
+        ..book_metadata(isbn)
+    }))
+}
+
+async fn lookup_google_books(
+    client: &impl HttpClient,
+    isbn: &str,
+) -> Result<Option<ArticleMetadata>, AppError> {
+    let api_url = format!(
+        "https://www.googleapis.com/books/v1/volumes?q=isbn:{}",
+        isbn
+    );
+    let response: GoogleBooksResponse = fetch_json(client, &api_url).await?;
+    let Some(volume) = response.items.into_iter().next() else {
+        return Ok(None);
+    };
+
+    Ok(Some(ArticleMetadata {
+        isbn: Some(isbn.to_string()),
+        ..volume_metadata(volume.volume_info)
+    }))
+}
+
+/// Looks a book up in WorldCat's OpenSearch API. Its Atom feed only gives the title and
+/// authors, so the entry has no publisher or year.
+async fn lookup_worldcat(
+    client: &impl HttpClient,
+    isbn: &str,
+    wskey: &str,
+) -> Result<Option<ArticleMetadata>, AppError> {
+    let api_url = format!(
+        "https://www.worldcat.org/webservices/catalog/search/worldcat/opensearch?q=bn:{}&format=atom&count=1&wskey={}",
+        isbn, wskey
+    );
+    let res = client.get(&api_url, Some("application/atom+xml")).await?;
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "WorldCat returned {} for ISBN {}",
+            res.status, isbn
+        )));
+    }
+    Ok(parse_worldcat_atom(&res.body, isbn))
+}
+
+/// The title and authors of the first entry in a WorldCat OpenSearch Atom feed.
+pub fn parse_worldcat_atom(xml: &str, isbn: &str) -> Option<ArticleMetadata> {
+    let entry = ATOM_ENTRY_RE.find(xml)?.as_str();
+    let text = |raw: &str| {
+        let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        html_escape::decode_html_entities(&text).into_owned()
+    };
+    let title = text(&ATOM_TITLE_RE.captures(entry)?[1]);
+    let authors = ATOM_AUTHOR_RE
+        .captures_iter(entry)
+        .map(|caps| text(&caps[1]))
+        .collect();
+
+    Some(ArticleMetadata {
+        title,
+        author: normalize_authors(authors).join(" and "),
+        ..book_metadata(isbn)
+    })
+}
+
+fn book_metadata(isbn: &str) -> ArticleMetadata {
+    ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::Book,
+        isbn: Some(isbn.to_string()),
+        ..Default::default()
+    }
+}
+
+fn full_title(title: String, subtitle: Option<String>) -> String {
+    match subtitle.filter(|subtitle| !subtitle.is_empty()) {
+        Some(subtitle) => format!("{}: {}", title, subtitle),
+        None => title,
+    }
+}
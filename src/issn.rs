@@ -0,0 +1,60 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::Html as ScraperHtml;
+
+use crate::extract::select_all_text;
+
+// --- ISSN Parsing ---
+
+lazy_static! {
+    // Eight characters, optionally split in two by a hyphen or space; the last may be `X`.
+    static ref ISSN_RE: Regex = Regex::new(r"(?i)\b(\d{4})[-\s]?(\d{3}[\dX])\b").unwrap();
+}
+
+/// Finds the first valid ISSN in `s` and writes it in the standard `1234-5678` form,
+/// e.g. `"ISSN 0028 0836 (Print)"` gives `0028-0836`. Candidates whose check digit is
+/// wrong are skipped.
+pub fn extract_issn(s: &str) -> Option<String> {
+    ISSN_RE.captures_iter(s).find_map(|caps| {
+        let issn = format!("{}{}", &caps[1], caps[2].to_ascii_uppercase());
+        // Digits weighted 8 down to 2, plus the check digit, must sum to a multiple of 11.
+        let valid = issn
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                'X' if i == 7 => Some(10),
+                c => c.to_digit(10),
+            })
+            .zip((1..=8).rev())
+            .try_fold(0, |sum, (value, weight)| Some(sum + value? * weight))
+            .is_some_and(|sum| sum % 11 == 0);
+        valid.then(|| format!("{}-{}", &issn[..4], &issn[4..]))
+    })
+}
+
+/// The print and electronic ISSNs a journal article's page declares, in that order.
+///
+/// `prism.eIssn` and `citation_fulltext_issn` name the electronic edition and
+/// `prism.issn` the print one. Highwire's `citation_issn` doesn't say which it is, so
+/// when it is repeated the first is taken as print and the next as electronic.
+pub fn page_issns(document: &ScraperHtml) -> (Option<String>, Option<String>) {
+    let issns = |selector: &str| -> Vec<String> {
+        select_all_text(document, selector, "content")
+            .iter()
+            .filter_map(|value| extract_issn(value))
+            .collect()
+    };
+
+    let mut electronic = issns("meta[name='prism.eIssn'], meta[name='citation_fulltext_issn']")
+        .into_iter()
+        .next();
+    let mut print = issns("meta[name='prism.issn']").into_iter().next();
+    for issn in issns("meta[name='citation_issn']") {
+        if print.is_none() && electronic.as_ref() != Some(&issn) {
+            print = Some(issn);
+        } else if electronic.is_none() && print.as_ref() != Some(&issn) {
+            electronic = Some(issn);
+        }
+    }
+    (print, electronic)
+}
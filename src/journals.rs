@@ -0,0 +1,95 @@
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::collections::HashMap;
+
+// --- Journal Abbreviations ---
+
+/// Journal names and their abbreviations, one `"Full Name","Abbrev.","ISSN"` record per
+/// line in the layout of JabRef's abbreviation lists, plus common acronyms such as CACM.
+const ABBREVIATIONS_CSV: &str = include_str!("../assets/journal_abbreviations.csv");
+
+lazy_static! {
+    static ref JOURNALS: HashMap<String, JournalName> = load_journals();
+    static ref JOURNAL_FIELD_RE: Regex =
+        Regex::new(r"(?i)(\bjournal\s*=\s*\{)([^{}]*)(\})").unwrap();
+}
+
+/// A journal's abbreviation together with its full name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalName {
+    pub abbrev: String,
+    pub full: String,
+    pub issn: Option<String>,
+}
+
+fn load_journals() -> HashMap<String, JournalName> {
+    let mut journals = HashMap::new();
+    for line in ABBREVIATIONS_CSV
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+    {
+        let cells = split_csv_line(line);
+        let [full, abbrev, issn, ..] = cells.as_slice() else {
+            continue;
+        };
+        journals.insert(
+            lookup_key(abbrev),
+            JournalName {
+                abbrev: abbrev.clone(),
+                full: full.clone(),
+                issn: (!issn.is_empty()).then(|| issn.clone()),
+            },
+        );
+    }
+    journals
+}
+
+/// Splits one CSV record, honouring double-quoted cells and `""` escapes.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+/// Matches abbreviations regardless of case, dots and spacing, so "J ACM" finds "J. ACM".
+fn lookup_key(name: &str) -> String {
+    name.to_lowercase()
+        .replace('.', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Looks up the full name of an abbreviated journal title.
+pub fn resolve_abbreviation(journal: &str) -> Option<JournalName> {
+    JOURNALS.get(&lookup_key(journal)).cloned()
+}
+
+/// Replaces an abbreviated `journal` field in a finished entry with the full name.
+/// Works on scraped entries and on those doi.org returns.
+pub fn expand_journal_abbreviations(bibtex: &str) -> String {
+    JOURNAL_FIELD_RE
+        .replace(bibtex, |caps: &Captures| {
+            match resolve_abbreviation(&caps[2]) {
+                Some(journal) => format!("{}{}{}", &caps[1], journal.full, &caps[3]),
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
@@ -0,0 +1,248 @@
+use phf::phf_map;
+
+// --- LaTeX to Plain Text ---
+
+// Accented letters, keyed by accent command and base letter: `\'{e}` is `'e`, `\v{s}` is `vs`.
+static ACCENTED: phf::Map<&'static str, &'static str> = phf_map! {
+    "'a" => "á", "'e" => "é", "'i" => "í", "'o" => "ó", "'u" => "ú", "'y" => "ý",
+    "'A" => "Á", "'E" => "É", "'I" => "Í", "'O" => "Ó", "'U" => "Ú", "'Y" => "Ý",
+    "'c" => "ć", "'n" => "ń", "'s" => "ś", "'z" => "ź", "'l" => "ĺ", "'r" => "ŕ",
+    "'C" => "Ć", "'N" => "Ń", "'S" => "Ś", "'Z" => "Ź", "'L" => "Ĺ", "'R" => "Ŕ",
+    "`a" => "à", "`e" => "è", "`i" => "ì", "`o" => "ò", "`u" => "ù",
+    "`A" => "À", "`E" => "È", "`I" => "Ì", "`O" => "Ò", "`U" => "Ù",
+    "^a" => "â", "^e" => "ê", "^i" => "î", "^o" => "ô", "^u" => "û",
+    "^A" => "Â", "^E" => "Ê", "^I" => "Î", "^O" => "Ô", "^U" => "Û",
+    "\"a" => "ä", "\"e" => "ë", "\"i" => "ï", "\"o" => "ö", "\"u" => "ü", "\"y" => "ÿ",
+    "\"A" => "Ä", "\"E" => "Ë", "\"I" => "Ï", "\"O" => "Ö", "\"U" => "Ü", "\"Y" => "Ÿ",
+    "~a" => "ã", "~n" => "ñ", "~o" => "õ", "~A" => "Ã", "~N" => "Ñ", "~O" => "Õ",
+    "=a" => "ā", "=e" => "ē", "=i" => "ī", "=o" => "ō", "=u" => "ū",
+    "=A" => "Ā", "=E" => "Ē", "=I" => "Ī", "=O" => "Ō", "=U" => "Ū",
+    ".z" => "ż", ".Z" => "Ż", ".e" => "ė", ".E" => "Ė", ".I" => "İ",
+    "cc" => "ç", "cC" => "Ç", "cs" => "ş", "cS" => "Ş", "ct" => "ţ", "cT" => "Ţ",
+    "vc" => "č", "vs" => "š", "vz" => "ž", "vr" => "ř", "ve" => "ě", "vn" => "ň",
+    "vd" => "ď", "vt" => "ť", "vC" => "Č", "vS" => "Š", "vZ" => "Ž", "vR" => "Ř",
+    "vE" => "Ě", "vN" => "Ň", "vD" => "Ď", "vT" => "Ť",
+    "ra" => "å", "rA" => "Å", "ru" => "ů", "rU" => "Ů",
+    "ua" => "ă", "uA" => "Ă", "ug" => "ğ", "uG" => "Ğ",
+    "Ho" => "ő", "HO" => "Ő", "Hu" => "ű", "HU" => "Ű",
+    "ka" => "ą", "kA" => "Ą", "ke" => "ę", "kE" => "Ę",
+};
+
+// Commands that stand for a letter, symbol or logo on their own.
+static SYMBOLS: phf::Map<&'static str, &'static str> = phf_map! {
+    "LaTeX" => "LaTeX", "TeX" => "TeX", "BibTeX" => "BibTeX", "LaTeXe" => "LaTeX2e",
+    "ss" => "ß", "o" => "ø", "O" => "Ø", "ae" => "æ", "AE" => "Æ", "oe" => "œ", "OE" => "Œ",
+    "aa" => "å", "AA" => "Å", "l" => "ł", "L" => "Ł", "i" => "i", "j" => "j",
+    "textendash" => "–", "textemdash" => "—", "ldots" => "…", "dots" => "…",
+    "textregistered" => "®", "texttrademark" => "™", "copyright" => "©", "S" => "§",
+    "alpha" => "α", "beta" => "β", "gamma" => "γ", "delta" => "δ", "epsilon" => "ε",
+    "varepsilon" => "ε", "zeta" => "ζ", "eta" => "η", "theta" => "θ", "kappa" => "κ",
+    "lambda" => "λ", "mu" => "μ", "nu" => "ν", "xi" => "ξ", "pi" => "π", "rho" => "ρ",
+    "sigma" => "σ", "tau" => "τ", "phi" => "φ", "varphi" => "φ", "chi" => "χ", "psi" => "ψ",
+    "omega" => "ω", "Gamma" => "Γ", "Delta" => "Δ", "Theta" => "Θ", "Lambda" => "Λ",
+    "Pi" => "Π", "Sigma" => "Σ", "Phi" => "Φ", "Psi" => "Ψ", "Omega" => "Ω",
+    "times" => "×", "pm" => "±", "leq" => "≤", "geq" => "≥", "infty" => "∞", "to" => "→",
+};
+
+// Accents written with a letter rather than a symbol, as in `\v{s}` or `\c c`.
+const LETTER_ACCENTS: &[&str] = &["c", "v", "u", "H", "r", "k"];
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('n', 'ⁿ'),
+    ('i', 'ⁱ'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+];
+
+/// Turns a LaTeX-marked-up field value into plain text, for output read outside LaTeX.
+///
+/// Formatting commands such as `\textit{}` and `\emph{}` keep their content, accent
+/// commands become accented letters (`\'{e}` is `é`), escaped specials such as `\&` lose
+/// their backslash, braces are dropped, and subscripts and superscripts in math become
+/// Unicode ones where possible, so `H$_2$O` reads `H₂O`. Unknown commands are dropped.
+pub fn strip_latex_commands(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    let mut in_math = false;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                i += 1;
+                command(&chars, &mut i, &mut out);
+            }
+            '{' | '}' => i += 1,
+            '$' => {
+                in_math = !in_math;
+                i += 1;
+            }
+            script @ ('_' | '^') if in_math => {
+                i += 1;
+                let text = argument(&chars, &mut i);
+                let table = if script == '^' {
+                    SUPERSCRIPTS
+                } else {
+                    SUBSCRIPTS
+                };
+                out.push_str(&scripted(&text, table));
+            }
+            '~' => {
+                out.push(' ');
+                i += 1;
+            }
+            '-' => {
+                let run = chars[i..].iter().take_while(|&&c| c == '-').count();
+                match run {
+                    2 => out.push('–'),
+                    3 => out.push('—'),
+                    _ => out.extend(std::iter::repeat_n('-', run)),
+                }
+                i += run;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the command after a backslash at `chars[*i]` and writes what it stands for.
+fn command(chars: &[char], i: &mut usize, out: &mut String) {
+    let Some(&first) = chars.get(*i) else {
+        return;
+    };
+
+    if !first.is_ascii_alphabetic() {
+        *i += 1;
+        match first {
+            '\'' | '`' | '^' | '"' | '~' | '=' | '.' => {
+                let base = argument(chars, i);
+                out.push_str(&accented(first, &base));
+            }
+            '\\' | ',' | ';' | ' ' => out.push(' '),
+            // A discretionary hyphen, or italic correction: nothing to print.
+            '-' | '/' => {}
+            c => out.push(c),
+        }
+        return;
+    }
+
+    let start = *i;
+    while chars.get(*i).is_some_and(char::is_ascii_alphabetic) {
+        *i += 1;
+    }
+    let name: String = chars[start..*i].iter().collect();
+
+    if LETTER_ACCENTS.contains(&name.as_str()) {
+        let base = argument(chars, i);
+        let accent = name.chars().next().unwrap_or_default();
+        out.push_str(&accented(accent, &base));
+    } else if let Some(symbol) = SYMBOLS.get(name.as_str()) {
+        out.push_str(symbol);
+    } else if name == "textsuperscript" || name == "textsubscript" {
+        let text = argument(chars, i);
+        let table = if name == "textsuperscript" {
+            SUPERSCRIPTS
+        } else {
+            SUBSCRIPTS
+        };
+        out.push_str(&scripted(&text, table));
+    }
+    // Anything else, like `\textit` or `\emph`, is dropped; its braced argument
+    // is then read as ordinary text.
+}
+
+/// Reads a command's argument: a braced group, a control word such as `\i`, or one
+/// character, skipping spaces before it. Returns it as plain text.
+fn argument(chars: &[char], i: &mut usize) -> String {
+    while chars.get(*i) == Some(&' ') {
+        *i += 1;
+    }
+    match chars.get(*i) {
+        Some('{') => {
+            let start = *i + 1;
+            let mut depth = 0;
+            while let Some(&c) = chars.get(*i) {
+                *i += 1;
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let end = if depth == 0 { *i - 1 } else { *i };
+            strip_latex_commands(&chars[start..end].iter().collect::<String>())
+        }
+        Some('\\') => {
+            *i += 1;
+            let mut text = String::new();
+            command(chars, i, &mut text);
+            text
+        }
+        Some(&c) => {
+            *i += 1;
+            c.to_string()
+        }
+        None => String::new(),
+    }
+}
+
+/// `base` with `accent` applied, or `base` unchanged if there is no such letter.
+fn accented(accent: char, base: &str) -> String {
+    ACCENTED
+        .get(format!("{}{}", accent, base).as_str())
+        .map_or_else(|| base.to_string(), |letter| letter.to_string())
+}
+
+/// `text` in Unicode superscript or subscript characters, or as it is if one of its
+/// characters has no such form.
+fn scripted(text: &str, table: &[(char, char)]) -> String {
+    text.chars()
+        .map(|c| {
+            table
+                .iter()
+                .find(|(plain, _)| *plain == c)
+                .map(|(_, script)| *script)
+        })
+        .collect::<Option<String>>()
+        .unwrap_or_else(|| text.to_string())
+}
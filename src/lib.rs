@@ -0,0 +1,176 @@
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+};
+use std::sync::{Arc, Mutex};
+
+use access_log::{LogFormat, TrustedProxies, access_log};
+use entries::EntryStore;
+use http::{DomainDelays, ThrottledClient};
+use rate_limit::{ApiKeys, RateLimiter, rate_limit};
+use robots::RobotsCache;
+use stats::Stats;
+
+pub mod access_log;
+pub mod api;
+pub mod authors;
+pub mod bib_json;
+pub mod bibtex;
+pub mod cite;
+pub mod clean;
+pub mod crossref;
+pub mod dates;
+pub mod dedupe;
+pub mod doi_check;
+pub mod doi_from_url;
+pub mod enrich;
+pub mod entries;
+pub mod error;
+pub mod extract;
+pub mod http;
+pub mod isbn;
+pub mod issn;
+pub mod journals;
+pub mod latex;
+pub mod license;
+pub mod note;
+pub mod pdf;
+pub mod preflight;
+pub mod publisher;
+pub mod rate_limit;
+pub mod relations;
+pub mod rfc;
+pub mod ris;
+pub mod robots;
+pub mod routes;
+pub mod search;
+pub mod similar;
+pub mod sites;
+pub mod smoke;
+pub mod stats;
+pub mod titles;
+pub mod trim;
+pub mod wayback;
+pub mod wikidata;
+
+// --- Application State ---
+
+// Shared by all handlers: the outgoing HTTP client with its per-domain delays, the
+// request statistics, the entries generated so far and the per-client rate limits.
+#[derive(Clone)]
+pub struct AppState {
+    pub client: ThrottledClient<reqwest::Client>,
+    pub stats: Arc<Mutex<Stats>>,
+    pub entries: Arc<Mutex<EntryStore>>,
+    pub extraction_limit: Arc<RateLimiter>,
+    pub search_limit: Arc<RateLimiter>,
+    pub robots: Arc<RobotsCache>,
+}
+
+impl AppState {
+    pub fn new(client: reqwest::Client) -> Self {
+        let api_keys = ApiKeys::from_env();
+        let trusted_proxies = TrustedProxies::from_env();
+        Self {
+            client: ThrottledClient::new(client, DomainDelays::from_env()),
+            stats: Arc::default(),
+            entries: Arc::new(Mutex::new(EntryStore::from_env())),
+            extraction_limit: Arc::new(
+                RateLimiter::extraction_from_env()
+                    .with_api_keys(api_keys.clone())
+                    .with_trusted_proxies(trusted_proxies.clone()),
+            ),
+            search_limit: Arc::new(
+                RateLimiter::search_from_env()
+                    .with_api_keys(api_keys)
+                    .with_trusted_proxies(trusted_proxies),
+            ),
+            robots: Arc::default(),
+        }
+    }
+}
+
+/// Builds the application router: the HTML UI plus the JSON API under `/api`.
+pub fn app(state: AppState) -> Router {
+    // Endpoints that fetch and extract pages, limited per client.
+    let extraction = Router::new()
+        .route("/get_bibtex", get(routes::get_bibtex_handler))
+        .route(
+            "/api/bibtex",
+            get(api::bibtex_handler).head(api::bibtex_preflight_handler),
+        )
+        .route("/api/preview", get(api::preview_handler))
+        .route("/api/preview-html", get(routes::preview_html_handler))
+        .route("/api/cite", get(api::cite_handler))
+        .route("/api/doi-from-url", get(api::doi_from_url_handler))
+        .route("/api/doi-metadata", get(api::doi_metadata_handler))
+        .route("/api/check-doi", get(api::check_doi_handler))
+        .route("/api/similar", get(api::similar_handler))
+        .route(
+            "/api/enrich-bib",
+            post(api::enrich_bib_handler).layer(DefaultBodyLimit::max(enrich::max_bib_size())),
+        )
+        .route(
+            "/api/bibtex-from-isbn",
+            get(api::bibtex_from_isbn_query_handler).post(api::bibtex_from_isbn_handler),
+        )
+        .route("/api/bibtex-for-rfc", get(api::bibtex_for_rfc_handler))
+        .route("/api/scholar-profile", get(api::scholar_profile_handler))
+        .route(
+            "/api/random-bibtex-test",
+            get(api::random_bibtex_test_handler),
+        )
+        .route(
+            "/api/bibtex-from-wikidata",
+            get(api::bibtex_from_wikidata_handler),
+        )
+        .route(
+            "/api/bibtex-from-title",
+            get(api::bibtex_from_title_handler),
+        )
+        .route(
+            "/api/extract-pdf",
+            post(api::extract_pdf_handler).layer(DefaultBodyLimit::max(pdf::max_pdf_size())),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.extraction_limit.clone(),
+            rate_limit,
+        ));
+    let search = Router::new()
+        .route("/api/search", get(api::search_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.search_limit.clone(),
+            rate_limit,
+        ));
+
+    let router = Router::new()
+        .route("/", get(routes::show_form))
+        .route("/healthz", get(routes::healthz))
+        .route("/api/formats", get(api::formats_handler))
+        .route("/api/version", get(api::version_handler))
+        .route("/api/known-sites", get(api::known_sites_handler))
+        .route("/api/schema", get(api::schema_handler))
+        .route("/api/stats", get(api::stats_handler))
+        .route("/api/bibtex-file/:file", get(api::bibtex_file_handler))
+        .route(
+            "/api/export-bibtex-batch",
+            get(api::export_bibtex_batch_handler),
+        )
+        .route("/api/export-ris-batch", get(api::export_ris_batch_handler))
+        .route("/api/bibtex-to-json", post(api::bibtex_to_json_handler))
+        .route(
+            "/api/resolve-abbreviation",
+            get(api::resolve_abbreviation_handler),
+        )
+        .merge(extraction)
+        .merge(search)
+        .with_state(state);
+
+    // An invalid format is reported by `main` at startup; here it just disables the log.
+    match LogFormat::from_env() {
+        Ok(Some(format)) => router.layer(middleware::from_fn_with_state(format, access_log)),
+        _ => router,
+    }
+}
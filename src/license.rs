@@ -0,0 +1,109 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// --- License Normalization ---
+
+lazy_static! {
+    // e.g. https://creativecommons.org/licenses/by-nc-sa/4.0/ or .../publicdomain/zero/1.0/
+    static ref CC_URL_RE: Regex =
+        Regex::new(r"creativecommons\.org/(licenses|publicdomain)/([a-z-]+)/(\d\.\d)").unwrap();
+    // e.g. "CC BY 4.0", "cc-by-nc-4.0", "CC BY-SA 3.0"
+    static ref CC_CODE_RE: Regex =
+        Regex::new(r"^cc[\s-]+(by(?:[\s-]+(?:nc|nd|sa))*)[\s-]+(\d\.\d)$").unwrap();
+    static ref VERSION_RE: Regex = Regex::new(r"\d\.\d").unwrap();
+}
+
+/// Well-known licenses by their common names, after `license_key` flattening.
+const KNOWN_LICENSES: &[(&str, &str)] = &[
+    ("mit", "MIT"),
+    ("apache 2 0", "Apache-2.0"),
+    ("apache version 2 0", "Apache-2.0"),
+    ("bsd 2 clause", "BSD-2-Clause"),
+    ("bsd 3 clause", "BSD-3-Clause"),
+    ("gpl 3 0", "GPL-3.0-only"),
+    ("gnu general public v3 0", "GPL-3.0-only"),
+    ("mpl 2 0", "MPL-2.0"),
+    ("mozilla public 2 0", "MPL-2.0"),
+    ("cc0", "CC0-1.0"),
+    ("cc0 1 0", "CC0-1.0"),
+    ("odbl", "ODbL-1.0"),
+    ("odbl 1 0", "ODbL-1.0"),
+    ("open database", "ODbL-1.0"),
+];
+
+/// Maps a license name or URL to its SPDX identifier, e.g. "Creative Commons
+/// Attribution 4.0 International" to `CC-BY-4.0`. Unrecognised values are returned as given.
+pub fn normalize_license(raw: &str) -> String {
+    let raw = raw.trim();
+    let lower = raw.to_lowercase();
+
+    if let Some(caps) = CC_URL_RE.captures(&lower) {
+        return match (&caps[1], &caps[2]) {
+            ("publicdomain", "zero") => format!("CC0-{}", &caps[3]),
+            ("licenses", code) => format!("CC-{}-{}", code.to_uppercase(), &caps[3]),
+            _ => raw.to_string(),
+        };
+    }
+    if let Some(caps) = CC_CODE_RE.captures(&lower) {
+        let code = caps[1].split([' ', '-']).filter(|part| !part.is_empty());
+        return format!(
+            "CC-{}-{}",
+            code.collect::<Vec<_>>().join("-").to_uppercase(),
+            &caps[2]
+        );
+    }
+    if lower.contains("creative commons")
+        && let Some(spdx) = creative_commons_name(&lower)
+    {
+        return spdx;
+    }
+    if lower.contains("opendefinition.org/licenses/odc-odbl") {
+        return "ODbL-1.0".to_string();
+    }
+
+    let key = license_key(&lower);
+    KNOWN_LICENSES
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map_or_else(|| raw.to_string(), |(_, spdx)| spdx.to_string())
+}
+
+/// Reads a spelled-out Creative Commons name such as
+/// "Creative Commons Attribution-NonCommercial 4.0 International".
+fn creative_commons_name(lower: &str) -> Option<String> {
+    let version = VERSION_RE.find(lower)?.as_str();
+    if lower.contains("zero") || lower.contains("cc0") {
+        return Some(format!("CC0-{}", version));
+    }
+    if !lower.contains("attribution") {
+        return None;
+    }
+
+    let flat = lower.replace(['-', ' '], "");
+    let mut code = String::from("CC-BY");
+    if flat.contains("noncommercial") {
+        code.push_str("-NC");
+    }
+    if flat.contains("noderiv") {
+        code.push_str("-ND");
+    }
+    if flat.contains("sharealike") {
+        code.push_str("-SA");
+    }
+    Some(format!("{}-{}", code, version))
+}
+
+/// Flattens a license name for lookup: lowercase words, no punctuation, and
+/// without "the", "license" or "licence".
+fn license_key(lower: &str) -> String {
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !["the", "license", "licence"].contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether an SPDX identifier is one of the Creative Commons licenses.
+pub fn is_creative_commons(spdx: &str) -> bool {
+    spdx.starts_with("CC-") || spdx.starts_with("CC0-")
+}
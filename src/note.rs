@@ -0,0 +1,96 @@
+use chrono::{DateTime, Local};
+use lazy_static::lazy_static;
+use std::str::FromStr;
+
+pub const DEFAULT_NOTE_TEMPLATE: &str = "Accessed: {accessed_date}";
+
+lazy_static! {
+    // Validated once by `main` at startup, so the `expect` only fires for library users
+    // who skip that check.
+    static ref NOTE_TEMPLATE: NoteTemplate =
+        NoteTemplate::from_env().expect("invalid BIBTEXTER_NOTE_TEMPLATE");
+}
+
+/// A parsed `note` field template, e.g. `Retrieved {accessed_date} from {site_name}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    AccessedDate,
+    AccessedIso,
+    SiteName,
+    Doi,
+}
+
+/// The values a note template can refer to.
+pub struct NoteContext<'a> {
+    pub accessed: DateTime<Local>,
+    pub site_name: &'a str,
+    pub doi: Option<&'a str>,
+}
+
+impl NoteTemplate {
+    /// Reads `BIBTEXTER_NOTE_TEMPLATE`, falling back to `Accessed: {accessed_date}`.
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("BIBTEXTER_NOTE_TEMPLATE") {
+            Ok(template) => template.parse(),
+            Err(_) => DEFAULT_NOTE_TEMPLATE.parse(),
+        }
+    }
+
+    pub fn render(&self, context: &NoteContext) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::AccessedDate => context.accessed.format("%Y-%m-%d").to_string(),
+                TemplatePart::AccessedIso => context.accessed.to_rfc3339(),
+                TemplatePart::SiteName => context.site_name.to_string(),
+                TemplatePart::Doi => context.doi.unwrap_or_default().to_string(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for NoteTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut rest = s;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(TemplatePart::Literal(rest[..start].to_string()));
+            }
+            let Some(len) = rest[start..].find('}') else {
+                return Err(format!("Unclosed placeholder in note template '{}'", s));
+            };
+            parts.push(match &rest[start + 1..start + len] {
+                "accessed_date" => TemplatePart::AccessedDate,
+                "accessed_iso" => TemplatePart::AccessedIso,
+                "site_name" => TemplatePart::SiteName,
+                "doi" => TemplatePart::Doi,
+                other => {
+                    return Err(format!(
+                        "Unknown placeholder '{{{}}}' in note template, expected accessed_date, accessed_iso, site_name or doi",
+                        other
+                    ));
+                }
+            });
+            rest = &rest[start + len + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_string()));
+        }
+        Ok(NoteTemplate { parts })
+    }
+}
+
+/// The template configured for this process.
+pub fn note_template() -> &'static NoteTemplate {
+    &NOTE_TEMPLATE
+}
@@ -0,0 +1,121 @@
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::OutputMode;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, fetch_and_generate_bibtex, format_bibtex};
+use crate::http::HttpClient;
+
+// Uploads larger than this are rejected unless `BIBTEXTER_MAX_PDF_SIZE` says otherwise.
+const DEFAULT_MAX_PDF_SIZE: usize = 10 * 1024 * 1024;
+
+lazy_static! {
+    static ref XMP_PACKET_RE: Regex = Regex::new(r"(?s)<x:xmpmeta\b.*?</x:xmpmeta>").unwrap();
+    static ref XMP_DOI_RE: Regex =
+        Regex::new(r"(?s)<prism:doi>\s*(?:doi:)?([^<]+?)\s*</prism:doi>").unwrap();
+    static ref XMP_TITLE_RE: Regex = Regex::new(r"(?s)<dc:title>(.*?)</dc:title>").unwrap();
+    static ref XMP_CREATOR_RE: Regex =
+        Regex::new(r"(?s)<dc:creator>(.*?)</dc:creator>").unwrap();
+    static ref XMP_DATE_RE: Regex = Regex::new(
+        r"(?s)<prism:(?:publicationDate|coverDate)>\s*([^<]+?)\s*</prism:(?:publicationDate|coverDate)>"
+    )
+    .unwrap();
+    static ref XMP_LI_RE: Regex =
+        Regex::new(r"(?s)<rdf:li[^>]*>\s*([^<]*?)\s*</rdf:li>").unwrap();
+    // PDF string delimiters and XML markup can't appear in a DOI, so they end the match.
+    static ref PDF_DOI_RE: Regex = Regex::new(r#"10\.\d{4,}/[^\s()<>\[\]"]+"#).unwrap();
+}
+
+/// The largest PDF `/api/extract-pdf` accepts, from `BIBTEXTER_MAX_PDF_SIZE` (in bytes).
+pub fn max_pdf_size() -> usize {
+    std::env::var("BIBTEXTER_MAX_PDF_SIZE")
+        .ok()
+        .and_then(|size| size.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_PDF_SIZE)
+}
+
+/// Reads what a PDF says about itself: the XMP metadata packet first, then any
+/// DOI that appears in the file's uncompressed objects (info dictionary, annotations).
+pub fn extract_pdf_metadata(bytes: &[u8]) -> ArticleMetadata {
+    let mut metadata = ArticleMetadata::default();
+
+    if let Some(packet) = XMP_PACKET_RE.find(bytes) {
+        let packet = packet.as_bytes();
+        metadata.doi = capture(&XMP_DOI_RE, packet);
+        metadata.title = XMP_TITLE_RE
+            .captures(packet)
+            .and_then(|caps| xmp_list(&caps[1]).into_iter().next())
+            .unwrap_or_default();
+        metadata.author = XMP_CREATOR_RE
+            .captures(packet)
+            .map(|caps| normalize_authors(xmp_list(&caps[1])).join(" and "))
+            .unwrap_or_default();
+        metadata.year = capture(&XMP_DATE_RE, packet)
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default();
+    }
+
+    if metadata.doi.is_none() {
+        metadata.doi = PDF_DOI_RE
+            .find(bytes)
+            .map(|doi| String::from_utf8_lossy(doi.as_bytes()))
+            .map(|doi| doi.trim_end_matches(['.', ',', ';']).to_string());
+    }
+
+    metadata
+}
+
+/// Builds a BibTeX entry for an uploaded PDF: via DOI content negotiation when the
+/// file names its DOI, otherwise straight from its XMP metadata.
+pub async fn bibtex_from_pdf(client: &impl HttpClient, bytes: &[u8]) -> Result<String, AppError> {
+    if !bytes.starts_with(b"%PDF-") {
+        return Err(AppError::InvalidInput(
+            "The uploaded file is not a PDF.".into(),
+        ));
+    }
+
+    let metadata = extract_pdf_metadata(bytes);
+    if let Some(doi) = &metadata.doi {
+        eprintln!("-> Found DOI {} in PDF.", doi);
+        return fetch_and_generate_bibtex(client, &format!("https://doi.org/{}", doi)).await;
+    }
+
+    if metadata.title.is_empty() {
+        return Err(AppError::ExtractionError(
+            "The PDF has no DOI or XMP metadata.".into(),
+        ));
+    }
+    eprintln!("-> Extracted metadata from PDF XMP packet.");
+    format_bibtex(metadata, None, OutputMode::Bibtex)
+}
+
+fn capture(re: &Regex, haystack: &[u8]) -> Option<String> {
+    re.captures(haystack)
+        .map(|caps| xml_text(&caps[1]))
+        .filter(|value| !value.is_empty())
+}
+
+// Reads the `rdf:li` items of an XMP `rdf:Alt`/`rdf:Seq`, or the element's own text.
+fn xmp_list(inner: &[u8]) -> Vec<String> {
+    let items = XMP_LI_RE
+        .captures_iter(inner)
+        .map(|caps| xml_text(&caps[1]))
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    if items.is_empty() && !inner.contains(&b'<') {
+        let text = xml_text(inner);
+        return if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![text]
+        };
+    }
+    items
+}
+
+fn xml_text(bytes: &[u8]) -> String {
+    html_escape::decode_html_entities(String::from_utf8_lossy(bytes).trim()).into_owned()
+}
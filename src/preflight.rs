@@ -0,0 +1,189 @@
+use std::time::Duration;
+use url::Url;
+
+use crate::error::AppError;
+use crate::http::HttpClient;
+use crate::robots::RobotsCache;
+
+// --- Extraction Pre-flight ---
+
+// The whole pre-flight must answer well before a full extraction would; checks still
+// running after this long count as a failure to reach the page.
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Domains from `BIBTEXTER_BLOCKED_DOMAINS`, a comma-separated list such as
+/// `example.com,paywalled.org`. Subdomains of a listed domain are blocked too.
+pub fn blocked_domains() -> Vec<String> {
+    std::env::var("BIBTEXTER_BLOCKED_DOMAINS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|domain| domain.trim().trim_start_matches('.').to_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect()
+}
+
+/// Whether `host` is, or is a subdomain of, a blocked domain.
+pub fn is_blocked(host: &str, blocked: &[String]) -> bool {
+    let host = host.to_lowercase();
+    blocked.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+/// Refuses URLs on a blocked domain before any request is made to them.
+pub fn check_not_blocked(url: &Url) -> Result<(), AppError> {
+    match url.host_str() {
+        Some(host) if is_blocked(host, &blocked_domains()) => Err(AppError::InvalidInput(format!(
+            "Extraction from {} is blocked on this server.",
+            host
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a `robots.txt` lets any crawler fetch `path`. Only the `User-agent: *` groups
+/// are read; the longest matching `Allow` or `Disallow` rule wins, with `Allow` breaking ties.
+pub fn robots_allows(robots_txt: &str, path: &str) -> bool {
+    let mut applies = false;
+    let mut in_agents = false;
+    // The longest matching rule so far, and whether it allows.
+    let mut best: Option<(usize, bool)> = None;
+
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_lowercase().as_str() {
+            "user-agent" => {
+                // Consecutive `User-agent` lines share the rules that follow them.
+                if !in_agents {
+                    applies = false;
+                    in_agents = true;
+                }
+                applies |= value == "*";
+            }
+            rule @ ("allow" | "disallow") => {
+                in_agents = false;
+                // An empty `Disallow` allows everything, so it is no rule at all.
+                if !applies || value.is_empty() || !path.starts_with(value) {
+                    continue;
+                }
+                let allow = rule == "allow";
+                best = match best {
+                    Some((len, best_allow))
+                        if len > value.len() || (len == value.len() && best_allow) =>
+                    {
+                        Some((len, best_allow))
+                    }
+                    _ => Some((value.len(), allow)),
+                };
+            }
+            _ => in_agents = false,
+        }
+    }
+    best.is_none_or(|(_, allow)| allow)
+}
+
+/// How extraction of a URL is expected to go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendedStrategy {
+    /// A doi.org link, answered by content negotiation.
+    Doi,
+    /// A web page, scraped for its metadata.
+    HtmlScrape,
+}
+
+impl RecommendedStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecommendedStrategy::Doi => "doi",
+            RecommendedStrategy::HtmlScrape => "html_scrape",
+        }
+    }
+}
+
+/// The outcome of a pre-flight check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preflight {
+    pub extractable: bool,
+    pub strategy: RecommendedStrategy,
+    pub domain_blocked: bool,
+}
+
+/// Checks, without extracting anything, whether `/api/bibtex` is likely to succeed for
+/// `url`: the domain must not be blocked, robots.txt must allow the page, and a HEAD
+/// request must reach it and find HTML. doi.org links skip the page checks. Only a
+/// malformed URL is an error. robots.txt files are kept in `robots` between checks.
+pub async fn preflight(
+    client: &impl HttpClient,
+    robots: &RobotsCache,
+    url: &str,
+) -> Result<Preflight, AppError> {
+    let url = Url::parse(url.trim()).map_err(AppError::UrlParseError)?;
+    let host = url.host_str().unwrap_or_default();
+    if !matches!(url.scheme(), "http" | "https") || host.is_empty() {
+        return Err(AppError::InvalidInput(
+            "The url query parameter must be an http or https URL.".into(),
+        ));
+    }
+
+    if host == "doi.org" || host == "dx.doi.org" {
+        return Ok(Preflight {
+            extractable: url.path().len() > 1,
+            strategy: RecommendedStrategy::Doi,
+            domain_blocked: false,
+        });
+    }
+    if is_blocked(host, &blocked_domains()) {
+        return Ok(Preflight {
+            extractable: false,
+            strategy: RecommendedStrategy::HtmlScrape,
+            domain_blocked: true,
+        });
+    }
+
+    let reachable = tokio::time::timeout(PREFLIGHT_TIMEOUT, page_checks(client, robots, &url))
+        .await
+        .unwrap_or_else(|_| {
+            eprintln!("-> Pre-flight for {} timed out.", url);
+            false
+        });
+    Ok(Preflight {
+        extractable: reachable,
+        strategy: RecommendedStrategy::HtmlScrape,
+        domain_blocked: false,
+    })
+}
+
+/// Whether robots.txt allows the page and a HEAD request finds HTML there.
+async fn page_checks(client: &impl HttpClient, robots: &RobotsCache, url: &Url) -> bool {
+    if !robots.rules(client, url).await.allows(url.path()) {
+        eprintln!("-> robots.txt disallows {}.", url);
+        return false;
+    }
+
+    let Ok(res) = client.head(url.as_str()).await else {
+        return false;
+    };
+    // Some servers refuse HEAD outright but serve the page to GET.
+    if !res.status.is_success() && res.status != reqwest::StatusCode::METHOD_NOT_ALLOWED {
+        return false;
+    }
+    match res
+        .headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(content_type) => {
+            let content_type = content_type.to_lowercase();
+            content_type.starts_with("text/html")
+                || content_type.starts_with("application/xhtml+xml")
+        }
+        None => true,
+    }
+}
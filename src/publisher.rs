@@ -0,0 +1,222 @@
+use lazy_static::lazy_static;
+use phf::phf_map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::http::HttpClient;
+use crate::issn::extract_issn;
+
+// --- Publisher Resolution ---
+
+// Print and electronic ISSNs of well-known journals from the major academic publishers.
+const PUBLISHERS_BY_ISSN: &[(&str, &str)] = &[
+    ("0028-0836", "Springer Nature"),
+    ("1476-4687", "Springer Nature"),
+    ("2041-1723", "Springer Nature"),
+    ("2045-2322", "Springer Nature"),
+    ("2196-1115", "Springer"),
+    ("0885-6125", "Springer"),
+    (
+        "0036-8075",
+        "American Association for the Advancement of Science",
+    ),
+    (
+        "1095-9203",
+        "American Association for the Advancement of Science",
+    ),
+    ("0092-8674", "Elsevier"),
+    ("0140-6736", "Elsevier"),
+    ("0004-3702", "Elsevier"),
+    ("0893-6080", "Elsevier"),
+    ("0028-4793", "Massachusetts Medical Society"),
+    ("0027-8424", "National Academy of Sciences"),
+    ("1091-6490", "National Academy of Sciences"),
+    ("0004-5411", "Association for Computing Machinery"),
+    ("0001-0782", "Association for Computing Machinery"),
+    ("0360-0300", "Association for Computing Machinery"),
+    ("0162-8828", "IEEE"),
+    ("0018-9219", "IEEE"),
+    ("2169-3536", "IEEE"),
+    ("1932-6203", "Public Library of Science"),
+    ("0098-7484", "American Medical Association"),
+    ("0959-8138", "BMJ Publishing Group"),
+    ("0031-9007", "American Physical Society"),
+    ("0002-7863", "American Chemical Society"),
+    ("1367-4803", "Oxford University Press"),
+    ("0305-1048", "Oxford University Press"),
+    ("0006-3444", "Oxford University Press"),
+    ("2050-084X", "eLife Sciences Publications"),
+    ("1532-4435", "Microtome Publishing"),
+    ("0002-8282", "American Economic Association"),
+    ("2054-5703", "The Royal Society"),
+    ("1664-1078", "Frontiers Media"),
+    ("1424-8220", "MDPI"),
+    ("0038-0644", "Wiley"),
+    ("0021-8979", "AIP Publishing"),
+    ("0162-1459", "Taylor & Francis"),
+    (
+        "0036-1445",
+        "Society for Industrial and Applied Mathematics",
+    ),
+];
+
+// Head office of the major publishers, keyed by lowercased name, for the `address` field.
+static PUBLISHER_ADDRESSES: phf::Map<&'static str, &'static str> = phf_map! {
+    "springer" => "Berlin, Germany",
+    "springer nature" => "Berlin, Germany",
+    "elsevier" => "Amsterdam, Netherlands",
+    "acm" => "New York, NY, USA",
+    "association for computing machinery" => "New York, NY, USA",
+    "ieee" => "New York, NY, USA",
+    "institute of electrical and electronics engineers" => "New York, NY, USA",
+    "wiley" => "Hoboken, NJ, USA",
+    "john wiley & sons" => "Hoboken, NJ, USA",
+};
+
+// The same publishers by the domains their articles are hosted on.
+static PUBLISHERS_BY_DOMAIN: phf::Map<&'static str, &'static str> = phf_map! {
+    "springer.com" => "springer",
+    "nature.com" => "springer nature",
+    "sciencedirect.com" => "elsevier",
+    "elsevier.com" => "elsevier",
+    "acm.org" => "acm",
+    "ieee.org" => "ieee",
+    "wiley.com" => "wiley",
+};
+
+// How often the ISSN cache answered a lookup, reported by `/api/stats`.
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    // Results of ISSN Portal lookups, including misses, so each journal is only queried once.
+    static ref ISSN_CACHE: Mutex<HashMap<String, JournalRecord>> = Mutex::new(HashMap::new());
+}
+
+/// What the ISSN Portal knows about a journal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JournalRecord {
+    // The linking ISSN shared by all of the journal's editions.
+    pub issn_l: Option<String>,
+    pub publisher: Option<String>,
+}
+
+/// Resolves the official publisher name for a journal ISSN.
+/// Checks the built-in table first, then the ISSN Portal API.
+pub async fn resolve_publisher(client: &impl HttpClient, issn: &str) -> Option<String> {
+    resolve_journal(client, &[issn]).await.publisher
+}
+
+/// Resolves the publisher and ISSN-L of a journal known by one or more ISSNs, such as
+/// its electronic and print ones, preferred in the order given.
+///
+/// Only the first ISSN is looked up, and its record is cached under every ISSN given and
+/// its ISSN-L, so articles that cite the journal by another edition's ISSN share it.
+pub async fn resolve_journal(client: &impl HttpClient, issns: &[&str]) -> JournalRecord {
+    let issns: Vec<String> = issns
+        .iter()
+        .map(|issn| issn.trim().to_uppercase())
+        .collect();
+    let Some(first) = issns.first() else {
+        return JournalRecord::default();
+    };
+
+    if let Some((_, publisher)) = PUBLISHERS_BY_ISSN
+        .iter()
+        .find(|(known, _)| issns.iter().any(|issn| issn == known))
+    {
+        return JournalRecord {
+            publisher: Some(publisher.to_string()),
+            ..Default::default()
+        };
+    }
+
+    if let Some(cached) = {
+        let cache = ISSN_CACHE.lock().unwrap();
+        issns.iter().find_map(|issn| cache.get(issn).cloned())
+    } {
+        CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+        return cached;
+    }
+    CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+
+    let record = lookup_issn_portal(client, first).await.unwrap_or_default();
+    let mut cache = ISSN_CACHE.lock().unwrap();
+    for issn in issns.iter().chain(&record.issn_l) {
+        cache.insert(issn.clone(), record.clone());
+    }
+    record
+}
+
+/// Hits and misses of the ISSN Portal cache since the process started.
+pub fn cache_counts() -> (u64, u64) {
+    (
+        CACHE_HITS.load(Ordering::Relaxed),
+        CACHE_MISSES.load(Ordering::Relaxed),
+    )
+}
+
+/// Queries the ISSN Portal's JSON-LD record for the publisher's name and the ISSN-L.
+async fn lookup_issn_portal(client: &impl HttpClient, issn: &str) -> Option<JournalRecord> {
+    let api_url = format!("https://portal.issn.org/resource/ISSN/{}?format=json", issn);
+    let res = client.get(&api_url, Some("application/json")).await.ok()?;
+    if !res.status.is_success() {
+        return None;
+    }
+
+    let record = serde_json::from_str::<Value>(&res.body).ok()?;
+    let graph = record.get("@graph")?.as_array()?;
+
+    // The linking ISSN is a node of its own, `resource/ISSN-L/{issn}`.
+    let issn_l = graph.iter().find_map(|node| {
+        node.get("@id")?
+            .as_str()?
+            .strip_prefix("resource/ISSN-L/")
+            .and_then(extract_issn)
+    });
+
+    // The publisher is either inlined or a reference to another node in the graph.
+    let publisher = graph.iter().find_map(|node| match node.get("publisher")? {
+        Value::String(reference) => graph
+            .iter()
+            .find(|other| other.get("@id").and_then(Value::as_str) == Some(reference))
+            .and_then(|other| other.get("name"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        Value::Object(publisher) => publisher
+            .get("name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    });
+    Some(JournalRecord { issn_l, publisher })
+}
+
+/// The head office of a major publisher, e.g. "New York, NY, USA" for the ACM. Matches
+/// the publisher's name loosely ("Elsevier BV", "Institute of Electrical and Electronics
+/// Engineers (IEEE)"), then falls back to the domain the article was found on.
+pub fn publisher_address(publisher: &str, host: Option<&str>) -> Option<&'static str> {
+    let name = publisher.trim().to_lowercase();
+    let (outside, inside) = match name.split_once('(') {
+        Some((outside, inside)) => (outside.trim(), inside.trim_end_matches(')').trim()),
+        None => (name.as_str(), ""),
+    };
+    let first_word = outside.split_whitespace().next().unwrap_or_default();
+    if let Some(address) = [name.as_str(), outside, inside, first_word]
+        .iter()
+        .find_map(|candidate| PUBLISHER_ADDRESSES.get(*candidate))
+    {
+        return Some(address);
+    }
+
+    // `link.springer.com` -> `springer.com`, and so on up to the registrable domain.
+    let mut domain = host?;
+    loop {
+        if let Some(publisher) = PUBLISHERS_BY_DOMAIN.get(domain) {
+            return PUBLISHER_ADDRESSES.get(*publisher).copied();
+        }
+        domain = domain.split_once('.')?.1;
+    }
+}
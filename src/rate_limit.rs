@@ -0,0 +1,230 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::access_log::{TrustedProxies, client_ip};
+use crate::error::AppError;
+
+// --- Rate Limiting ---
+
+// Requests per client per minute when the environment does not say otherwise. Searches
+// fan out to three upstream APIs each, so they get a much smaller allowance.
+const DEFAULT_EXTRACT_LIMIT: u32 = 60;
+const DEFAULT_SEARCH_LIMIT: u32 = 10;
+
+// Variables naming API keys, e.g. `BIBTEXTER_API_KEY_ALICE=key123:1000rpm`.
+const API_KEY_VAR_PREFIX: &str = "BIBTEXTER_API_KEY_";
+
+/// Counts requests per client in fixed windows and turns away those over the limit.
+/// Requests carrying a known API key are counted per key, at that key's own limit,
+/// so that users sharing an address (behind NAT, say) don't use up each other's.
+/// Clients are told apart by their peer address; `X-Forwarded-For` is only read from
+/// trusted proxies.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    api_keys: ApiKeys,
+    trusted_proxies: TrustedProxies,
+    clients: Mutex<HashMap<String, ClientWindow>>,
+}
+
+/// The API keys this server knows, each with its own requests-per-minute limit.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    keys: HashMap<String, ApiKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    /// The name from the variable, e.g. `ALICE`; the key itself is never logged.
+    pub name: String,
+    pub limit: u32,
+}
+
+impl ApiKeys {
+    /// Reads every `BIBTEXTER_API_KEY_{NAME}=key:{limit}rpm` variable.
+    pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Reads API keys from variables like those in the environment. Malformed ones are
+    /// skipped with a warning.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut keys = HashMap::new();
+        for (var, value) in vars {
+            let Some(name) = var.strip_prefix(API_KEY_VAR_PREFIX) else {
+                continue;
+            };
+            match parse_api_key(&value) {
+                Some((key, limit)) => {
+                    keys.insert(
+                        key,
+                        ApiKey {
+                            name: name.to_string(),
+                            limit,
+                        },
+                    );
+                }
+                None => eprintln!("-> Warning: ignoring {}; expected key:{{limit}}rpm.", var),
+            }
+        }
+        Self { keys }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ApiKey> {
+        self.keys.get(key)
+    }
+}
+
+/// Splits a value such as `key123:1000rpm` into the key and its per-minute limit.
+fn parse_api_key(value: &str) -> Option<(String, u32)> {
+    let (key, limit) = value.trim().rsplit_once(':')?;
+    let limit = limit.trim().strip_suffix("rpm").unwrap_or(limit).trim();
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key.to_string(), limit.parse().ok()?))
+}
+
+/// The API key a request presents, in `X-API-Key` or as an `Authorization: Bearer` token.
+pub fn request_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+}
+
+/// The token admin endpoints require, from `BIBTEXTER_ADMIN_TOKEN`. Without one, they
+/// are switched off.
+pub fn admin_token() -> Option<String> {
+    std::env::var("BIBTEXTER_ADMIN_TOKEN")
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// Refuses requests that don't present the admin token, the same way API keys are
+/// presented. Tokens are compared in constant time.
+pub fn require_admin(headers: &HeaderMap) -> Result<(), AppError> {
+    let (Some(expected), Some(presented)) = (admin_token(), request_api_key(headers)) else {
+        return Err(AppError::Unauthorized);
+    };
+    let (expected, presented) = (expected.as_bytes(), presented.as_bytes());
+    let differences = expected
+        .iter()
+        .zip(presented)
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    if expected.len() != presented.len() || differences != 0 {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct ClientWindow {
+    started: Instant,
+    count: u32,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            api_keys: ApiKeys::default(),
+            trusted_proxies: TrustedProxies::default(),
+            clients: Mutex::default(),
+        }
+    }
+
+    /// Lets requests with one of `api_keys` be limited per key instead of per client.
+    pub fn with_api_keys(mut self, api_keys: ApiKeys) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Lets the proxies in `trusted_proxies` say which client they forwarded for.
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    /// The limit for extraction endpoints, per minute from `BIBTEXTER_EXTRACT_RATE_LIMIT`.
+    pub fn extraction_from_env() -> Self {
+        Self::per_minute_from_env("BIBTEXTER_EXTRACT_RATE_LIMIT", DEFAULT_EXTRACT_LIMIT)
+    }
+
+    /// The limit for `/api/search`, per minute from `BIBTEXTER_SEARCH_RATE_LIMIT`.
+    pub fn search_from_env() -> Self {
+        Self::per_minute_from_env("BIBTEXTER_SEARCH_RATE_LIMIT", DEFAULT_SEARCH_LIMIT)
+    }
+
+    fn per_minute_from_env(var: &str, default: u32) -> Self {
+        let limit = std::env::var(var)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(default);
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    /// Counts a request from `client`, returning whether it is within the limit.
+    pub fn check(&self, client: &str) -> bool {
+        self.check_with_limit(client, self.limit)
+    }
+
+    /// Counts a request, against its API key when it carries a known one and against
+    /// the client's address otherwise.
+    pub fn check_request(&self, headers: &HeaderMap, client: &str) -> bool {
+        match request_api_key(headers).and_then(|key| self.api_keys.get(key)) {
+            Some(key) => self.check_with_limit(&format!("key:{}", key.name), key.limit),
+            None => self.check(client),
+        }
+    }
+
+    fn check_with_limit(&self, client: &str, limit: u32) -> bool {
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap();
+        // Forget clients whose window has passed so the map does not grow without bound.
+        clients.retain(|_, window| now.duration_since(window.started) < self.window);
+
+        let window = clients.entry(client.to_string()).or_insert(ClientWindow {
+            started: now,
+            count: 0,
+        });
+        window.count += 1;
+        window.count <= limit
+    }
+}
+
+/// Middleware that answers `429 Too Many Requests` once a client is over the limit.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let client = client_ip(
+        req.headers(),
+        req.extensions().get::<ConnectInfo<SocketAddr>>(),
+        &limiter.trusted_proxies,
+    )
+    .unwrap_or_default();
+    if !limiter.check_request(req.headers(), &client) {
+        return AppError::RateLimited.into_response();
+    }
+    next.run(req).await
+}
@@ -0,0 +1,112 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::http::{HttpClient, encode_path_segment};
+
+// --- Crossref Relations ---
+
+lazy_static! {
+    static ref NOTE_FIELD_RE: Regex = Regex::new(r"(?i)\bnote\s*=\s*[{\x22]").unwrap();
+}
+
+/// DOIs Crossref records as standing in a notable relation to a work.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Relations {
+    /// Retraction notices for the work.
+    pub retracted_by: Vec<String>,
+    /// Expressions of concern the journal has published about the work.
+    pub expression_of_concern: Vec<String>,
+    /// The published versions of a preprint.
+    pub preprint_of: Vec<String>,
+}
+
+impl Relations {
+    /// The warning a citation of the work should carry, e.g.
+    /// `RETRACTED: see doi:10.1234/retraction`.
+    pub fn warning_note(&self) -> Option<String> {
+        let see = |dois: &[String]| {
+            dois.iter()
+                .map(|doi| format!("doi:{}", doi))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if !self.retracted_by.is_empty() {
+            Some(format!("RETRACTED: see {}", see(&self.retracted_by)))
+        } else if !self.expression_of_concern.is_empty() {
+            Some(format!(
+                "EXPRESSION OF CONCERN: see {}",
+                see(&self.expression_of_concern)
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads the `relation` object of a Crossref works API response.
+pub fn parse_relations(work: &Value) -> Relations {
+    let relation = work.get("message").unwrap_or(work).get("relation");
+    let dois = |name: &str| -> Vec<String> {
+        relation
+            .and_then(|relation| relation.get(name))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|related| {
+                related
+                    .get("id-type")
+                    .and_then(Value::as_str)
+                    .is_some_and(|id_type| id_type.eq_ignore_ascii_case("doi"))
+            })
+            .filter_map(|related| related.get("id").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect()
+    };
+    Relations {
+        retracted_by: dois("is-retracted-by"),
+        expression_of_concern: dois("has-expression-of-concern"),
+        preprint_of: dois("is-preprint-of"),
+    }
+}
+
+/// Looks up a DOI's relations in the Crossref works API. A failed lookup finds none.
+pub async fn fetch_relations(client: &impl HttpClient, doi: &str) -> Relations {
+    let api_url = format!(
+        "https://api.crossref.org/works/{}",
+        encode_path_segment(doi)
+    );
+    match client.get(&api_url, Some("application/json")).await {
+        Ok(res) if res.status.is_success() => serde_json::from_str::<Value>(&res.body)
+            .map(|work| parse_relations(&work))
+            .unwrap_or_default(),
+        _ => Relations::default(),
+    }
+}
+
+/// Puts `note` at the front of a finished entry's `note` field, adding the field if the
+/// entry has none. Works on entries doi.org returns as well as our own.
+pub fn prepend_note(bibtex: &str, note: &str) -> String {
+    if let Some(existing) = NOTE_FIELD_RE.find(bibtex) {
+        return format!(
+            "{}{}; {}",
+            &bibtex[..existing.end()],
+            note,
+            &bibtex[existing.end()..]
+        );
+    }
+    // After the citation key, e.g. `@article{Doe2025Title,`.
+    let Some(key_end) = bibtex
+        .find('@')
+        .and_then(|at| bibtex[at..].find(',').map(|comma| at + comma + 1))
+    else {
+        return bibtex.to_string();
+    };
+    format!(
+        "{}\n  note = {{{}}},{}",
+        &bibtex[..key_end],
+        note,
+        &bibtex[key_end..]
+    )
+}
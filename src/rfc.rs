@@ -0,0 +1,84 @@
+use serde::Deserialize;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::{BibtexEntryType, OutputMode};
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy, format_bibtex};
+use crate::http::HttpClient;
+use crate::sites::fetch_json;
+
+// --- IETF RFCs ---
+
+// The RFC Editor's metadata record for one RFC, from `/rfc/rfc{number}.json`.
+#[derive(Deserialize, Debug)]
+struct RfcRecord {
+    title: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    pub_date: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+}
+
+/// Reads an RFC number written as `8574`, `RFC 8574` or `rfc8574`.
+pub fn parse_rfc_number(input: &str) -> Option<u32> {
+    let input = input.trim();
+    let digits = input
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("rfc"))
+        .map_or(input, |_| input[3..].trim_start());
+    digits.parse().ok().filter(|number| *number > 0)
+}
+
+/// Looks an RFC up in the RFC Editor's metadata and describes it as an IETF technical report.
+pub async fn lookup_rfc(
+    client: &impl HttpClient,
+    number: u32,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!("https://www.rfc-editor.org/rfc/rfc{}.json", number);
+    let record: RfcRecord = fetch_json(client, &api_url).await?;
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::TechReport,
+        title: record.title.trim().to_string(),
+        // Editors are listed as "J. Doe, Ed.", which is not part of the name.
+        author: normalize_authors(
+            record
+                .authors
+                .iter()
+                .map(|author| author.trim_end_matches(", Ed.").trim().to_string())
+                .collect(),
+        )
+        .join(" and "),
+        year: record
+            .pub_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        institution: Some("IETF".to_string()),
+        number: Some(format!("RFC {}", number)),
+        resource_type: Some("RFC".to_string()),
+        doi: record.doi,
+        abstract_text: record.abstract_text.filter(|text| !text.is_empty()),
+        keywords: record.keywords,
+        ..Default::default()
+    })
+}
+
+/// Generates a `@techreport` entry for an RFC, given its number in any common form.
+pub async fn bibtex_for_rfc(
+    client: &impl HttpClient,
+    rfc: &str,
+    mode: OutputMode,
+) -> Result<String, AppError> {
+    let number = parse_rfc_number(rfc)
+        .ok_or_else(|| AppError::InvalidInput(format!("'{}' is not an RFC number.", rfc.trim())))?;
+    let metadata = lookup_rfc(client, number).await?;
+    let url = format!("https://www.rfc-editor.org/info/rfc{}", number);
+    format_bibtex(metadata, Some(&url), mode)
+}
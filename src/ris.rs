@@ -0,0 +1,106 @@
+use crate::bib_json::{JsonEntry, bibtex_to_json};
+use crate::error::AppError;
+
+// --- BibTeX to RIS ---
+
+// RIS reference types for BibTeX entry types. Anything else is `GEN`.
+const RIS_TYPES: &[(&str, &str)] = &[
+    ("article", "JOUR"),
+    ("book", "BOOK"),
+    ("inbook", "CHAP"),
+    ("incollection", "CHAP"),
+    ("inproceedings", "CPAPER"),
+    ("conference", "CPAPER"),
+    ("proceedings", "CONF"),
+    ("collection", "EDBOOK"),
+    ("phdthesis", "THES"),
+    ("mastersthesis", "THES"),
+    ("techreport", "RPRT"),
+    ("manual", "COMP"),
+    ("unpublished", "UNPB"),
+    ("online", "ELEC"),
+    ("misc", "ELEC"),
+    ("dataset", "DATA"),
+];
+
+// BibTeX fields copied one-to-one into an RIS tag.
+const RIS_FIELDS: &[(&str, &str)] = &[
+    ("title", "TI"),
+    ("journal", "JO"),
+    ("booktitle", "T2"),
+    ("series", "T3"),
+    ("volume", "VL"),
+    ("number", "IS"),
+    ("year", "PY"),
+    ("publisher", "PB"),
+    ("address", "CY"),
+    ("isbn", "SN"),
+    ("issn", "SN"),
+    ("doi", "DO"),
+    ("url", "UR"),
+    ("abstract", "AB"),
+    ("note", "N1"),
+    ("langid", "LA"),
+];
+
+/// Converts every entry in a BibTeX string to an RIS record, for reference managers
+/// such as EndNote and Zotero that import RIS.
+pub fn bibtex_to_ris(input: &str) -> Result<String, AppError> {
+    Ok(bibtex_to_json(input)?
+        .into_iter()
+        .map(|entry| ris_record(entry.into_plain_text()))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// One RIS record, from `TY` to `ER`.
+fn ris_record(entry: JsonEntry) -> String {
+    let ris_type = RIS_TYPES
+        .iter()
+        .find(|(bibtex, _)| *bibtex == entry.entry_type)
+        .map_or("GEN", |(_, ris)| ris);
+    let mut lines = vec![
+        format!("TY  - {}", ris_type),
+        format!("ID  - {}", entry.key),
+    ];
+    let field = |name: &str| entry.fields.get(name).map(String::as_str);
+
+    for (bibtex, tag) in [("author", "AU"), ("editor", "ED")] {
+        for name in field(bibtex)
+            .into_iter()
+            .flat_map(|names| names.split(" and "))
+        {
+            lines.push(format!("{}  - {}", tag, name.trim()));
+        }
+    }
+    for (bibtex, tag) in RIS_FIELDS {
+        if let Some(value) = field(bibtex) {
+            lines.push(format!("{}  - {}", tag, value));
+        }
+    }
+    // Web pages cited as `@misc` keep their address in `howpublished = {\url{...}}`.
+    if field("url").is_none()
+        && let Some(url) = field("howpublished").filter(|value| value.starts_with("http"))
+    {
+        lines.push(format!("UR  - {}", url));
+    }
+    if let Some(pages) = field("pages") {
+        let (start, end) = pages
+            .split_once(['–', '-'])
+            .map_or((pages, None), |(start, end)| (start, Some(end)));
+        lines.push(format!("SP  - {}", start.trim()));
+        if let Some(end) = end {
+            lines.push(format!("EP  - {}", end.trim_start_matches('-').trim()));
+        }
+    }
+    for keyword in field("keywords")
+        .into_iter()
+        .flat_map(|keywords| keywords.split([',', ';']))
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+    {
+        lines.push(format!("KW  - {}", keyword));
+    }
+    lines.push("ER  - ".to_string());
+    lines.join("\n") + "\n"
+}
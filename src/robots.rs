@@ -0,0 +1,134 @@
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+use crate::http::HttpClient;
+use crate::preflight::robots_allows;
+
+// --- robots.txt Cache ---
+
+// How many hosts' robots.txt files are kept, and for how long.
+const DEFAULT_CAPACITY: usize = 1000;
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What a host's robots.txt says about crawling it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RobotsRules {
+    /// No robots.txt, or none that could be read.
+    AllowAll,
+    /// The host is rate limiting us, so nothing should be fetched from it.
+    DisallowAll,
+    /// The text of the robots.txt file.
+    Rules(String),
+}
+
+impl RobotsRules {
+    /// Reads the response to a robots.txt request. A `429 Too Many Requests` is taken as
+    /// a refusal of everything; any other failure places no restrictions.
+    pub fn from_response(status: StatusCode, body: &str) -> Self {
+        if status.is_success() {
+            RobotsRules::Rules(body.to_string())
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
+            RobotsRules::DisallowAll
+        } else {
+            RobotsRules::AllowAll
+        }
+    }
+
+    pub fn allows(&self, path: &str) -> bool {
+        match self {
+            RobotsRules::AllowAll => true,
+            RobotsRules::DisallowAll => false,
+            RobotsRules::Rules(robots_txt) => robots_allows(robots_txt, path),
+        }
+    }
+}
+
+/// Keeps each host's robots.txt so it is fetched at most once a day. When full, the
+/// least recently used host is forgotten.
+#[derive(Debug)]
+pub struct RobotsCache {
+    capacity: usize,
+    ttl: Duration,
+    hosts: Mutex<HashMap<String, CachedRobots>>,
+}
+
+#[derive(Debug)]
+struct CachedRobots {
+    rules: Arc<RobotsRules>,
+    fetched: Instant,
+    last_used: Instant,
+}
+
+impl Default for RobotsCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
+
+impl RobotsCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            hosts: Mutex::default(),
+        }
+    }
+
+    /// The rules for the host of `url`, fetching its robots.txt unless a fresh copy is
+    /// cached. A 429 is not cached, so the host is asked again once it has calmed down.
+    pub async fn rules(&self, client: &impl HttpClient, url: &Url) -> Arc<RobotsRules> {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        if let Some(rules) = self.get(&host) {
+            return rules;
+        }
+
+        let robots_url = format!("{}/robots.txt", url.origin().ascii_serialization());
+        let rules = match client.get(&robots_url, Some("text/plain")).await {
+            Ok(res) => RobotsRules::from_response(res.status, &res.body),
+            Err(_) => return Arc::new(RobotsRules::AllowAll),
+        };
+        let rules = Arc::new(rules);
+        if *rules != RobotsRules::DisallowAll {
+            self.insert(host, rules.clone());
+        }
+        rules
+    }
+
+    fn get(&self, host: &str) -> Option<Arc<RobotsRules>> {
+        let now = Instant::now();
+        let mut hosts = self.hosts.lock().unwrap();
+        let cached = hosts.get_mut(host)?;
+        if now.duration_since(cached.fetched) >= self.ttl {
+            hosts.remove(host);
+            return None;
+        }
+        cached.last_used = now;
+        Some(cached.rules.clone())
+    }
+
+    fn insert(&self, host: String, rules: Arc<RobotsRules>) {
+        let now = Instant::now();
+        let mut hosts = self.hosts.lock().unwrap();
+        hosts.retain(|_, cached| now.duration_since(cached.fetched) < self.ttl);
+        if hosts.len() >= self.capacity
+            && !hosts.contains_key(&host)
+            && let Some(oldest) = hosts
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(host, _)| host.clone())
+        {
+            hosts.remove(&oldest);
+        }
+        hosts.insert(
+            host,
+            CachedRobots {
+                rules,
+                fetched: now,
+                last_used: now,
+            },
+        );
+    }
+}
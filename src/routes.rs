@@ -0,0 +1,362 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{Html, IntoResponse, Response},
+};
+use lazy_static::lazy_static;
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use url::Url;
+
+use crate::AppState;
+use crate::bibtex::{FieldFilter, OutputMode, compact_bibtex, filter_bibtex_fields};
+use crate::error::AppError;
+use crate::extract::{
+    ArticleMetadata, URL_TITLE_WARNING, fetch_and_generate_entry, fetch_metadata, format_bibtex,
+    require_real_title,
+};
+use crate::journals::expand_journal_abbreviations;
+
+// Represents the query parameter from the URL, e.g., /get_bibtex?url=...
+#[derive(Deserialize, Serialize)]
+pub struct BibtexQuery {
+    pub url: String,
+    #[serde(default)]
+    pub mode: OutputMode,
+    // Replace abbreviated journal names such as "J. ACM" with their full names.
+    #[serde(default)]
+    pub expand_journal_abbrevs: bool,
+    // `pretty=false` writes each entry on a single line.
+    #[serde(default = "default_pretty")]
+    pub pretty: bool,
+    // `strict=true` fails instead of making a title up from the URL path.
+    #[serde(default)]
+    pub strict: bool,
+    // Comma-separated field names to keep, and to drop, e.g. `exclude_fields=abstract,note`.
+    pub include_fields: Option<String>,
+    pub exclude_fields: Option<String>,
+}
+
+impl BibtexQuery {
+    pub fn field_filter(&self) -> FieldFilter {
+        FieldFilter::new(
+            self.include_fields.as_deref(),
+            self.exclude_fields.as_deref(),
+        )
+    }
+}
+
+fn default_pretty() -> bool {
+    true
+}
+
+// Fields every entry needs, shown on the preview page even when empty.
+const REQUIRED_FIELDS: &[&str] = &["title", "author", "year"];
+
+// The form page never changes, so browsers may cache it and revalidate by ETag.
+const FORM_HTML: &str = r#"
+        <!doctype html>
+        <html>
+            <head>
+                <title>BibTeX Extractor</title>
+                <style>
+                    body { font-family: sans-serif; max-width: 800px; margin: auto; padding: 2em; background: #f4f4f4; }
+                    input { width: 100%; padding: 8px; margin-bottom: 1em; }
+                    pre { background: #e3e3e3; padding: 1em; white-space: pre-wrap; word-wrap: break-word; }
+                </style>
+            </head>
+            <body>
+                <h1>Rust BibTeX Extractor</h1>
+                <p>Enter a URL to attempt to extract its BibTeX entry.</p>
+                <form action="/get_bibtex" method="get">
+                    <input type="url" name="url" placeholder="https://example.com" required>
+                    <select name="mode">
+                        <option value="bibtex">BibTeX</option>
+                        <option value="biblatex">BibLaTeX</option>
+                        <option value="biblatex_arxiv">BibLaTeX (arXiv as @online)</option>
+                    </select>
+                    <button type="submit">Get BibTeX</button>
+                </form>
+            </body>
+        </html>
+        "#;
+
+lazy_static! {
+    // The first 16 hex digits of the page's SHA-256, quoted as an HTTP entity tag.
+    static ref FORM_ETAG: String = {
+        let digest = digest::digest(&digest::SHA256, FORM_HTML.as_bytes());
+        let hex: String = digest.as_ref()[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("\"{}\"", hex)
+    };
+}
+
+/// Handler for the main page, showing a simple HTML form.
+pub async fn show_form(headers: HeaderMap) -> Response {
+    let cache_headers = [
+        (header::ETAG, FORM_ETAG.as_str()),
+        (header::CACHE_CONTROL, "public, max-age=86400"),
+    ];
+    if etag_matches(&headers, &FORM_ETAG) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+    (cache_headers, Html(FORM_HTML)).into_response()
+}
+
+/// Whether an `If-None-Match` header lists `etag`, ignoring weak validator prefixes.
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// Liveness probe for container healthchecks and load balancers. Touches nothing
+/// outside the process, so a slow publisher can't make the server look down.
+pub async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// The main handler that drives the BibTeX extraction logic.
+pub async fn get_bibtex_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BibtexQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let started = Instant::now();
+    let result = fetch_and_generate_entry(&state.client, &query.url, query.mode)
+        .await
+        .and_then(|entry| require_real_title(entry, query.strict));
+    state.stats.lock().unwrap().record(
+        &query.url,
+        result.as_ref().map(|entry| entry.strategy),
+        started.elapsed(),
+    );
+    let entry = result?;
+    let (mut bibtex_entry, strategy) = (entry.bibtex, entry.strategy);
+    if query.expand_journal_abbrevs {
+        bibtex_entry = expand_journal_abbreviations(&bibtex_entry);
+    }
+    bibtex_entry = filter_bibtex_fields(&bibtex_entry, &query.field_filter());
+    if !query.pretty {
+        bibtex_entry = compact_bibtex(&bibtex_entry);
+    }
+    state
+        .entries
+        .lock()
+        .unwrap()
+        .insert_extracted(&bibtex_entry, Some(strategy));
+
+    // Results are generated per request and may be private, so keep them out of caches.
+    Ok((
+        [(header::CACHE_CONTROL, "no-store")],
+        Html(render_result_page(&query.url, &bibtex_entry)),
+    ))
+}
+
+/// The result page for an entry generated from `url`. Both are user-controlled, so
+/// everything is escaped, and only `http` and `https` URLs are rendered as links.
+pub fn render_result_page(url: &str, bibtex: &str) -> String {
+    let source = match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => format!(
+            r#"<a href="{}">{}</a>"#,
+            html_escape::encode_safe(url),
+            html_escape::encode_text(url)
+        ),
+        _ => html_escape::encode_text(url).into_owned(),
+    };
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <title>BibTeX Result</title>
+                <style>
+                    body {{ font-family: sans-serif; max-width: 800px; margin: auto; padding: 2em; background: #f4f4f4; }}
+                    pre {{ background: #e3e3e3; padding: 1em; white-space: pre-wrap; word-wrap: break-word; border: 1px solid #ccc; position: relative; }}
+                    a {{ color: #007bff; }}
+                    .copy-button {{
+                        position: absolute;
+                        top: 10px;
+                        right: 10px;
+                        padding: 8px 16px;
+                        background: #007bff;
+                        color: white;
+                        border: none;
+                        border-radius: 4px;
+                        cursor: pointer;
+                        font-size: 14px;
+                    }}
+                    .copy-button:hover {{
+                        background: #0056b3;
+                    }}
+                    .copy-button.copied {{
+                        background: #28a745;
+                    }}
+                </style>
+            </head>
+            <body>
+                <h1>BibTeX Result</h1>
+                <p>Source URL: {source}</p>
+                <div style="position: relative;">
+                    <pre><code id="bibtex-content">{entry}</code></pre>
+                    <button class="copy-button" onclick="copyBibTeX()">Copy BibTeX</button>
+                </div>
+                <a href="/">Try another URL</a>
+
+                <script>
+                    function copyBibTeX() {{
+                        const content = document.getElementById('bibtex-content').textContent;
+                        navigator.clipboard.writeText(content).then(() => {{
+                            const button = document.querySelector('.copy-button');
+                            const originalText = button.textContent;
+                            button.textContent = 'Copied!';
+                            button.classList.add('copied');
+                            setTimeout(() => {{
+                                button.textContent = originalText;
+                                button.classList.remove('copied');
+                            }}, 2000);
+                        }});
+                    }}
+                </script>
+            </body>
+        </html>
+        "#,
+        entry = html_escape::encode_text(bibtex)
+    )
+}
+
+/// An HTML page laying out what was extracted from a URL, field by field, above the
+/// BibTeX made from it. Meant for checking why an entry came out the way it did.
+pub async fn preview_html_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BibtexQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let started = Instant::now();
+    let result = fetch_metadata(&state.client, &query.url).await;
+    state.stats.lock().unwrap().record(
+        &query.url,
+        result.as_ref().map(|metadata| metadata.strategy),
+        started.elapsed(),
+    );
+    let metadata = result?;
+    let bibtex = format_bibtex(metadata.clone(), Some(&query.url), query.mode).ok();
+
+    Ok((
+        [(header::CACHE_CONTROL, "no-store")],
+        Html(render_preview_page(
+            &query.url,
+            &metadata,
+            bibtex.as_deref(),
+        )),
+    ))
+}
+
+/// The preview page for metadata extracted from `url`. Rows are green for fields that
+/// were found, yellow for fields made up by a fallback, and red for required fields
+/// that are missing.
+pub fn render_preview_page(url: &str, metadata: &ArticleMetadata, bibtex: Option<&str>) -> String {
+    // Fields filled in by a last resort rather than read from the page.
+    let fallbacks: &[&str] = match &metadata.comment {
+        Some(comment) if comment.contains(URL_TITLE_WARNING) => &["title"],
+        _ => &[],
+    };
+    let fields = match serde_json::to_value(metadata) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => Default::default(),
+    };
+
+    let mut rows = vec![format!(
+        r#"<tr class="strategy"><th>strategy</th><td>{}</td></tr>"#,
+        metadata.strategy.as_str()
+    )];
+    for (name, value) in &fields {
+        if name == "strategy" {
+            continue;
+        }
+        let value = match value {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .map_or_else(|| item.to_string(), str::to_string)
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            other => other.to_string(),
+        };
+        let class = if value.is_empty() {
+            if !REQUIRED_FIELDS.contains(&name.as_str()) {
+                continue;
+            }
+            "missing"
+        } else if fallbacks.contains(&name.as_str()) {
+            "fallback"
+        } else {
+            "found"
+        };
+        rows.push(format!(
+            r#"<tr class="{}"><th>{}</th><td>{}</td></tr>"#,
+            class,
+            html_escape::encode_text(name),
+            html_escape::encode_text(&value)
+        ));
+    }
+
+    let warnings = metadata
+        .warnings()
+        .iter()
+        .map(|warning| format!("<li>{}</li>", html_escape::encode_text(warning)))
+        .collect::<String>();
+    let warnings = if warnings.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<ul class="warnings">{}</ul>"#, warnings)
+    };
+    let bibtex = match bibtex {
+        Some(bibtex) => format!(
+            "<pre><code>{}</code></pre>",
+            html_escape::encode_text(bibtex)
+        ),
+        None => "<p>No BibTeX entry could be built from these fields.</p>".to_string(),
+    };
+
+    format!(
+        r#"
+        <!doctype html>
+        <html>
+            <head>
+                <title>Extraction Preview</title>
+                <style>
+                    body {{ font-family: sans-serif; max-width: 900px; margin: auto; padding: 2em; background: #f4f4f4; }}
+                    table {{ border-collapse: collapse; width: 100%; margin-bottom: 2em; }}
+                    th, td {{ text-align: left; vertical-align: top; padding: 6px 10px; border: 1px solid #ccc; }}
+                    th {{ width: 12em; font-family: monospace; }}
+                    tr.found {{ background: #d4edda; }}
+                    tr.fallback {{ background: #fff3cd; }}
+                    tr.missing {{ background: #f8d7da; }}
+                    tr.strategy {{ background: #e3e3e3; }}
+                    .warnings {{ color: #856404; }}
+                    pre {{ background: #e3e3e3; padding: 1em; white-space: pre-wrap; word-wrap: break-word; border: 1px solid #ccc; }}
+                </style>
+            </head>
+            <body>
+                <h1>Extraction Preview</h1>
+                <p>Source URL: {source}</p>
+                {warnings}
+                <table>{rows}</table>
+                {bibtex}
+            </body>
+        </html>
+        "#,
+        source = html_escape::encode_text(url),
+        rows = rows.join("\n"),
+    )
+}
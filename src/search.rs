@@ -0,0 +1,426 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::dedupe::title_fingerprint;
+use crate::error::AppError;
+use crate::http::HttpClient;
+use crate::sites::fetch_json;
+
+// --- Paper Search ---
+
+// Reciprocal rank fusion constant: damps the difference between the top few ranks so
+// that a paper found by several sources outranks one found near the top of just one.
+const RANK_FUSION_K: f64 = 60.0;
+
+/// The most results `/api/search` returns, whatever `limit` asks for.
+pub const MAX_SEARCH_LIMIT: usize = 50;
+
+/// A search hit, with a link back into this server for its BibTeX.
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchResult {
+    pub title: String,
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+    pub doi: Option<String>,
+    pub venue: Option<String>,
+    pub bibtex_url: String,
+}
+
+// One source's hit before merging; `url` is its landing page, used when there is no DOI.
+#[derive(Debug)]
+struct Hit {
+    title: String,
+    authors: Vec<String>,
+    year: Option<u16>,
+    doi: Option<String>,
+    venue: Option<String>,
+    url: Option<String>,
+}
+
+// Structs for parsing the Crossref works API.
+#[derive(Deserialize, Debug)]
+struct CrossrefResponse {
+    message: CrossrefMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefMessage {
+    #[serde(default)]
+    items: Vec<CrossrefItem>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefItem {
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<CrossrefAuthor>,
+    issued: Option<CrossrefDate>,
+    #[serde(rename = "container-title", default)]
+    container_title: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefAuthor {
+    given: Option<String>,
+    family: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrossrefDate {
+    #[serde(rename = "date-parts", default)]
+    date_parts: Vec<Vec<Option<u16>>>,
+}
+
+// Structs for parsing the Semantic Scholar paper search API.
+#[derive(Deserialize, Debug)]
+struct S2SearchResponse {
+    #[serde(default)]
+    data: Vec<S2Paper>,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2Paper {
+    title: Option<String>,
+    year: Option<u16>,
+    #[serde(default)]
+    authors: Vec<S2Author>,
+    #[serde(rename = "externalIds")]
+    external_ids: Option<S2ExternalIds>,
+    venue: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2Author {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2ExternalIds {
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+}
+
+// Structs for parsing the OpenAlex works API.
+#[derive(Deserialize, Debug)]
+struct OpenAlexResponse {
+    #[serde(default)]
+    results: Vec<OpenAlexWork>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexWork {
+    id: Option<String>,
+    doi: Option<String>,
+    display_name: Option<String>,
+    publication_year: Option<u16>,
+    #[serde(default)]
+    authorships: Vec<OpenAlexAuthorship>,
+    primary_location: Option<OpenAlexLocation>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexAuthorship {
+    author: OpenAlexAuthor,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexAuthor {
+    display_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexLocation {
+    source: Option<OpenAlexSource>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAlexSource {
+    display_name: Option<String>,
+}
+
+/// Searches Crossref, Semantic Scholar and OpenAlex at once and merges their results:
+/// papers with the same DOI are combined, and papers found by several sources rank higher.
+/// A source that fails is skipped; the search only fails if every source does.
+pub async fn search_papers(
+    client: &impl HttpClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchResult>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::InvalidInput(
+            "The q query parameter must not be empty.".into(),
+        ));
+    }
+    let limit = limit.clamp(1, MAX_SEARCH_LIMIT);
+    let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+
+    let (crossref, semantic_scholar, openalex) = tokio::join!(
+        search_crossref(client, &encoded, limit),
+        search_semantic_scholar(client, &encoded, limit),
+        search_openalex(client, &encoded, limit),
+    );
+
+    let mut sources = Vec::new();
+    let mut last_error = None;
+    for (name, result) in [
+        ("Crossref", crossref),
+        ("Semantic Scholar", semantic_scholar),
+        ("OpenAlex", openalex),
+    ] {
+        match result {
+            Ok(hits) => sources.push(hits),
+            Err(err) => {
+                eprintln!("-> {} search failed: {:?}", name, err);
+                last_error = Some(err);
+            }
+        }
+    }
+    if sources.is_empty()
+        && let Some(err) = last_error
+    {
+        return Err(err);
+    }
+
+    let mut results = merge_hits(sources);
+    results.truncate(limit);
+    Ok(results)
+}
+
+/// Combines each source's ranked hits by reciprocal rank fusion, deduplicating by DOI.
+/// Fields missing from the best-ranked copy of a paper are filled in from the others.
+fn merge_hits(sources: Vec<Vec<Hit>>) -> Vec<SearchResult> {
+    let mut merged: Vec<(f64, Hit)> = Vec::new();
+    let mut by_doi: HashMap<String, usize> = HashMap::new();
+
+    for hits in sources {
+        for (rank, hit) in hits.into_iter().enumerate() {
+            let score = 1.0 / (RANK_FUSION_K + rank as f64 + 1.0);
+            let key = hit.doi.as_ref().map(|doi| doi.to_lowercase());
+            match key.as_ref().and_then(|key| by_doi.get(key)) {
+                Some(&index) => {
+                    let (total, existing) = &mut merged[index];
+                    *total += score;
+                    fill_missing(existing, hit);
+                }
+                None => {
+                    if let Some(key) = key {
+                        by_doi.insert(key, merged.len());
+                    }
+                    merged.push((score, hit));
+                }
+            }
+        }
+    }
+
+    // A stable sort keeps ties in the order the sources were queried.
+    merged.sort_by(|a, b| b.0.total_cmp(&a.0));
+    merged.into_iter().map(|(_, hit)| to_result(hit)).collect()
+}
+
+fn fill_missing(existing: &mut Hit, other: Hit) {
+    if existing.title.is_empty() {
+        existing.title = other.title;
+    }
+    if existing.authors.is_empty() {
+        existing.authors = other.authors;
+    }
+    existing.year = existing.year.or(other.year);
+    existing.venue = existing.venue.take().or(other.venue);
+    existing.url = existing.url.take().or(other.url);
+}
+
+fn to_result(hit: Hit) -> SearchResult {
+    let source_url = match (&hit.doi, &hit.url) {
+        (Some(doi), _) => format!("https://doi.org/{}", doi),
+        (None, Some(url)) => url.clone(),
+        (None, None) => String::new(),
+    };
+    let encoded: String = url::form_urlencoded::byte_serialize(source_url.as_bytes()).collect();
+    SearchResult {
+        title: hit.title,
+        authors: hit.authors,
+        year: hit.year,
+        doi: hit.doi,
+        venue: hit.venue,
+        bibtex_url: format!("/api/bibtex?url={}", encoded),
+    }
+}
+
+async fn search_crossref(
+    client: &impl HttpClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Hit>, AppError> {
+    let api_url = format!(
+        "https://api.crossref.org/works?query={}&rows={}&select=DOI,title,author,issued,container-title",
+        query, limit
+    );
+    let response: CrossrefResponse = fetch_json(client, &api_url).await?;
+    Ok(crossref_hits(response))
+}
+
+fn crossref_hits(response: CrossrefResponse) -> Vec<Hit> {
+    response
+        .message
+        .items
+        .into_iter()
+        .map(|item| Hit {
+            title: item.title.into_iter().next().unwrap_or_default(),
+            authors: item
+                .author
+                .into_iter()
+                .filter_map(|author| match (author.given, author.family, author.name) {
+                    (Some(given), Some(family), _) => Some(format!("{} {}", given, family)),
+                    (None, Some(family), _) => Some(family),
+                    (_, None, name) => name,
+                })
+                .collect(),
+            year: item
+                .issued
+                .and_then(|issued| issued.date_parts.into_iter().next())
+                .and_then(|parts| parts.into_iter().next().flatten()),
+            doi: item.doi,
+            venue: item.container_title.into_iter().next(),
+            url: None,
+        })
+        .collect()
+}
+
+// How many of Crossref's results a title lookup compares.
+const TITLE_MATCH_CANDIDATES: usize = 5;
+
+/// Looks a paper up in Crossref by its title, optionally narrowed by an author's name and
+/// the publication year, and returns the closest of the top few results along with how
+/// closely its title matches, from 0 to 1. Only results with a DOI are considered.
+pub async fn match_title(
+    client: &impl HttpClient,
+    title: &str,
+    author: Option<&str>,
+    year: Option<u16>,
+) -> Result<(SearchResult, f64), AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::InvalidInput(
+            "The title query parameter must not be empty.".into(),
+        ));
+    }
+    let encode = |text: &str| -> String {
+        url::form_urlencoded::byte_serialize(text.trim().as_bytes()).collect()
+    };
+    let mut api_url = format!(
+        "https://api.crossref.org/works?query.bibliographic={}&rows={}&select=DOI,title,author,issued,container-title",
+        encode(title),
+        TITLE_MATCH_CANDIDATES
+    );
+    if let Some(author) = author.filter(|author| !author.trim().is_empty()) {
+        api_url.push_str(&format!("&query.author={}", encode(author)));
+    }
+    if let Some(year) = year {
+        api_url.push_str(&format!(
+            "&filter=from-pub-date:{},until-pub-date:{}",
+            year, year
+        ));
+    }
+    let response: CrossrefResponse = fetch_json(client, &api_url).await?;
+
+    // `max_by` keeps the last of equals; reversing prefers Crossref's own ranking on ties.
+    crossref_hits(response)
+        .into_iter()
+        .filter(|hit| hit.doi.is_some())
+        .map(|hit| (title_similarity(title, &hit.title), hit))
+        .rev()
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(score, hit)| (to_result(hit), score))
+        .ok_or_else(|| {
+            AppError::ExtractionError(format!("Crossref found no paper titled '{}'.", title))
+        })
+}
+
+/// How alike two titles are, from 0 to 1: one minus their Levenshtein distance over the
+/// longer one's length, comparing them without case, punctuation or extra spaces.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = title_fingerprint(a).chars().collect();
+    let b: Vec<char> = title_fingerprint(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    // One row of the edit distance table at a time.
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+async fn search_semantic_scholar(
+    client: &impl HttpClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Hit>, AppError> {
+    let api_url = format!(
+        "https://api.semanticscholar.org/graph/v1/paper/search?query={}&limit={}&fields=title,authors,year,externalIds,venue,url",
+        query, limit
+    );
+    let response: S2SearchResponse = fetch_json(client, &api_url).await?;
+
+    Ok(response
+        .data
+        .into_iter()
+        .map(|paper| Hit {
+            title: paper.title.unwrap_or_default(),
+            authors: paper.authors.into_iter().map(|a| a.name).collect(),
+            year: paper.year,
+            doi: paper.external_ids.and_then(|ids| ids.doi),
+            venue: paper.venue.filter(|venue| !venue.is_empty()),
+            url: paper.url,
+        })
+        .collect())
+}
+
+async fn search_openalex(
+    client: &impl HttpClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Hit>, AppError> {
+    let api_url = format!(
+        "https://api.openalex.org/works?search={}&per-page={}",
+        query, limit
+    );
+    let response: OpenAlexResponse = fetch_json(client, &api_url).await?;
+
+    Ok(response
+        .results
+        .into_iter()
+        .map(|work| Hit {
+            title: work.display_name.unwrap_or_default(),
+            authors: work
+                .authorships
+                .into_iter()
+                .map(|authorship| authorship.author.display_name)
+                .collect(),
+            year: work.publication_year,
+            // OpenAlex gives DOIs as URLs.
+            doi: work
+                .doi
+                .map(|doi| doi.trim_start_matches("https://doi.org/").to_string()),
+            venue: work
+                .primary_location
+                .and_then(|location| location.source)
+                .and_then(|source| source.display_name),
+            url: work.id,
+        })
+        .collect())
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::http::{HttpClient, encode_path_segment};
+
+// --- Related Paper Discovery ---
+
+// Structs for parsing the Semantic Scholar recommendations API.
+#[derive(Deserialize, Debug)]
+struct RecommendationsResponse {
+    #[serde(rename = "recommendedPapers", default)]
+    recommended_papers: Vec<RecommendedPaper>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RecommendedPaper {
+    #[serde(rename = "paperId")]
+    paper_id: String,
+    title: Option<String>,
+    year: Option<u16>,
+    #[serde(default)]
+    authors: Vec<RecommendedAuthor>,
+    #[serde(rename = "externalIds")]
+    external_ids: Option<ExternalIds>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RecommendedAuthor {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ExternalIds {
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+}
+
+/// A related paper, with a link back into this server for its BibTeX.
+#[derive(Serialize, Debug)]
+pub struct SimilarPaper {
+    pub title: String,
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+    pub doi: Option<String>,
+    pub bibtex_url: String,
+}
+
+/// Asks Semantic Scholar for papers related to `paper_id`,
+/// which may be a bare S2 id or a prefixed id such as `DOI:10.1145/3158154` or `URL:https://...`.
+pub async fn find_similar(
+    client: &impl HttpClient,
+    paper_id: &str,
+) -> Result<Vec<SimilarPaper>, AppError> {
+    let api_url = format!(
+        "https://api.semanticscholar.org/recommendations/v1/papers/forpaper/{}?fields=title,authors,year,externalIds&limit=5",
+        encode_path_segment(paper_id)
+    );
+    let res = client.get(&api_url, Some("application/json")).await?;
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "Semantic Scholar returned status {} for {}",
+            res.status, paper_id
+        )));
+    }
+
+    let response: RecommendationsResponse = serde_json::from_str(&res.body).map_err(|err| {
+        AppError::ExtractionError(format!("Unexpected Semantic Scholar response: {}", err))
+    })?;
+
+    Ok(response
+        .recommended_papers
+        .into_iter()
+        .map(|paper| {
+            let doi = paper.external_ids.and_then(|ids| ids.doi);
+            let source_url = match &doi {
+                Some(doi) => format!("https://doi.org/{}", doi),
+                None => format!("https://www.semanticscholar.org/paper/{}", paper.paper_id),
+            };
+            let encoded: String =
+                url::form_urlencoded::byte_serialize(source_url.as_bytes()).collect();
+            SimilarPaper {
+                title: paper.title.unwrap_or_default(),
+                authors: paper.authors.into_iter().map(|a| a.name).collect(),
+                year: paper.year,
+                doi,
+                bibtex_url: format!("/api/bibtex?url={}", encoded),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,153 @@
+use scraper::Html as ScraperHtml;
+use url::Url;
+
+use crate::error::AppError;
+use crate::extract::ArticleMetadata;
+use crate::http::{HttpClient, HttpResponse};
+
+pub mod acm;
+pub mod ads;
+pub mod dryad;
+pub mod europe_pmc;
+pub mod figshare;
+pub mod google_books;
+pub mod google_scholar;
+pub mod known;
+pub mod mendeley_data;
+pub mod nber;
+pub mod oai_pmh;
+pub mod oup;
+pub mod overleaf;
+pub mod overrides;
+pub mod semantic_scholar;
+pub mod springer;
+pub mod ssrn;
+pub mod taylor_francis;
+
+// --- Site-Specific Extractors ---
+
+/// Runs the dedicated extractor for the URL's host, if there is one.
+/// Returns `None` when the site should go through the generic scraping strategies.
+pub async fn extract_site_specific(
+    client: &impl HttpClient,
+    url: &Url,
+) -> Option<Result<ArticleMetadata, AppError>> {
+    let host = url.host_str()?;
+
+    if host == "figshare.com" || host.ends_with(".figshare.com") {
+        let id = figshare::article_id(url)?;
+        eprintln!("-> Detected Figshare article {}.", id);
+        return Some(figshare::extract_figshare(client, &id).await);
+    }
+
+    if host == "datadryad.org" || host.ends_with(".datadryad.org") {
+        let doi = dryad::dataset_doi(url)?;
+        eprintln!("-> Detected Dryad dataset {}.", doi);
+        return Some(dryad::extract_dryad(client, &doi).await);
+    }
+
+    if let Some((id, version)) = mendeley_data::dataset_id(url) {
+        eprintln!("-> Detected Mendeley Data dataset {}.", id);
+        return Some(mendeley_data::extract_mendeley_data(client, &id, version.as_deref()).await);
+    }
+
+    if host == "www.wikidata.org" && url.path().starts_with("/wiki/Q") {
+        let id = crate::wikidata::parse_entity_id(url.path())?;
+        eprintln!("-> Detected Wikidata item {}.", id);
+        return Some(crate::wikidata::lookup_wikidata(client, &id).await);
+    }
+
+    if let Some(bibcode) = ads::bibcode(url)
+        && let Some(token) = ads::ads_api_token()
+    {
+        eprintln!("-> Detected NASA ADS record {}.", bibcode);
+        return Some(ads::extract_ads(client, &bibcode, &token).await);
+    }
+
+    if let Some((source, id)) = europe_pmc::article_id(url) {
+        eprintln!("-> Detected Europe PMC article {}/{}.", source, id);
+        return Some(europe_pmc::extract_europe_pmc(client, &source, &id).await);
+    }
+
+    if let Some(number) = nber::paper_number(url) {
+        eprintln!("-> Detected NBER working paper {}.", number);
+        return Some(nber::extract_nber(client, &number).await);
+    }
+
+    if let Some(id) = google_books::book_id(url) {
+        eprintln!("-> Detected Google Books volume {}.", id);
+        return Some(google_books::extract_google_books(client, &id).await);
+    }
+
+    if host == "semanticscholar.org" || host == "www.semanticscholar.org" {
+        let id = semantic_scholar::paper_id(url)?;
+        eprintln!("-> Detected Semantic Scholar paper {}.", id);
+        return Some(semantic_scholar::extract_semantic_scholar(client, &id).await);
+    }
+
+    // Institutional repositories (DSpace, EPrints, Digital Commons) speak OAI-PMH.
+    if let Some(metadata) = oai_pmh::extract_oai_pmh(client, url).await {
+        return Some(Ok(metadata));
+    }
+
+    None
+}
+
+/// Runs the dedicated scraper for hosts whose page markup needs special handling.
+/// Returns `None` when the generic extractors should read the page instead.
+pub fn extract_from_page(document: &ScraperHtml, url: &Url) -> Option<ArticleMetadata> {
+    let host = url.host_str()?;
+
+    if host == "tandfonline.com" || host.ends_with(".tandfonline.com") {
+        eprintln!("-> Detected Taylor & Francis Online article.");
+        return taylor_francis::extract_taylor_francis(document, url);
+    }
+
+    if (host == "overleaf.com" || host == "www.overleaf.com") && url.path().starts_with("/read/") {
+        eprintln!("-> Detected Overleaf shared project.");
+        return overleaf::extract_overleaf(document);
+    }
+
+    if host == "ssrn.com" || host.ends_with(".ssrn.com") {
+        eprintln!("-> Detected SSRN paper.");
+        return ssrn::extract_ssrn(document, url);
+    }
+
+    if host == "dl.acm.org" && acm::is_proceedings_page(url) {
+        eprintln!("-> Detected ACM Digital Library proceedings.");
+        return acm::extract_acm_proceedings(document, url);
+    }
+
+    if host == "academic.oup.com" {
+        eprintln!("-> Detected Oxford Academic article.");
+        return oup::extract_oup(document, url);
+    }
+
+    None
+}
+
+/// Fetches and decodes a JSON document from a site's API.
+pub(crate) async fn fetch_json<T: serde::de::DeserializeOwned>(
+    client: &impl HttpClient,
+    api_url: &str,
+) -> Result<T, AppError> {
+    let res = client.get(api_url, Some("application/json")).await?;
+    decode_json(api_url, res)
+}
+
+/// Decodes an API's JSON response, treating an error status as a failed lookup.
+pub(crate) fn decode_json<T: serde::de::DeserializeOwned>(
+    api_url: &str,
+    res: HttpResponse,
+) -> Result<T, AppError> {
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "API at {} returned status {}",
+            api_url, res.status
+        )));
+    }
+
+    serde_json::from_str(&res.body).map_err(|err| {
+        AppError::ExtractionError(format!("Unexpected response from {}: {}", api_url, err))
+    })
+}
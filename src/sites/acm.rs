@@ -0,0 +1,122 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::{Html as ScraperHtml, Selector};
+use serde_json::Value;
+use url::Url;
+
+use crate::authors::last_first;
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::extract::{
+    ArticleMetadata, ExtractionStrategy, ld_text, schema_names, select_all_text, select_text,
+};
+
+lazy_static! {
+    // "ISBN: 978-1-4503-8391-2" in the proceedings' publication details.
+    static ref ISBN_RE: Regex = Regex::new(r"ISBN:?\s*((?:97[89][-\s]?)?(?:\d[-\s]?){9}[\dX])").unwrap();
+}
+
+/// Whether a `dl.acm.org` URL is a proceedings volume, `/doi/proceedings/{doi}`, or a
+/// conference series page, `/conference/{acronym}`, rather than a single paper.
+pub fn is_proceedings_page(url: &Url) -> bool {
+    url.path().starts_with("/doi/proceedings/") || url.path().starts_with("/conference/")
+}
+
+/// Scrapes an ACM Digital Library proceedings page as a `@proceedings` entry. The
+/// volume's editors and series come from its JSON-LD when present; otherwise the
+/// series is the conference named in the breadcrumb trail.
+pub fn extract_acm_proceedings(document: &ScraperHtml, url: &Url) -> Option<ArticleMetadata> {
+    let book = proceedings_json_ld(document);
+    let book_field = |key: &str| book.as_ref().and_then(|book| book.get(key));
+
+    let title = book_field("name")
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='dc.Title']", "content"))
+        .or_else(|| select_text(document, "meta[property='og:title']", "content"))
+        .filter(|title| !title.is_empty())?;
+
+    let year = book_field("datePublished")
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='dc.Date']", "content"))
+        .and_then(|date| parse_year(&date))
+        .or_else(|| parse_year(&title))
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+    // Editors take the place of authors on a volume, so write them unambiguously.
+    let editor = schema_names(book_field("editor"))
+        .or_else(|| {
+            let editors = select_all_text(document, "meta[name='dc.Contributor']", "content");
+            (!editors.is_empty()).then(|| editors.join(" and "))
+        })
+        .map(|editors| {
+            editors
+                .split(" and ")
+                .map(last_first)
+                .collect::<Vec<_>>()
+                .join(" and ")
+        });
+
+    let series = book_field("isPartOf")
+        .and_then(|series| series.get("name").and_then(ld_text))
+        .or_else(|| breadcrumb_series(document));
+
+    let isbn = book_field("isbn")
+        .and_then(ld_text)
+        .or_else(|| select_text(document, "meta[name='citation_isbn']", "content"))
+        .or_else(|| {
+            let text: String = document.root_element().text().collect();
+            ISBN_RE.captures(&text).map(|caps| caps[1].to_string())
+        })
+        .map(|isbn| isbn.replace([' ', '-'], ""));
+
+    Some(ArticleMetadata {
+        strategy: if book.is_some() {
+            ExtractionStrategy::JsonLd
+        } else {
+            ExtractionStrategy::MetaTags
+        },
+        entry_type: BibtexEntryType::Proceedings,
+        title,
+        year,
+        editor,
+        publisher: "ACM".to_string(),
+        series,
+        volume: book_field("volumeNumber").and_then(ld_text),
+        isbn,
+        doi: url
+            .path()
+            .strip_prefix("/doi/proceedings/")
+            .filter(|doi| doi.starts_with("10."))
+            .map(str::to_string),
+        ..Default::default()
+    })
+}
+
+/// The JSON-LD node describing the proceedings volume.
+fn proceedings_json_ld(document: &ScraperHtml) -> Option<Value> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    document
+        .select(&selector)
+        .filter_map(|element| serde_json::from_str::<Value>(&element.inner_html()).ok())
+        .flat_map(|json| match json {
+            Value::Array(nodes) => nodes,
+            node => vec![node],
+        })
+        .find(|node| {
+            matches!(
+                node.get("@type").and_then(Value::as_str),
+                Some("Book" | "PublicationVolume")
+            )
+        })
+}
+
+/// The conference linked from the breadcrumb trail, e.g. "PLDI" in
+/// Home / Conferences / PLDI / Proceedings / PLDI 2021.
+fn breadcrumb_series(document: &ScraperHtml) -> Option<String> {
+    let selector = Selector::parse(".article__breadcrumbs a[href^='/conference/']").unwrap();
+    document
+        .select(&selector)
+        .map(|link| link.text().collect::<String>().trim().to_string())
+        .find(|name| !name.is_empty())
+}
@@ -0,0 +1,173 @@
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// The fields requested from the search API; `doctype` tells papers from proceedings.
+const SEARCH_FIELDS: &str =
+    "title,author,year,pub,volume,issue,page,doi,identifier,abstract,doctype";
+
+// Response of `/v1/export/bibtex/{bibcode}`.
+#[derive(Deserialize, Debug)]
+struct AdsExport {
+    export: String,
+}
+
+// Structs for parsing the ADS search API.
+#[derive(Deserialize, Debug)]
+struct AdsSearch {
+    response: AdsResults,
+}
+
+#[derive(Deserialize, Debug)]
+struct AdsResults {
+    #[serde(default)]
+    docs: Vec<AdsDoc>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AdsDoc {
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<String>,
+    year: Option<String>,
+    #[serde(rename = "pub")]
+    publication: Option<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    #[serde(default)]
+    page: Vec<String>,
+    #[serde(default)]
+    doi: Vec<String>,
+    #[serde(default)]
+    identifier: Vec<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    doctype: Option<String>,
+}
+
+/// The token for the NASA ADS API, from `ADS_API_TOKEN`.
+pub fn ads_api_token() -> Option<String> {
+    std::env::var("ADS_API_TOKEN")
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// Finds the bibcode in `ui.adsabs.harvard.edu/abs/{bibcode}/abstract` URLs, e.g.
+/// `2016PhRvL.116f1102A`. Bibcodes containing `&` arrive percent-encoded.
+pub fn bibcode(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if host != "ui.adsabs.harvard.edu" && host != "adsabs.harvard.edu" {
+        return None;
+    }
+    let mut segments = url.path_segments()?;
+    if segments.next()? != "abs" {
+        return None;
+    }
+    let bibcode = percent_encoding::percent_decode_str(segments.next()?)
+        .decode_utf8()
+        .ok()?;
+    (bibcode.len() == 19).then(|| bibcode.into_owned())
+}
+
+/// ADS's own BibTeX for a bibcode, from `https://api.adsabs.harvard.edu/v1/export/bibtex/{bibcode}`.
+pub async fn export_bibtex(
+    client: &impl HttpClient,
+    bibcode: &str,
+    token: &str,
+) -> Result<String, AppError> {
+    let api_url = format!(
+        "https://api.adsabs.harvard.edu/v1/export/bibtex/{}",
+        encode(bibcode)
+    );
+    let export: AdsExport = fetch_json(client, &api_url, token).await?;
+    let bibtex = export.export.trim();
+    if !bibtex.starts_with('@') {
+        return Err(AppError::ExtractionError(format!(
+            "ADS has no BibTeX for {}",
+            bibcode
+        )));
+    }
+    Ok(bibtex.to_string())
+}
+
+/// Builds metadata for a bibcode from the ADS search API. Papers in conference
+/// proceedings become `@inproceedings`, everything else `@article`.
+pub async fn extract_ads(
+    client: &impl HttpClient,
+    bibcode: &str,
+    token: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://api.adsabs.harvard.edu/v1/search/query?q=bibcode:{}&fl={}",
+        encode(bibcode),
+        SEARCH_FIELDS
+    );
+    let search: AdsSearch = fetch_json(client, &api_url, token).await?;
+    let Some(doc) = search.response.docs.into_iter().next() else {
+        return Err(AppError::ExtractionError(format!(
+            "ADS has no record of {}",
+            bibcode
+        )));
+    };
+
+    // `identifier` mixes bibcodes, DOIs and arXiv IDs such as "arXiv:1602.03837".
+    let doi = doc.doi.into_iter().next().or_else(|| {
+        doc.identifier
+            .iter()
+            .find(|id| id.starts_with("10."))
+            .cloned()
+    });
+    let eprint = doc
+        .identifier
+        .iter()
+        .find_map(|id| id.strip_prefix("arXiv:"))
+        .map(str::to_string);
+
+    let entry_type = match doc.doctype.as_deref() {
+        Some("inproceedings" | "abstract") => BibtexEntryType::InProceedings,
+        _ => BibtexEntryType::Article,
+    };
+    let (journal, booktitle) = match entry_type {
+        BibtexEntryType::InProceedings => (None, doc.publication),
+        _ => (doc.publication, None),
+    };
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type,
+        title: doc.title.into_iter().next().unwrap_or_default(),
+        author: normalize_authors(doc.author).join(" and "),
+        year: doc.year.unwrap_or_default(),
+        journal,
+        booktitle,
+        volume: doc.volume,
+        number: doc.issue,
+        page_range: doc.page.into_iter().next(),
+        doi,
+        eprint,
+        abstract_text: doc.abstract_text,
+        ..Default::default()
+    })
+}
+
+fn encode(bibcode: &str) -> String {
+    url::form_urlencoded::byte_serialize(bibcode.as_bytes()).collect()
+}
+
+/// Like [`super::fetch_json`], with the API token in an `Authorization` header.
+async fn fetch_json<T: DeserializeOwned>(
+    client: &impl HttpClient,
+    api_url: &str,
+    token: &str,
+) -> Result<T, AppError> {
+    let res = client.get_with_token(api_url, token).await?;
+    super::decode_json(api_url, res)
+}
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Dryad v2 dataset API.
+#[derive(Deserialize, Debug)]
+struct DryadDataset {
+    title: String,
+    #[serde(default)]
+    authors: Vec<DryadAuthor>,
+    #[serde(rename = "publicationDate")]
+    publication_date: Option<String>,
+    identifier: Option<String>,
+    license: Option<String>,
+    #[serde(rename = "versionNumber")]
+    version_number: Option<u32>,
+    #[serde(default)]
+    keywords: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DryadAuthor {
+    #[serde(rename = "firstName")]
+    first_name: Option<String>,
+    #[serde(rename = "lastName")]
+    last_name: Option<String>,
+}
+
+/// Pulls the `doi:10.5061/...` identifier out of `/stash/dataset/doi:10.5061/...` URLs.
+pub fn dataset_doi(url: &Url) -> Option<String> {
+    let doi = url.path().split_once("/dataset/")?.1;
+    doi.starts_with("doi:").then(|| doi.to_string())
+}
+
+/// Builds metadata for a Dryad dataset from `https://datadryad.org/api/v2/datasets/{doi}`.
+pub async fn extract_dryad(
+    client: &impl HttpClient,
+    doi: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let encoded: String = url::form_urlencoded::byte_serialize(doi.as_bytes()).collect();
+    let api_url = format!("https://datadryad.org/api/v2/datasets/{}", encoded);
+    let dataset: DryadDataset = super::fetch_json(client, &api_url).await?;
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        title: dataset.title,
+        author: dataset
+            .authors
+            .into_iter()
+            .map(|a| {
+                [a.first_name, a.last_name]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" and "),
+        year: dataset
+            .publication_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: "Dryad".to_string(),
+        doi: dataset
+            .identifier
+            .map(|id| id.trim_start_matches("doi:").to_string()),
+        license: dataset.license,
+        entry_type: BibtexEntryType::Dataset,
+        resource_type: Some("Dataset".to_string()),
+        version: dataset.version_number.map(|version| version.to_string()),
+        keywords: dataset.keywords,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,168 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Europe PMC REST search API (`resultType=core`).
+#[derive(Deserialize, Debug)]
+struct EpmcSearch {
+    #[serde(rename = "resultList")]
+    result_list: EpmcResults,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcResults {
+    #[serde(default)]
+    result: Vec<EpmcArticle>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcArticle {
+    title: String,
+    pmid: Option<String>,
+    pmcid: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "authorList")]
+    author_list: Option<EpmcAuthorList>,
+    #[serde(rename = "journalInfo")]
+    journal_info: Option<EpmcJournalInfo>,
+    #[serde(rename = "pageInfo")]
+    page_info: Option<String>,
+    #[serde(rename = "pubYear")]
+    pub_year: Option<String>,
+    #[serde(rename = "abstractText")]
+    abstract_text: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcAuthorList {
+    #[serde(default)]
+    author: Vec<EpmcAuthor>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcAuthor {
+    #[serde(rename = "fullName")]
+    full_name: Option<String>,
+    #[serde(rename = "firstName")]
+    first_name: Option<String>,
+    #[serde(rename = "lastName")]
+    last_name: Option<String>,
+    #[serde(rename = "authorId")]
+    author_id: Option<EpmcAuthorId>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcAuthorId {
+    #[serde(rename = "type")]
+    kind: String,
+    value: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcJournalInfo {
+    volume: Option<String>,
+    issue: Option<String>,
+    journal: Option<EpmcJournal>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpmcJournal {
+    title: Option<String>,
+}
+
+/// Finds the source and ID in `europepmc.org/article/{source}/{id}` and
+/// `europepmc.org/abstract/{source}/{id}` URLs, e.g. `("MED", "31452104")`, and in
+/// `europepmc.org/articles/{pmcid}` URLs, whose source is always `PMC`.
+pub fn article_id(url: &Url) -> Option<(String, String)> {
+    let host = url.host_str()?;
+    if host != "europepmc.org" && host != "www.europepmc.org" {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    let (source, id) = match segments.next()? {
+        "article" | "abstract" => (segments.next()?.to_uppercase(), segments.next()?),
+        "articles" => ("PMC".to_string(), segments.next()?),
+        _ => return None,
+    };
+    let id = id.to_uppercase();
+    let digits = id.strip_prefix("PMC").unwrap_or(&id);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((source, id))
+}
+
+/// Builds an `@article` entry from
+/// `https://www.ebi.ac.uk/europepmc/webservices/rest/search?query=ext_id:{id}`,
+/// keeping the PubMed and PubMed Central IDs alongside the DOI.
+pub async fn extract_europe_pmc(
+    client: &impl HttpClient,
+    source: &str,
+    id: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://www.ebi.ac.uk/europepmc/webservices/rest/search?query=ext_id:{}%20AND%20src:{}&format=json&resultType=core",
+        id, source
+    );
+    let search: EpmcSearch = super::fetch_json(client, &api_url).await?;
+    let Some(article) = search.result_list.result.into_iter().next() else {
+        return Err(AppError::ExtractionError(format!(
+            "Europe PMC has no record of {}/{}",
+            source, id
+        )));
+    };
+
+    let authors = article
+        .author_list
+        .map(|list| list.author)
+        .unwrap_or_default();
+    let orcids = authors
+        .iter()
+        .filter_map(|author| author.author_id.as_ref())
+        .filter(|author_id| author_id.kind.eq_ignore_ascii_case("ORCID"))
+        .map(|author_id| author_id.value.clone())
+        .collect::<Vec<_>>();
+    let names = authors
+        .into_iter()
+        .filter_map(|author| match (author.last_name, author.first_name) {
+            (Some(last), Some(first)) => Some(format!("{}, {}", last, first)),
+            (last, _) => author.full_name.or(last),
+        })
+        .collect();
+    let (volume, number, journal) = match article.journal_info {
+        Some(info) => (
+            info.volume,
+            info.issue,
+            info.journal.and_then(|journal| journal.title),
+        ),
+        None => (None, None, None),
+    };
+    let page_range = article
+        .page_info
+        .filter(|pages| !pages.is_empty())
+        .map(|pages| pages.replacen('-', "--", 1));
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::Article,
+        // Titles are sentences there, full stop included.
+        title: article.title.trim().trim_end_matches('.').to_string(),
+        author: normalize_authors(names).join(" and "),
+        year: article.pub_year.unwrap_or_default(),
+        journal,
+        volume,
+        number,
+        page_range,
+        doi: article.doi,
+        pmid: article.pmid,
+        pmcid: article.pmcid,
+        orcid: (!orcids.is_empty()).then_some(orcids),
+        abstract_text: article.abstract_text,
+        ..Default::default()
+    })
+}
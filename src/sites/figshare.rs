@@ -0,0 +1,87 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Figshare v2 article API.
+#[derive(Deserialize, Debug)]
+struct FigshareArticle {
+    title: String,
+    #[serde(default)]
+    authors: Vec<FigshareAuthor>,
+    published_date: Option<String>,
+    doi: Option<String>,
+    license: Option<FigshareLicense>,
+    defined_type_name: Option<String>,
+    version: Option<u32>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct FigshareAuthor {
+    full_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct FigshareLicense {
+    name: String,
+}
+
+/// Finds the numeric article ID in URLs like `/articles/dataset/Some_title/12345678/2`.
+pub fn article_id(url: &Url) -> Option<String> {
+    url.path_segments()?
+        .skip_while(|segment| *segment != "articles")
+        .find(|segment| segment.len() >= 4 && segment.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// Builds metadata for a Figshare item from `https://api.figshare.com/v2/articles/{id}`.
+pub async fn extract_figshare(
+    client: &impl HttpClient,
+    id: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!("https://api.figshare.com/v2/articles/{}", id);
+    let article: FigshareArticle = super::fetch_json(client, &api_url).await?;
+
+    let defined_type = article.defined_type_name.as_deref().unwrap_or("dataset");
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: if defined_type.eq_ignore_ascii_case("dataset") {
+            BibtexEntryType::Dataset
+        } else {
+            BibtexEntryType::Misc
+        },
+        title: article.title,
+        author: article
+            .authors
+            .into_iter()
+            .map(|a| a.full_name)
+            .collect::<Vec<_>>()
+            .join(" and "),
+        year: article
+            .published_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: "figshare".to_string(),
+        doi: article.doi.filter(|doi| !doi.is_empty()),
+        license: article.license.map(|license| license.name),
+        resource_type: Some(capitalize(defined_type)),
+        version: article.version.map(|version| version.to_string()),
+        keywords: article.tags,
+        ..Default::default()
+    })
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
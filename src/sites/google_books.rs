@@ -0,0 +1,112 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Google Books volumes API, shared with the ISBN lookup.
+#[derive(Deserialize, Debug)]
+pub(crate) struct GoogleBooksVolume {
+    #[serde(rename = "volumeInfo")]
+    pub volume_info: GoogleBooksInfo,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct GoogleBooksInfo {
+    title: String,
+    subtitle: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    publisher: Option<String>,
+    // Either a year, "1984", or a date, "1984-06-01".
+    #[serde(rename = "publishedDate")]
+    published_date: Option<String>,
+    description: Option<String>,
+    #[serde(rename = "industryIdentifiers", default)]
+    industry_identifiers: Vec<GoogleBooksIdentifier>,
+    #[serde(rename = "pageCount")]
+    page_count: Option<u32>,
+    #[serde(default)]
+    categories: Vec<String>,
+    language: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GoogleBooksIdentifier {
+    #[serde(rename = "type")]
+    kind: String,
+    identifier: String,
+}
+
+/// Finds the volume ID in `books.google.com/books?id={id}` URLs, on any of Google's
+/// country domains, and in `google.com/books/edition/{title}/{id}` URLs.
+pub fn book_id(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let id = if host.starts_with("books.google.") {
+        url.query_pairs()
+            .find(|(name, _)| name == "id")
+            .map(|(_, id)| id.into_owned())
+    } else if host.trim_start_matches("www.").starts_with("google.") {
+        let mut segments = url.path_segments()?;
+        match (segments.next(), segments.next()) {
+            (Some("books"), Some("edition")) => segments.nth(1).map(str::to_string),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    id.filter(|id| {
+        !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+/// Builds a `@book` entry from `https://www.googleapis.com/books/v1/volumes/{id}`, which
+/// answers for public volumes without an API key.
+pub async fn extract_google_books(
+    client: &impl HttpClient,
+    id: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!("https://www.googleapis.com/books/v1/volumes/{}", id);
+    let volume: GoogleBooksVolume = super::fetch_json(client, &api_url).await?;
+    Ok(volume_metadata(volume.volume_info))
+}
+
+/// The metadata of a Google Books volume. Its ISBN-13 is preferred over its ISBN-10.
+pub(crate) fn volume_metadata(info: GoogleBooksInfo) -> ArticleMetadata {
+    let isbn = ["ISBN_13", "ISBN_10"].iter().find_map(|kind| {
+        info.industry_identifiers
+            .iter()
+            .find(|identifier| identifier.kind == *kind)
+            .map(|identifier| identifier.identifier.clone())
+    });
+    let title = match info.subtitle.filter(|subtitle| !subtitle.is_empty()) {
+        Some(subtitle) => format!("{}: {}", info.title, subtitle),
+        None => info.title,
+    };
+
+    ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::Book,
+        title,
+        author: normalize_authors(info.authors).join(" and "),
+        year: info
+            .published_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: info.publisher.unwrap_or_default(),
+        abstract_text: info.description,
+        isbn,
+        pages: info.page_count,
+        keywords: info.categories,
+        language: info.language,
+        ..Default::default()
+    }
+}
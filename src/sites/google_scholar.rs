@@ -0,0 +1,198 @@
+use scraper::{ElementRef, Html as ScraperHtml, Selector};
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::{BibtexEntryType, OutputMode};
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy, format_bibtex};
+use crate::http::HttpClient;
+
+// --- Google Scholar Profiles ---
+//
+// Scholar has no API, and its terms of service forbid automated access. Profiles are
+// only read when the operator opts in with `BIBTEXTER_ENABLE_SCHOLAR=true`, and then
+// with one request per profile.
+
+/// How many publications a profile lookup returns unless asked for fewer or more.
+pub const DEFAULT_SCHOLAR_LIMIT: usize = 20;
+/// The most Scholar will list on one profile page.
+pub const MAX_SCHOLAR_LIMIT: usize = 100;
+
+/// One row of a profile's publication list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScholarPublication {
+    pub title: String,
+    /// Scholar shortens long author lists, ending them with "...".
+    pub authors: Vec<String>,
+    /// The journal or conference line, e.g. `Nature 521 (7553), 436-444`.
+    pub venue: Option<String>,
+    pub year: Option<String>,
+    pub cited_by: Option<u32>,
+    /// The publication's own page on Scholar.
+    pub url: Option<String>,
+}
+
+/// Whether the operator has opted in to reading Google Scholar profiles.
+pub fn scholar_enabled() -> bool {
+    std::env::var("BIBTEXTER_ENABLE_SCHOLAR").is_ok_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+/// Finds the user ID in `scholar.google.{tld}/citations?user={id}` profile URLs.
+pub fn profile_user(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if !host.starts_with("scholar.google.") || url.path() != "/citations" {
+        return None;
+    }
+    url.query_pairs()
+        .find(|(name, _)| name == "user")
+        .map(|(_, user)| user.trim().to_string())
+        .filter(|user| {
+            !user.is_empty()
+                && user
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+/// Reads the publication rows (`tr.gsc_a_tr`) of a profile page, at most `limit`.
+pub fn parse_profile(document: &ScraperHtml, limit: usize) -> Vec<ScholarPublication> {
+    let row_selector = Selector::parse("tr.gsc_a_tr").unwrap();
+    let title_selector = Selector::parse("a.gsc_a_at").unwrap();
+    let gray_selector = Selector::parse("div.gs_gray").unwrap();
+    let cited_selector = Selector::parse("td.gsc_a_c a").unwrap();
+    let year_selector = Selector::parse("td.gsc_a_y span").unwrap();
+
+    document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let title_link = row.select(&title_selector).next()?;
+            let title = element_text(title_link)?;
+            // The first gray line lists the authors, the second the venue and year.
+            let mut gray = row.select(&gray_selector).filter_map(element_text);
+            let authors = gray
+                .next()
+                .map(|authors| {
+                    authors
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty() && *name != "...")
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let year = row
+                .select(&year_selector)
+                .next()
+                .and_then(element_text)
+                .filter(|year| year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()));
+            let venue = gray
+                .next()
+                .map(|venue| strip_trailing_year(&venue, year.as_deref()))
+                .filter(|venue| !venue.is_empty());
+            Some(ScholarPublication {
+                title,
+                authors,
+                venue,
+                year,
+                cited_by: row
+                    .select(&cited_selector)
+                    .next()
+                    .and_then(element_text)
+                    .and_then(|count| count.parse().ok()),
+                url: title_link
+                    .value()
+                    .attr("href")
+                    .map(|href| format!("https://scholar.google.com{}", href)),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Fetches a profile and returns an entry for each of its first `limit` publications,
+/// separated by blank lines. Each entry notes how often Scholar counts it as cited.
+pub async fn scholar_profile_bibtex(
+    client: &impl HttpClient,
+    profile_url: &str,
+    limit: usize,
+    mode: OutputMode,
+) -> Result<String, AppError> {
+    if !scholar_enabled() {
+        return Err(AppError::InvalidInput(
+            "Google Scholar profiles are not read on this server.".into(),
+        ));
+    }
+    let url = Url::parse(profile_url).map_err(AppError::UrlParseError)?;
+    let user = profile_user(&url).ok_or_else(|| {
+        AppError::InvalidInput(format!(
+            "'{}' is not a Google Scholar profile URL.",
+            profile_url
+        ))
+    })?;
+    let limit = limit.clamp(1, MAX_SCHOLAR_LIMIT);
+    let page_url = format!(
+        "https://scholar.google.com/citations?user={}&hl=en&cstart=0&pagesize={}",
+        user, MAX_SCHOLAR_LIMIT
+    );
+    eprintln!("-> Reading Google Scholar profile {}.", user);
+    let res = client.get(&page_url, None).await?;
+    if !res.status.is_success() {
+        return Err(AppError::ExtractionError(format!(
+            "Google Scholar returned status {}",
+            res.status
+        )));
+    }
+
+    let publications = parse_profile(&ScraperHtml::parse_document(&res.body), limit);
+    if publications.is_empty() {
+        return Err(AppError::ExtractionError(
+            "No publications found on the Google Scholar profile.".into(),
+        ));
+    }
+    let entries = publications
+        .into_iter()
+        .map(|publication| {
+            let url = publication.url.clone();
+            format_bibtex(publication_metadata(publication), url.as_deref(), mode)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(entries.join("\n\n"))
+}
+
+fn publication_metadata(publication: ScholarPublication) -> ArticleMetadata {
+    ArticleMetadata {
+        strategy: ExtractionStrategy::MetaTags,
+        entry_type: if publication.venue.is_some() {
+            BibtexEntryType::Article
+        } else {
+            BibtexEntryType::Misc
+        },
+        title: publication.title,
+        author: normalize_authors(publication.authors).join(" and "),
+        year: publication.year.unwrap_or_default(),
+        journal: publication.venue,
+        note: publication
+            .cited_by
+            .map(|count| format!("Cited by {} on Google Scholar", count)),
+        ..Default::default()
+    }
+}
+
+fn element_text(element: ElementRef) -> Option<String> {
+    let text = element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+// Venue lines end with the year, which has a field of its own: "Nature 521, 436-444, 2015".
+fn strip_trailing_year(venue: &str, year: Option<&str>) -> String {
+    let venue = venue.trim();
+    year.and_then(|year| venue.strip_suffix(year))
+        .map(|rest| rest.trim_end().trim_end_matches(',').trim_end())
+        .unwrap_or(venue)
+        .to_string()
+}
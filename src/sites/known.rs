@@ -0,0 +1,198 @@
+use serde::Serialize;
+
+use crate::bibtex::BibtexEntryType;
+use crate::extract::ExtractionStrategy;
+
+// --- Known Sites ---
+
+/// How complete the entries for a site usually are.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    /// Every field a style needs, from a structured source.
+    Excellent,
+    /// The core fields, with occasional gaps.
+    Good,
+    /// Title and URL, but often no authors or date.
+    Fair,
+}
+
+/// What extraction produces for pages on a site the server has been tested against.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownSite {
+    pub domain: &'static str,
+    pub strategy: ExtractionStrategy,
+    pub quality: Quality,
+    pub entry_type: BibtexEntryType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limitations: Option<&'static str>,
+    /// A page whose recorded copy the test suite extracts, to keep this entry honest.
+    #[serde(skip)]
+    pub example_url: &'static str,
+}
+
+/// Sites with dedicated handling or a recorded example page, alphabetically. Each
+/// example page is extracted in `tests/known_sites.rs`, which fails when a site's
+/// strategy or entry type drifts from what is listed here.
+pub const KNOWN_SITES: &[KnownSite] = &[
+    KnownSite {
+        domain: "academic.oup.com",
+        strategy: ExtractionStrategy::JsonLd,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Article,
+        limitations: None,
+        example_url: "https://academic.oup.com/brain/article/147/4/1450/7600000",
+    },
+    KnownSite {
+        domain: "arxiv.org",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Unpublished,
+        limitations: Some("cited as a preprint even after journal publication"),
+        example_url: "https://arxiv.org/abs/1706.03762",
+    },
+    KnownSite {
+        domain: "data.mendeley.com",
+        strategy: ExtractionStrategy::SiteApi,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Dataset,
+        limitations: None,
+        example_url: "https://data.mendeley.com/datasets/8gx2mk6x7w/2",
+    },
+    KnownSite {
+        domain: "datadryad.org",
+        strategy: ExtractionStrategy::SiteApi,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Dataset,
+        limitations: None,
+        example_url: "https://datadryad.org/stash/dataset/doi:10.5061/dryad.2bvq83bn5",
+    },
+    KnownSite {
+        domain: "dl.acm.org",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Article,
+        limitations: Some("conference papers are cited as articles in the proceedings' name"),
+        example_url: "https://dl.acm.org/doi/10.1145/2692956.2663188",
+    },
+    KnownSite {
+        domain: "en.wikipedia.org",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Fair,
+        entry_type: BibtexEntryType::Misc,
+        limitations: Some("no authors or revision date"),
+        example_url: "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+    },
+    KnownSite {
+        domain: "f1000research.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Article,
+        limitations: Some("each peer-reviewed version has its own DOI"),
+        example_url: "https://f1000research.com/articles/9-245/v2",
+    },
+    KnownSite {
+        domain: "figshare.com",
+        strategy: ExtractionStrategy::SiteApi,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Dataset,
+        limitations: None,
+        example_url: "https://figshare.com/articles/dataset/Global_soil_moisture_observations/5616445/2",
+    },
+    KnownSite {
+        domain: "github.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Fair,
+        entry_type: BibtexEntryType::Misc,
+        limitations: Some("no authors or release date; CITATION.cff files are not read"),
+        example_url: "https://github.com/rust-lang/rust",
+    },
+    KnownSite {
+        domain: "ieeexplore.ieee.org",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::InProceedings,
+        limitations: Some("may miss page numbers"),
+        example_url: "https://ieeexplore.ieee.org/document/7780459",
+    },
+    KnownSite {
+        domain: "link.springer.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Article,
+        limitations: Some("may miss volume for some articles"),
+        example_url: "https://link.springer.com/article/10.1007/s10994-021-06000-0",
+    },
+    KnownSite {
+        domain: "nature.com",
+        strategy: ExtractionStrategy::JsonLd,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Misc,
+        limitations: Some("news and comment pieces are cited as web pages, not articles"),
+        example_url: "https://www.nature.com/articles/d41586-024-02391-9",
+    },
+    KnownSite {
+        domain: "nber.org",
+        strategy: ExtractionStrategy::SiteApi,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::TechReport,
+        limitations: Some("cited as the working paper even after journal publication"),
+        example_url: "https://www.nber.org/papers/w29000",
+    },
+    KnownSite {
+        domain: "overleaf.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Fair,
+        entry_type: BibtexEntryType::Misc,
+        limitations: Some("read-only project links only; no authors"),
+        example_url: "https://www.overleaf.com/read/kzxqvbdmnrtp",
+    },
+    KnownSite {
+        domain: "readthedocs.io",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Manual,
+        limitations: None,
+        example_url: "https://requests.readthedocs.io/en/v2.31.0/user/quickstart/",
+    },
+    KnownSite {
+        domain: "semanticscholar.org",
+        strategy: ExtractionStrategy::SiteApi,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Article,
+        limitations: Some("the entry type follows Semantic Scholar's publication type"),
+        example_url: "https://www.semanticscholar.org/paper/Attention-is-All-you-Need-Vaswani-Shazeer/204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+    },
+    KnownSite {
+        domain: "ssrn.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Good,
+        entry_type: BibtexEntryType::Misc,
+        limitations: Some("working papers have no journal"),
+        example_url: "https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4712345",
+    },
+    KnownSite {
+        domain: "tandfonline.com",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Article,
+        limitations: None,
+        example_url: "https://www.tandfonline.com/doi/full/10.1080/09640568.2021.1900000",
+    },
+    KnownSite {
+        domain: "www.jstor.org",
+        strategy: ExtractionStrategy::MetaTags,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Article,
+        limitations: None,
+        example_url: "https://www.jstor.org/stable/1879431",
+    },
+    KnownSite {
+        domain: "zenodo.org",
+        strategy: ExtractionStrategy::JsonLd,
+        quality: Quality::Excellent,
+        entry_type: BibtexEntryType::Dataset,
+        limitations: None,
+        example_url: "https://zenodo.org/records/11200345",
+    },
+];
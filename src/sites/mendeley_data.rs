@@ -0,0 +1,125 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Mendeley Data mints DOIs under this prefix as `10.17632/{id}.{version}`.
+const DOI_PREFIX: &str = "10.17632/";
+
+// Structs for parsing the Mendeley datasets API.
+#[derive(Deserialize, Debug)]
+struct MendeleyDataset {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    contributors: Vec<MendeleyContributor>,
+    publish_date: Option<String>,
+    data_licence: Option<MendeleyLicence>,
+    doi: Option<MendeleyDoi>,
+    version: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct MendeleyContributor {
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct MendeleyLicence {
+    name: String,
+}
+
+// The DOI comes either bare or as `{"id": "10.17632/...", "status": "..."}`.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum MendeleyDoi {
+    Bare(String),
+    Record { id: String },
+}
+
+/// Finds the dataset ID and, when the URL pins one, the version in
+/// `data.mendeley.com/datasets/{id}/{version}` URLs.
+pub fn dataset_id(url: &Url) -> Option<(String, Option<String>)> {
+    if url.host_str()? != "data.mendeley.com" {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    if segments.next()? != "datasets" {
+        return None;
+    }
+    let id = segments.next()?;
+    if !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let version = segments
+        .next()
+        .filter(|version| version.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string);
+    Some((id.to_lowercase(), version))
+}
+
+/// Builds a dataset entry from `https://api.mendeley.com/datasets/{id}`. The API
+/// describes the latest version, so when the URL names an earlier one the DOI is
+/// rewritten to that version's.
+pub async fn extract_mendeley_data(
+    client: &impl HttpClient,
+    id: &str,
+    version: Option<&str>,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!("https://api.mendeley.com/datasets/{}", id);
+    let dataset: MendeleyDataset = super::fetch_json(client, &api_url).await?;
+
+    let version = version
+        .map(str::to_string)
+        .or_else(|| dataset.version.map(|version| version.to_string()));
+    let doi = dataset.doi.map(|doi| match doi {
+        MendeleyDoi::Bare(id) | MendeleyDoi::Record { id } => id,
+    });
+    let names = dataset
+        .contributors
+        .into_iter()
+        .filter_map(
+            |contributor| match (contributor.last_name, contributor.first_name) {
+                (Some(last), Some(first)) => Some(format!("{}, {}", last, first)),
+                (last, first) => last.or(first),
+            },
+        )
+        .collect();
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::Dataset,
+        title: dataset.name.trim().to_string(),
+        author: normalize_authors(names).join(" and "),
+        year: dataset
+            .publish_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: "Mendeley Data".to_string(),
+        doi: versioned_doi(doi, id, version.as_deref()),
+        license: dataset.data_licence.map(|licence| licence.name),
+        abstract_text: dataset.description.filter(|text| !text.trim().is_empty()),
+        resource_type: Some("Dataset".to_string()),
+        version,
+        ..Default::default()
+    })
+}
+
+/// The DOI of the given version of a dataset, e.g. `10.17632/abc123.2`. Each version
+/// has its own DOI, ending in the version number.
+pub fn versioned_doi(doi: Option<String>, id: &str, version: Option<&str>) -> Option<String> {
+    let Some(version) = version else {
+        return doi;
+    };
+    match doi {
+        Some(doi) if !doi.to_lowercase().starts_with(DOI_PREFIX) => Some(doi),
+        _ => Some(format!("{}{}.{}", DOI_PREFIX, id, version)),
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the NBER working paper API.
+#[derive(Deserialize, Debug)]
+struct NberPaper {
+    title: String,
+    #[serde(default)]
+    authors: Vec<NberAuthor>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    #[serde(default)]
+    subjects: Vec<String>,
+    #[serde(rename = "issueDate")]
+    issue_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NberAuthor {
+    name: String,
+}
+
+/// Finds the paper number in `nber.org/papers/w{number}` URLs, e.g. `w29000`.
+pub fn paper_number(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if host != "nber.org" && host != "www.nber.org" {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    if segments.next()? != "papers" {
+        return None;
+    }
+    let number = segments.next()?.to_lowercase();
+    let digits = number.strip_prefix('w')?;
+    (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())).then_some(number)
+}
+
+/// Builds a `@techreport` entry for an NBER working paper from
+/// `https://www.nber.org/api/v1/working_paper/contentType/working_paper/id/{number}`.
+pub async fn extract_nber(
+    client: &impl HttpClient,
+    number: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://www.nber.org/api/v1/working_paper/contentType/working_paper/id/{}",
+        number
+    );
+    let paper: NberPaper = super::fetch_json(client, &api_url).await?;
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type: BibtexEntryType::TechReport,
+        title: paper.title.trim().to_string(),
+        author: normalize_authors(paper.authors.into_iter().map(|a| a.name).collect())
+            .join(" and "),
+        year: paper
+            .issue_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        institution: Some("National Bureau of Economic Research".to_string()),
+        resource_type: Some("NBER Working Paper".to_string()),
+        number: Some(number.to_string()),
+        // NBER registers every working paper's DOI under its own prefix.
+        doi: Some(format!("10.3386/{}", number)),
+        abstract_text: paper.abstract_text.filter(|text| !text.trim().is_empty()),
+        keywords: paper.subjects,
+        ..Default::default()
+    })
+}
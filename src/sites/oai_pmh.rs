@@ -0,0 +1,142 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::dates::parse_year;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+lazy_static! {
+    static ref DSPACE_HANDLE_RE: Regex = Regex::new(r"/handle/(\d+(?:\.\d+)*/\d+)").unwrap();
+    static ref EPRINT_ID_RE: Regex = Regex::new(r"^/(?:id/eprint/)?(\d+)/?").unwrap();
+    static ref DIGITAL_COMMONS_RE: Regex = Regex::new(r"^/([a-z_]+)/(\d+)/?$").unwrap();
+    static ref DOI_RE: Regex = Regex::new(r"10\.\d{4,9}/\S+").unwrap();
+}
+
+// Platforms whose hostnames usually give them away, e.g. `dspace.mit.edu`.
+const REPOSITORY_HOST_MARKERS: &[&str] = &["dspace", "eprints", "digitalcommons", "repository"];
+
+/// OAI-PMH endpoints from `BIBTEXTER_OAI_ENDPOINTS`, a comma-separated list of URLs
+/// such as `https://dspace.mit.edu/oai/request`.
+pub fn configured_endpoints() -> Vec<Url> {
+    std::env::var("BIBTEXTER_OAI_ENDPOINTS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|endpoint| Url::parse(endpoint.trim()).ok())
+        .collect()
+}
+
+/// Works out the OAI identifier of a repository item from its landing page URL:
+/// DSpace handles, EPrints ids and Digital Commons `/{series}/{number}` paths.
+pub fn record_identifier(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let path = url.path();
+
+    let local_id = if let Some(caps) = DSPACE_HANDLE_RE.captures(path) {
+        caps[1].to_string()
+    } else if let Some(caps) = EPRINT_ID_RE.captures(path) {
+        caps[1].to_string()
+    } else if let Some(caps) = DIGITAL_COMMONS_RE.captures(path) {
+        format!("{}-{}", &caps[1], &caps[2])
+    } else {
+        return None;
+    };
+    Some(format!("oai:{}:{}", host, local_id))
+}
+
+/// Finds the OAI-PMH endpoint for a repository: a configured one on the same host,
+/// or else `{base}/oai` or `{base}/oai2` if the host looks like a repository and
+/// answers an `Identify` request there.
+async fn find_endpoint(client: &impl HttpClient, url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if let Some(endpoint) = configured_endpoints()
+        .into_iter()
+        .find(|endpoint| endpoint.host_str() == Some(host))
+    {
+        return Some(endpoint.to_string());
+    }
+
+    if !REPOSITORY_HOST_MARKERS
+        .iter()
+        .any(|marker| host.contains(marker))
+    {
+        return None;
+    }
+    let base = format!("{}://{}", url.scheme(), host);
+    for path in ["/oai", "/oai2"] {
+        let endpoint = format!("{}{}", base, path);
+        let identify = format!("{}?verb=Identify", endpoint);
+        if let Ok(res) = client.get(&identify, Some("text/xml")).await
+            && res.status.is_success()
+            && res.body.contains("<Identify")
+        {
+            return Some(endpoint);
+        }
+    }
+    None
+}
+
+/// Harvests a repository item's Dublin Core record over OAI-PMH. Returns `None` when
+/// the site has no endpoint or the record can't be read, so the page gets scraped instead.
+pub async fn extract_oai_pmh(client: &impl HttpClient, url: &Url) -> Option<ArticleMetadata> {
+    let identifier = record_identifier(url)?;
+    let endpoint = find_endpoint(client, url).await?;
+    eprintln!("-> Harvesting {} over OAI-PMH.", identifier);
+
+    let encoded: String = url::form_urlencoded::byte_serialize(identifier.as_bytes()).collect();
+    let request_url = format!(
+        "{}?verb=GetRecord&metadataPrefix=oai_dc&identifier={}",
+        endpoint, encoded
+    );
+    let res = client.get(&request_url, Some("text/xml")).await.ok()?;
+    if !res.status.is_success() {
+        return None;
+    }
+    parse_oai_dc(&res.body)
+}
+
+/// Reads an `oai_dc` record from a `GetRecord` response.
+pub fn parse_oai_dc(xml: &str) -> Option<ArticleMetadata> {
+    let title = dc_values(xml, "title").into_iter().next()?;
+
+    let identifiers = dc_values(xml, "identifier");
+    let doi = identifiers
+        .iter()
+        .find_map(|id| DOI_RE.find(id))
+        .map(|doi| doi.as_str().trim_end_matches(['.', ',', ';']).to_string());
+    let keywords = dc_values(xml, "subject");
+
+    Some(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        title,
+        author: normalize_authors(dc_values(xml, "creator")).join(" and "),
+        year: dc_values(xml, "date")
+            .iter()
+            .find_map(|date| parse_year(date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: dc_values(xml, "publisher")
+            .into_iter()
+            .next()
+            .unwrap_or_default(),
+        doi,
+        license: dc_values(xml, "rights").into_iter().next(),
+        abstract_text: dc_values(xml, "description").into_iter().next(),
+        language: dc_values(xml, "language").into_iter().next(),
+        keywords,
+        ..Default::default()
+    })
+}
+
+/// All non-empty values of a Dublin Core element, e.g. every `<dc:creator>`.
+fn dc_values(xml: &str, element: &str) -> Vec<String> {
+    let re = Regex::new(&format!(r"(?s)<dc:{0}(?:\s[^>]*)?>(.*?)</dc:{0}>", element)).unwrap();
+    re.captures_iter(xml)
+        .map(|caps| {
+            let text = caps[1].split_whitespace().collect::<Vec<_>>().join(" ");
+            html_escape::decode_html_entities(&text).into_owned()
+        })
+        .filter(|value| !value.is_empty())
+        .collect()
+}
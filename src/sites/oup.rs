@@ -0,0 +1,90 @@
+use scraper::{Html as ScraperHtml, Selector};
+use serde_json::Value;
+use url::Url;
+
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::extract::{
+    ArticleMetadata, ExtractionStrategy, extract_metadata, ld_text, schema_names, select_text,
+};
+
+// `isPartOf` chains run article -> issue -> volume -> journal; stop well past that.
+const MAX_PART_OF_DEPTH: usize = 5;
+
+/// Scrapes an Oxford Academic article page. Its `ScholarlyArticle` JSON-LD nests the
+/// journal under issue and volume `isPartOf` nodes and often gives only a bare year,
+/// while page numbers only appear in the Highwire tags.
+pub fn extract_oup(document: &ScraperHtml, url: &Url) -> Option<ArticleMetadata> {
+    // Highwire and PRISM tags, including `citation_firstpage`/`citation_lastpage`.
+    let mut metadata = extract_metadata(document, url);
+
+    if let Some(article) = scholarly_article(document) {
+        metadata.strategy = ExtractionStrategy::JsonLd;
+        if let Some(title) = article
+            .get("headline")
+            .or_else(|| article.get("name"))
+            .and_then(ld_text)
+        {
+            metadata.title = title;
+        }
+        if let Some(authors) = schema_names(article.get("author")) {
+            metadata.author = authors;
+        }
+        if let Some(year) = article
+            .get("datePublished")
+            .and_then(ld_text)
+            .and_then(|date| parse_year(&date))
+        {
+            metadata.year = year.to_string();
+        }
+        metadata.journal = periodical_name(&article).or(metadata.journal);
+    }
+
+    metadata.journal = metadata
+        .journal
+        .or_else(|| select_text(document, "meta[name='prism.publicationName']", "content"));
+    if metadata.journal.is_some() {
+        metadata.entry_type = BibtexEntryType::Article;
+    }
+    if metadata.doi.is_none() {
+        metadata.doi = select_text(document, "meta[name='citation_doi']", "content")
+            .or_else(|| doi_from_url(url));
+    }
+
+    (!metadata.title.is_empty()).then_some(metadata)
+}
+
+/// Reads the DOI from `/article-abstract/doi/10.1093/...` style URLs.
+fn doi_from_url(url: &Url) -> Option<String> {
+    let (_, doi) = url.path().split_once("/doi/")?;
+    doi.starts_with("10.").then(|| doi.to_string())
+}
+
+fn scholarly_article(document: &ScraperHtml) -> Option<Value> {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    document
+        .select(&selector)
+        .filter_map(|element| serde_json::from_str::<Value>(&element.inner_html()).ok())
+        .flat_map(|json| match json {
+            Value::Array(nodes) => nodes,
+            node => vec![node],
+        })
+        .find(|node| node.get("@type").and_then(Value::as_str) == Some("ScholarlyArticle"))
+}
+
+/// Follows the `isPartOf` chain up to the `Periodical`, or the outermost named node.
+fn periodical_name(article: &Value) -> Option<String> {
+    let mut name = None;
+    let mut node = article.get("isPartOf");
+    for _ in 0..MAX_PART_OF_DEPTH {
+        let Some(part) = node else { break };
+        if let Some(part_name) = part.get("name").and_then(ld_text) {
+            if part.get("@type").and_then(Value::as_str) == Some("Periodical") {
+                return Some(part_name);
+            }
+            name = Some(part_name);
+        }
+        node = part.get("isPartOf");
+    }
+    name
+}
@@ -0,0 +1,28 @@
+use scraper::Html as ScraperHtml;
+
+use crate::extract::{ArticleMetadata, ExtractionStrategy, select_text};
+
+/// Overleaf appends its product name to every page title.
+const TITLE_SUFFIX: &str = " - Overleaf";
+
+/// Reads the project name of a read-only Overleaf share link (`/read/{token}`). Authors
+/// are only shown to signed-in collaborators, so the entry says as much in a comment.
+pub fn extract_overleaf(document: &ScraperHtml) -> Option<ArticleMetadata> {
+    let page_title = select_text(document, "title", "text")?;
+    let title = match page_title.rfind(TITLE_SUFFIX) {
+        Some(index) => page_title[..index].trim(),
+        None => page_title.trim(),
+    };
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(ArticleMetadata {
+        strategy: ExtractionStrategy::MetaTags,
+        title: title.to_string(),
+        publisher: "Overleaf".to_string(),
+        note: Some("Overleaf LaTeX document".to_string()),
+        comment: Some("NOTE: Author information not available for Overleaf shared links".into()),
+        ..Default::default()
+    })
+}
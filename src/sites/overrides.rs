@@ -0,0 +1,347 @@
+use lazy_static::lazy_static;
+use scraper::Html as ScraperHtml;
+use std::collections::HashMap;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::dates::parse_year;
+use crate::extract::{ArticleMetadata, select_all_text, select_text};
+
+// --- Per-Site Overrides ---
+
+/// Override files bundled into the binary, by name. Each is a small TOML file; see
+/// `assets/sites/acm.toml` for the layout.
+const BUNDLED_OVERRIDES: &[(&str, &str)] = &[
+    ("acm", include_str!("../../assets/sites/acm.toml")),
+    ("ieee", include_str!("../../assets/sites/ieee.toml")),
+];
+
+// Fields an override can point a selector at, or tell the scrapers to ignore.
+const OVERRIDE_FIELDS: &[&str] = &[
+    "title",
+    "author",
+    "year",
+    "publisher",
+    "journal",
+    "volume",
+    "number",
+    "booktitle",
+    "doi",
+    "isbn",
+    "pages",
+    "abstract",
+    "keywords",
+    "language",
+];
+
+lazy_static! {
+    static ref OVERRIDES: HashMap<String, SiteOverride> = load_overrides();
+}
+
+/// Corrections for a site whose pages the generic strategies misread.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteOverride {
+    pub name: String,
+    /// Hosts the override applies to, subdomains included.
+    pub hosts: Vec<String>,
+    pub entry_type: Option<BibtexEntryType>,
+    /// Where to find each field: a CSS selector, then `@attribute` to read an attribute
+    /// instead of the element's text, e.g. `meta[name='dc.Title']@content`.
+    pub selectors: HashMap<String, String>,
+    /// Fields to drop from whatever the generic strategies found.
+    pub ignore: Vec<String>,
+    /// Example pages and the field values extraction should produce for them.
+    pub tests: Vec<OverrideTest>,
+}
+
+/// An example page for an override, with the fields it should yield.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OverrideTest {
+    pub url: String,
+    pub expected: HashMap<String, String>,
+}
+
+fn load_overrides() -> HashMap<String, SiteOverride> {
+    let mut overrides = HashMap::new();
+    for site in bundled() {
+        for host in &site.hosts {
+            overrides.insert(host.clone(), site.clone());
+        }
+    }
+    overrides
+}
+
+/// Parses the bundled override files now rather than on the first matching request,
+/// so that a broken file stops the server at startup.
+pub fn load() {
+    lazy_static::initialize(&OVERRIDES);
+}
+
+/// Every bundled override. Panics if one of the files is invalid.
+pub fn bundled() -> Vec<SiteOverride> {
+    BUNDLED_OVERRIDES
+        .iter()
+        .map(|(name, source)| {
+            parse_override(name, source)
+                .unwrap_or_else(|err| panic!("Invalid site override {}.toml: {}", name, err))
+        })
+        .collect()
+}
+
+/// The override for `host`, or for the closest parent domain that has one.
+pub fn find_override(host: &str) -> Option<&'static SiteOverride> {
+    let mut domain = host;
+    loop {
+        if let Some(site) = OVERRIDES.get(domain) {
+            return Some(site);
+        }
+        domain = domain.split_once('.')?.1;
+    }
+}
+
+/// Applies an override to what the generic strategies found: ignored fields are
+/// cleared, then each field the override's selectors find replaces the scraped value.
+pub fn apply(site: &SiteOverride, document: &ScraperHtml, metadata: &mut ArticleMetadata) {
+    for field in &site.ignore {
+        set_field(metadata, field, Vec::new());
+    }
+    for (field, selector) in &site.selectors {
+        let (css, attr) = selector.rsplit_once('@').unwrap_or((selector, "text"));
+        let values = if attr == "text" {
+            select_text(document, css, attr).into_iter().collect()
+        } else {
+            select_all_text(document, css, attr)
+        };
+        let values: Vec<String> = values.into_iter().filter(|v| !v.is_empty()).collect();
+        if !values.is_empty() {
+            set_field(metadata, field, values);
+        }
+    }
+    if let Some(entry_type) = site.entry_type {
+        metadata.entry_type = entry_type;
+    }
+}
+
+/// Sets a field from the values a selector found; an empty list clears it. Only authors
+/// and keywords use every value, the other fields take the first.
+fn set_field(metadata: &mut ArticleMetadata, field: &str, values: Vec<String>) {
+    let first = values.first().cloned();
+    match field {
+        "title" => metadata.title = first.unwrap_or_default(),
+        "author" => metadata.author = normalize_authors(values).join(" and "),
+        "year" => {
+            metadata.year = first
+                .and_then(|date| parse_year(&date))
+                .map(|year| year.to_string())
+                .unwrap_or_default()
+        }
+        "publisher" => metadata.publisher = first.unwrap_or_default(),
+        "journal" => metadata.journal = first,
+        "volume" => metadata.volume = first,
+        "number" => metadata.number = first,
+        "booktitle" => metadata.booktitle = first,
+        "doi" => metadata.doi = first,
+        "isbn" => metadata.isbn = first,
+        "pages" => metadata.page_range = first,
+        "abstract" => metadata.abstract_text = first,
+        "keywords" => metadata.keywords = values,
+        "language" => metadata.language = first,
+        _ => {}
+    }
+}
+
+/// Reads an override file. Top-level `hosts`, `entry_type` and `ignore` keys, a
+/// `[selectors]` table mapping fields to selectors, and `[[tests]]` tables holding a
+/// `url` plus the expected value of each field.
+pub fn parse_override(name: &str, source: &str) -> Result<SiteOverride, String> {
+    let document = parse_toml(source)?;
+    let mut site = SiteOverride {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    for (key, value) in document.root {
+        match (key.as_str(), value) {
+            ("hosts", TomlValue::Array(hosts)) => site.hosts = hosts,
+            ("entry_type", TomlValue::String(entry_type)) => {
+                site.entry_type = Some(
+                    BibtexEntryType::ALL
+                        .iter()
+                        .copied()
+                        .find(|known| known.as_str() == entry_type)
+                        .ok_or_else(|| format!("unknown entry type '{}'", entry_type))?,
+                );
+            }
+            ("ignore", TomlValue::Array(fields)) => site.ignore = fields,
+            (key, _) => return Err(format!("unexpected key '{}'", key)),
+        }
+    }
+    if site.hosts.is_empty() {
+        return Err("no hosts given".into());
+    }
+
+    for (section, table) in document.tables {
+        if section != "selectors" {
+            return Err(format!("unexpected table [{}]", section));
+        }
+        for (field, value) in table {
+            let TomlValue::String(selector) = value else {
+                return Err(format!("selector for '{}' must be a string", field));
+            };
+            site.selectors.insert(field, selector);
+        }
+    }
+
+    for (section, tables) in document.array_tables {
+        if section != "tests" {
+            return Err(format!("unexpected table [[{}]]", section));
+        }
+        for mut table in tables {
+            let Some(TomlValue::String(url)) = table.remove("url") else {
+                return Err("test without a url".into());
+            };
+            let mut expected = HashMap::new();
+            for (field, value) in table {
+                let TomlValue::String(value) = value else {
+                    return Err(format!("expected value for '{}' must be a string", field));
+                };
+                expected.insert(field, value);
+            }
+            site.tests.push(OverrideTest { url, expected });
+        }
+    }
+
+    let fields = site
+        .selectors
+        .keys()
+        .chain(&site.ignore)
+        .chain(site.tests.iter().flat_map(|test| test.expected.keys()));
+    for field in fields {
+        if !OVERRIDE_FIELDS.contains(&field.as_str()) && field != "entry_type" {
+            return Err(format!("unknown field '{}'", field));
+        }
+    }
+    Ok(site)
+}
+
+// The subset of TOML the override files use: string values and single-line arrays of
+// strings, at the top level, in `[table]`s and in `[[array]]` tables.
+#[derive(Debug, Clone, PartialEq)]
+enum TomlValue {
+    String(String),
+    Array(Vec<String>),
+}
+
+type TomlTable = HashMap<String, TomlValue>;
+
+#[derive(Debug, Default)]
+struct TomlDocument {
+    root: TomlTable,
+    tables: HashMap<String, TomlTable>,
+    array_tables: HashMap<String, Vec<TomlTable>>,
+}
+
+enum Section {
+    Root,
+    Table(String),
+    ArrayTable(String),
+}
+
+fn parse_toml(source: &str) -> Result<TomlDocument, String> {
+    let mut document = TomlDocument::default();
+    let mut section = Section::Root;
+
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        let at_line = |err: String| format!("line {}: {}", number + 1, err);
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+            let name = name.trim().to_string();
+            document
+                .array_tables
+                .entry(name.clone())
+                .or_default()
+                .push(TomlTable::new());
+            section = Section::ArrayTable(name);
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            document.tables.entry(name.clone()).or_default();
+            section = Section::Table(name);
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| at_line(format!("expected key = value, found '{}'", line)))?;
+        let key = key.trim().trim_matches('"').to_string();
+        let value = parse_value(value.trim()).map_err(at_line)?;
+        let table = match &section {
+            Section::Root => &mut document.root,
+            Section::Table(name) => document.tables.get_mut(name).unwrap(),
+            Section::ArrayTable(name) => document
+                .array_tables
+                .get_mut(name)
+                .and_then(|tables| tables.last_mut())
+                .unwrap(),
+        };
+        if table.insert(key.clone(), value).is_some() {
+            return Err(at_line(format!("duplicate key '{}'", key)));
+        }
+    }
+    Ok(document)
+}
+
+fn parse_value(text: &str) -> Result<TomlValue, String> {
+    if let Some(items) = text.strip_prefix('[') {
+        let mut rest = items.trim_start();
+        let mut values = Vec::new();
+        loop {
+            if let Some(after) = rest.strip_prefix(']') {
+                return trailing_comment(after).map(|_| TomlValue::Array(values));
+            }
+            let (value, after) = parse_string(rest)?;
+            values.push(value);
+            rest = after.trim_start();
+            rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+        }
+    }
+    let (value, after) = parse_string(text)?;
+    trailing_comment(after).map(|_| TomlValue::String(value))
+}
+
+/// Reads a basic (`"..."`) or literal (`'...'`) string from the start of `text`,
+/// returning it and what follows.
+fn parse_string(text: &str) -> Result<(String, &str), String> {
+    let mut chars = text.char_indices();
+    let quote = match chars.next() {
+        Some((_, quote @ ('"' | '\''))) => quote,
+        _ => return Err(format!("expected a quoted string, found '{}'", text)),
+    };
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Ok((value, &text[i + 1..])),
+            '\\' if quote == '"' => match chars.next().map(|(_, c)| c) {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(c @ ('"' | '\\')) => value.push(c),
+                other => return Err(format!("unsupported escape '\\{}'", other.unwrap_or(' '))),
+            },
+            c => value.push(c),
+        }
+    }
+    Err("unterminated string".into())
+}
+
+fn trailing_comment(rest: &str) -> Result<(), String> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected '{}' after value", rest))
+    }
+}
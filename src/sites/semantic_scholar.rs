@@ -0,0 +1,106 @@
+use serde::Deserialize;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::BibtexEntryType;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Semantic Scholar Graph API paper endpoint.
+#[derive(Deserialize, Debug)]
+struct S2Paper {
+    title: String,
+    #[serde(default)]
+    authors: Vec<S2Author>,
+    year: Option<u16>,
+    venue: Option<String>,
+    #[serde(rename = "externalIds")]
+    external_ids: Option<S2ExternalIds>,
+    #[serde(rename = "publicationTypes")]
+    publication_types: Option<Vec<String>>,
+    #[serde(rename = "openAccessPdf")]
+    open_access_pdf: Option<S2OpenAccessPdf>,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2Author {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2ExternalIds {
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct S2OpenAccessPdf {
+    url: Option<String>,
+}
+
+/// Finds the 40-character paper ID in `/paper/{title-slug}/{paperId}` and `/paper/{paperId}` URLs.
+pub fn paper_id(url: &Url) -> Option<String> {
+    let mut segments = url.path_segments()?;
+    if segments.next()? != "paper" {
+        return None;
+    }
+    segments
+        .rfind(|segment| !segment.is_empty())
+        .filter(|id| id.len() == 40 && id.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_string)
+}
+
+/// Builds metadata for a paper from `https://api.semanticscholar.org/graph/v1/paper/{id}`.
+pub async fn extract_semantic_scholar(
+    client: &impl HttpClient,
+    id: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://api.semanticscholar.org/graph/v1/paper/{}?fields=title,authors,year,venue,externalIds,publicationTypes,openAccessPdf",
+        id
+    );
+    let paper: S2Paper = super::fetch_json(client, &api_url).await?;
+
+    let venue = paper.venue.filter(|venue| !venue.is_empty());
+    let entry_type = entry_type(paper.publication_types.as_deref().unwrap_or_default());
+    let (journal, booktitle) = match entry_type {
+        BibtexEntryType::Article => (venue, None),
+        BibtexEntryType::InProceedings | BibtexEntryType::InCollection => (None, venue),
+        _ => (None, None),
+    };
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type,
+        title: paper.title,
+        author: normalize_authors(paper.authors.into_iter().map(|a| a.name).collect())
+            .join(" and "),
+        year: paper.year.map(|year| year.to_string()).unwrap_or_default(),
+        journal,
+        booktitle,
+        doi: paper.external_ids.and_then(|ids| ids.doi),
+        pdf: paper
+            .open_access_pdf
+            .and_then(|pdf| pdf.url)
+            .filter(|url| !url.is_empty()),
+        ..Default::default()
+    })
+}
+
+/// Maps Semantic Scholar's `publicationTypes` to an entry type. A paper can have several,
+/// e.g. `["Review", "JournalArticle"]`; the first one that names a venue type wins.
+fn entry_type(publication_types: &[String]) -> BibtexEntryType {
+    publication_types
+        .iter()
+        .find_map(|publication_type| match publication_type.as_str() {
+            "JournalArticle" | "Review" | "LettersAndComments" | "Editorial" => {
+                Some(BibtexEntryType::Article)
+            }
+            "Conference" => Some(BibtexEntryType::InProceedings),
+            "Book" => Some(BibtexEntryType::Book),
+            "BookSection" => Some(BibtexEntryType::InCollection),
+            _ => None,
+        })
+        .unwrap_or(BibtexEntryType::Misc)
+}
@@ -0,0 +1,32 @@
+use scraper::Html as ScraperHtml;
+use url::Url;
+
+use crate::extract::select_text;
+
+const SHARED_IT_PARAM: &str = "shared-article-renderer";
+
+/// The SharedIt view of a SpringerLink article, which shows the full metadata without
+/// a subscription. Returns `None` for other pages or when already on the SharedIt view.
+pub fn shared_it_url(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if host != "link.springer.com" || !url.path().starts_with("/article/") {
+        return None;
+    }
+    match url.query() {
+        Some(query)
+            if query
+                .split('&')
+                .any(|pair| pair.starts_with(SHARED_IT_PARAM)) =>
+        {
+            None
+        }
+        Some(_) => Some(format!("{}&{}", url, SHARED_IT_PARAM)),
+        None => Some(format!("{}?{}", url, SHARED_IT_PARAM)),
+    }
+}
+
+/// Whether Springer marks the page as showing the full text, as SharedIt views do.
+pub fn full_text_available(document: &ScraperHtml) -> bool {
+    select_text(document, "meta[name='full-text-available']", "content")
+        .is_some_and(|available| available.eq_ignore_ascii_case("true"))
+}
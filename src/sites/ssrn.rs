@@ -0,0 +1,214 @@
+use scraper::{Html as ScraperHtml, Selector};
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy};
+use crate::http::HttpClient;
+
+// Structs for parsing the Next.js `__NEXT_DATA__` blob on SSRN abstract pages.
+#[derive(Deserialize, Debug)]
+struct NextData {
+    props: NextProps,
+}
+
+#[derive(Deserialize, Debug)]
+struct NextProps {
+    #[serde(rename = "pageProps")]
+    page_props: PageProps,
+}
+
+#[derive(Deserialize, Debug)]
+struct PageProps {
+    paper: Option<SsrnPaper>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SsrnPaper {
+    title: String,
+    #[serde(default)]
+    authors: Vec<SsrnAuthor>,
+    #[serde(rename = "publicationDate")]
+    publication_date: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    keywords: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SsrnAuthor {
+    #[serde(rename = "firstName")]
+    first_name: Option<String>,
+    #[serde(rename = "lastName")]
+    last_name: Option<String>,
+}
+
+// Structs for parsing the Elsevier article retrieval API.
+#[derive(Deserialize, Debug)]
+struct ElsevierResponse {
+    #[serde(rename = "full-text-retrieval-response")]
+    retrieval: ElsevierRetrieval,
+}
+
+#[derive(Deserialize, Debug)]
+struct ElsevierRetrieval {
+    coredata: ElsevierCoredata,
+}
+
+#[derive(Deserialize, Debug)]
+struct ElsevierCoredata {
+    #[serde(rename = "dc:title")]
+    title: Option<String>,
+    // A single `{"$": name}` object for one author, an array of them for several.
+    #[serde(rename = "dc:creator", default)]
+    creator: Value,
+    #[serde(rename = "prism:coverDate")]
+    cover_date: Option<String>,
+    #[serde(rename = "prism:doi")]
+    doi: Option<String>,
+    #[serde(rename = "dc:description")]
+    description: Option<String>,
+}
+
+/// Finds the abstract ID in `papers.cfm?abstract_id=4600000` and `/abstract=4600000` URLs.
+pub fn abstract_id(url: &Url) -> Option<String> {
+    let id = url
+        .query_pairs()
+        .find(|(key, _)| key == "abstract_id")
+        .map(|(_, id)| id.into_owned())
+        .or_else(|| {
+            let (_, id) = url.path().split_once("/abstract=")?;
+            Some(id.to_string())
+        })?;
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then_some(id)
+}
+
+/// Reads an SSRN abstract page's embedded `__NEXT_DATA__`, which carries the full
+/// paper record. Returns `None` on older pages that don't have it.
+pub fn extract_ssrn(document: &ScraperHtml, url: &Url) -> Option<ArticleMetadata> {
+    let selector = Selector::parse("script#__NEXT_DATA__").unwrap();
+    let script = document.select(&selector).next()?.inner_html();
+    let paper = serde_json::from_str::<NextData>(&script)
+        .ok()?
+        .props
+        .page_props
+        .paper?;
+
+    let authors = paper
+        .authors
+        .into_iter()
+        .filter_map(|author| match (author.first_name, author.last_name) {
+            (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
+            (first, last) => last.or(first),
+        })
+        .collect();
+
+    Some(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        title: paper.title,
+        author: normalize_authors(authors).join(" and "),
+        year: paper
+            .publication_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: "SSRN".to_string(),
+        doi: paper
+            .doi
+            .filter(|doi| !doi.is_empty())
+            .or_else(|| abstract_id(url).map(|id| ssrn_doi(&id))),
+        abstract_text: paper.abstract_text.filter(|text| !text.is_empty()),
+        keywords: paper
+            .keywords
+            .unwrap_or_default()
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(str::to_string)
+            .collect(),
+        ..Default::default()
+    })
+}
+
+/// Every SSRN paper is registered under `10.2139/ssrn.{abstract id}`.
+fn ssrn_doi(id: &str) -> String {
+    format!("10.2139/ssrn.{}", id)
+}
+
+/// The key for Elsevier's APIs, from `ELSEVIER_API_KEY`.
+pub fn elsevier_api_key() -> Option<String> {
+    std::env::var("ELSEVIER_API_KEY")
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Looks an SSRN paper up through `https://api.elsevier.com/content/article/ssrn:{id}`.
+pub async fn extract_elsevier(
+    client: &impl HttpClient,
+    id: &str,
+    api_key: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://api.elsevier.com/content/article/ssrn:{}?apiKey={}",
+        id, api_key
+    );
+    let response: ElsevierResponse = super::fetch_json(client, &api_url).await?;
+    let coredata = response.retrieval.coredata;
+
+    let creators = match coredata.creator {
+        Value::Array(creators) => creators,
+        Value::Null => Vec::new(),
+        creator => vec![creator],
+    };
+    let authors = creators
+        .iter()
+        .filter_map(|creator| creator.get("$").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        title: coredata.title.unwrap_or_default(),
+        author: normalize_authors(authors).join(" and "),
+        year: coredata
+            .cover_date
+            .and_then(|date| parse_year(&date))
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publisher: "SSRN".to_string(),
+        doi: coredata.doi.or_else(|| Some(ssrn_doi(id))),
+        abstract_text: coredata.description,
+        ..Default::default()
+    })
+}
+
+/// Combines the Elsevier API's record with what the page showed. The API's title can
+/// differ from the page's (older revisions, different casing), so the page's title wins
+/// to keep citations matching what readers see; gaps in the API record are filled from the page.
+pub fn merge_elsevier(page: ArticleMetadata, api: ArticleMetadata) -> ArticleMetadata {
+    ArticleMetadata {
+        title: if page.title.is_empty() {
+            api.title
+        } else {
+            page.title
+        },
+        author: if api.author.is_empty() {
+            page.author
+        } else {
+            api.author
+        },
+        year: if api.year.is_empty() {
+            page.year
+        } else {
+            api.year
+        },
+        abstract_text: api.abstract_text.or(page.abstract_text),
+        keywords: page.keywords,
+        ..api
+    }
+}
@@ -0,0 +1,71 @@
+use scraper::Html as ScraperHtml;
+use url::Url;
+
+use crate::authors::normalize_authors;
+use crate::dates::parse_year;
+use crate::extract::{ArticleMetadata, extract_metadata, select_all_text, select_text};
+
+/// Pulls the `10.1080/...` DOI out of `/doi/{abs,full,pdf}/10.1080/...` article URLs.
+pub fn doi_from_url(url: &Url) -> Option<String> {
+    let (_, doi) = url.path().split_once("/doi/")?;
+    let doi = doi
+        .trim_start_matches("abs/")
+        .trim_start_matches("full/")
+        .trim_start_matches("pdf/");
+    doi.starts_with("10.").then(|| doi.to_string())
+}
+
+/// Scrapes a Taylor & Francis Online article page. Its Highwire tags already list
+/// authors as "Last, First" and its `citation_doi` may carry a resolver URL prefix.
+pub fn extract_taylor_francis(document: &ScraperHtml, url: &Url) -> Option<ArticleMetadata> {
+    let mut metadata = extract_metadata(document, url);
+
+    if let Some(title) = select_text(document, "meta[name='citation_title']", "content")
+        .or_else(|| select_text(document, "meta[name='dc.Title']", "content"))
+    {
+        metadata.title = title;
+    }
+    if metadata.title.is_empty() {
+        return None;
+    }
+
+    // Already "Last, First": pass them through untouched so BibTeX parses them correctly.
+    let authors = select_all_text(document, "meta[name='citation_author']", "content");
+    if !authors.is_empty() {
+        metadata.author = normalize_authors(authors).join(" and ");
+    }
+
+    metadata.doi = select_text(document, "meta[name='citation_doi']", "content")
+        .and_then(|doi| strip_doi_prefix(&doi))
+        .or_else(|| doi_from_url(url));
+
+    if let Some(year) = select_text(
+        document,
+        "meta[name='citation_publication_date']",
+        "content",
+    )
+    .or_else(|| select_text(document, "meta[name='citation_online_date']", "content"))
+    .or_else(|| select_text(document, "meta[name='dc.Date']", "content"))
+    .and_then(|date| parse_year(&date))
+    {
+        metadata.year = year.to_string();
+    }
+
+    // The tag's presence, not its content, marks a freely readable article.
+    metadata.open_access = Some(
+        select_text(
+            document,
+            "meta[name='citation_fulltext_world_readable']",
+            "content",
+        )
+        .is_some(),
+    );
+
+    Some(metadata)
+}
+
+// Drops `https://doi.org/`, `https://www.tandfonline.com/doi/` and similar prefixes.
+fn strip_doi_prefix(doi: &str) -> Option<String> {
+    let start = doi.find("10.")?;
+    Some(doi[start..].trim().to_string())
+}
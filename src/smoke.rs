@@ -0,0 +1,73 @@
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Instant;
+
+use crate::bibtex::OutputMode;
+use crate::enrich::{BibItem, parse_bib};
+use crate::extract::{ExtractionStrategy, fetch_and_generate_entry};
+use crate::http::HttpClient;
+use crate::sites::known::KNOWN_SITES;
+
+// --- Extraction Smoke Tests ---
+
+// A DOI link, which no known site's example exercises.
+const DOI_TEST_URL: &str = "https://doi.org/10.1145/3158154";
+
+/// The outcome of extracting one test URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct SmokeReport {
+    pub url: String,
+    pub success: bool,
+    pub strategy: Option<ExtractionStrategy>,
+    pub elapsed_ms: u128,
+    pub title: Option<String>,
+    pub error: Option<String>,
+}
+
+/// The URLs a smoke test picks from: each known site's example page and a DOI link.
+pub fn smoke_test_urls() -> Vec<&'static str> {
+    KNOWN_SITES
+        .iter()
+        .map(|site| site.example_url)
+        .chain([DOI_TEST_URL])
+        .collect()
+}
+
+/// One of [`smoke_test_urls`], chosen at random.
+pub fn random_smoke_test_url() -> &'static str {
+    let urls = smoke_test_urls();
+    // `RandomState` is seeded afresh each time, which is random enough to pick a page.
+    let random = RandomState::new().build_hasher().finish();
+    urls[(random % urls.len() as u64) as usize]
+}
+
+/// Extracts `url` as `/api/bibtex` would and reports how it went.
+pub async fn run_smoke_test(client: &impl HttpClient, url: &str) -> SmokeReport {
+    let started = Instant::now();
+    let result = fetch_and_generate_entry(client, url, OutputMode::Bibtex).await;
+    let elapsed_ms = started.elapsed().as_millis();
+    match result {
+        Ok(entry) => SmokeReport {
+            url: url.to_string(),
+            success: true,
+            strategy: Some(entry.strategy),
+            elapsed_ms,
+            title: parse_bib(&entry.bibtex)
+                .into_iter()
+                .find_map(|item| match item {
+                    BibItem::Entry(entry) => entry.field("title").map(str::to_string),
+                    BibItem::Text(_) => None,
+                }),
+            error: None,
+        },
+        Err(err) => SmokeReport {
+            url: url.to_string(),
+            success: false,
+            strategy: None,
+            elapsed_ms,
+            title: None,
+            error: Some(err.kind().to_string()),
+        },
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+
+use crate::error::AppError;
+use crate::extract::ExtractionStrategy;
+use crate::publisher::cache_counts;
+
+// How many domains `/api/stats` lists.
+const TOP_DOMAINS: usize = 10;
+
+/// Running totals of the extraction requests this process has served.
+#[derive(Debug, Default)]
+pub struct Stats {
+    total_requests: u64,
+    domains: HashMap<String, u64>,
+    strategies: HashMap<ExtractionStrategy, StrategyTotals>,
+    errors: HashMap<&'static str, u64>,
+}
+
+#[derive(Debug, Default)]
+struct StrategyTotals {
+    count: u64,
+    latency: Duration,
+}
+
+#[derive(Serialize)]
+pub struct StatsResponse {
+    total_requests: u64,
+    error_rate: f64,
+    strategies: Vec<StrategyStats>,
+    top_domains: Vec<DomainStats>,
+    errors: Vec<ErrorStats>,
+    cache: CacheStats,
+}
+
+#[derive(Serialize)]
+struct StrategyStats {
+    name: &'static str,
+    count: u64,
+    average_latency_ms: f64,
+}
+
+#[derive(Serialize)]
+struct DomainStats {
+    domain: String,
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct ErrorStats {
+    variant: &'static str,
+    count: u64,
+    rate: f64,
+}
+
+#[derive(Serialize)]
+struct CacheStats {
+    hits: u64,
+    misses: u64,
+    hit_rate: f64,
+}
+
+impl Stats {
+    /// Counts one extraction of `url`, either the strategy that succeeded or the error it hit.
+    pub fn record(
+        &mut self,
+        url: &str,
+        outcome: Result<ExtractionStrategy, &AppError>,
+        latency: Duration,
+    ) {
+        self.total_requests += 1;
+
+        if let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        {
+            *self.domains.entry(host).or_default() += 1;
+        }
+
+        match outcome {
+            Ok(strategy) => {
+                let totals = self.strategies.entry(strategy).or_default();
+                totals.count += 1;
+                totals.latency += latency;
+            }
+            Err(err) => *self.errors.entry(err.kind()).or_default() += 1,
+        }
+    }
+
+    /// Summarises the totals for `/api/stats`.
+    pub fn snapshot(&self) -> StatsResponse {
+        let rate = |count: u64, total: u64| match total {
+            0 => 0.0,
+            total => count as f64 / total as f64,
+        };
+
+        let strategies = ExtractionStrategy::ALL
+            .iter()
+            .map(|strategy| {
+                let totals = self.strategies.get(strategy);
+                StrategyStats {
+                    name: strategy.as_str(),
+                    count: totals.map_or(0, |totals| totals.count),
+                    // Totals only exist once a request has used the strategy, so count >= 1.
+                    average_latency_ms: totals.map_or(0.0, |totals| {
+                        totals.latency.as_secs_f64() * 1000.0 / totals.count as f64
+                    }),
+                }
+            })
+            .collect();
+
+        let mut top_domains = self
+            .domains
+            .iter()
+            .map(|(domain, count)| DomainStats {
+                domain: domain.clone(),
+                count: *count,
+            })
+            .collect::<Vec<_>>();
+        top_domains.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.domain.cmp(&b.domain)));
+        top_domains.truncate(TOP_DOMAINS);
+
+        let mut errors = self
+            .errors
+            .iter()
+            .map(|(variant, count)| ErrorStats {
+                variant,
+                count: *count,
+                rate: rate(*count, self.total_requests),
+            })
+            .collect::<Vec<_>>();
+        errors.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.variant.cmp(b.variant)));
+
+        let failed = self.errors.values().sum();
+        let (hits, misses) = cache_counts();
+
+        StatsResponse {
+            total_requests: self.total_requests,
+            error_rate: rate(failed, self.total_requests),
+            strategies,
+            top_domains,
+            errors,
+            cache: CacheStats {
+                hits,
+                misses,
+                hit_rate: rate(hits, hits + misses),
+            },
+        }
+    }
+}
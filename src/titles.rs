@@ -0,0 +1,143 @@
+use url::Url;
+
+// --- Title Cleanup ---
+
+// What sites put between a page's title and their own name.
+const SITE_SEPARATORS: &[&str] = &[" | ", " - ", " :: ", " \u{2014} ", " \u{2013} "];
+
+// Markers search engines and link aggregators put in front of document titles.
+const TITLE_PREFIXES: &[&str] = &["[PDF]", "[HTML]", "[DOC]", "(PDF)"];
+
+/// Cleans up a scraped page title: drops a trailing site name such as
+/// `| Towards Data Science` or `- arXiv`, drops a leading `[PDF]`, and recases titles
+/// written entirely in capitals or entirely in lowercase as sentence case.
+///
+/// `site_name` may be the site's display name or its host; `towardsdatascience.com`
+/// matches a `Towards Data Science` suffix.
+pub fn normalize_title(raw: &str, site_name: &str) -> String {
+    let mut title = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    for prefix in TITLE_PREFIXES {
+        if title.len() > prefix.len()
+            && title.is_char_boundary(prefix.len())
+            && title[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            title = title[prefix.len()..].trim_start().to_string();
+        }
+    }
+
+    if let Some(stripped) = strip_site_suffix(&title, site_name) {
+        title = stripped.to_string();
+    }
+
+    if needs_recasing(&title) {
+        title = sentence_case(&title);
+    }
+    title
+}
+
+/// The title without a `{separator}{site name}` suffix, if it has one.
+fn strip_site_suffix<'a>(title: &'a str, site_name: &str) -> Option<&'a str> {
+    let names = site_name_keys(site_name);
+    if names.is_empty() {
+        return None;
+    }
+    SITE_SEPARATORS.iter().find_map(|separator| {
+        let (head, tail) = title.rsplit_once(separator)?;
+        (!head.trim().is_empty() && names.contains(&comparable(tail))).then(|| head.trim_end())
+    })
+}
+
+/// The forms of a site name a title suffix may take: the name itself and, for a host
+/// like `www.towardsdatascience.com`, the label before its top-level domain.
+fn site_name_keys(site_name: &str) -> Vec<String> {
+    let mut keys = vec![comparable(site_name)];
+    let labels: Vec<&str> = site_name
+        .trim()
+        .trim_start_matches("www.")
+        .split('.')
+        .collect();
+    if labels.len() >= 2 && !site_name.contains(' ') {
+        keys.push(comparable(labels[labels.len() - 2]));
+    }
+    keys.retain(|key| !key.is_empty());
+    keys
+}
+
+/// Lowercased letters and digits only, so `Towards Data Science` equals `towardsdatascience`.
+fn comparable(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether a title is shouted or whispered: several words of letters, all in one case.
+fn needs_recasing(title: &str) -> bool {
+    let words = title
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .count();
+    let letters = || title.chars().filter(|c| c.is_alphabetic());
+    words >= 2 && (letters().all(char::is_uppercase) || letters().all(char::is_lowercase))
+}
+
+/// Capitalizes the first word, and the first word after a colon, and lowercases the rest.
+/// Proper nouns can't be recognized in a title written in one case, so they are lowercased too.
+fn sentence_case(title: &str) -> String {
+    let mut capitalize_next = true;
+    title
+        .split(' ')
+        .map(|word| {
+            let lower = word.to_lowercase();
+            let word = if capitalize_next {
+                let mut chars = lower.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => lower,
+                }
+            } else {
+                lower
+            };
+            capitalize_next = word.ends_with(':') || word.ends_with('?') || word.ends_with('.');
+            word
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A last-resort title made from the final segment of a URL's path, for pages that
+/// name themselves nowhere: `https://example.com/blog/machine-learning-intro` gives
+/// `Machine Learning Intro`. Segments without a letter, such as numeric IDs, give none.
+pub fn fallback_title_from_url(url: &Url) -> Option<String> {
+    let segment = url
+        .path_segments()?
+        .rev()
+        .find(|segment| !segment.is_empty())?;
+    let segment = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+    let stem = match segment.rsplit_once('.') {
+        Some((stem, extension))
+            if ["html", "htm", "php", "asp", "aspx", "jsp"]
+                .contains(&extension.to_ascii_lowercase().as_str()) =>
+        {
+            stem
+        }
+        _ => &segment,
+    };
+    if !stem.chars().any(char::is_alphabetic) {
+        return None;
+    }
+
+    let words = stem
+        .split(['-', '_', '+', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>();
+    Some(words.join(" "))
+}
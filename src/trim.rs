@@ -0,0 +1,113 @@
+use crate::extract::ArticleMetadata;
+
+// --- Field Length Limits ---
+
+/// Maximum lengths, in characters, for fields that scraping can blow up, e.g. when a
+/// site serves its JavaScript bundle where the abstract should be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrimConfig {
+    pub max_title: usize,
+    pub max_abstract: usize,
+    pub max_keywords: usize,
+}
+
+impl Default for TrimConfig {
+    fn default() -> Self {
+        Self {
+            max_title: 300,
+            max_abstract: 3000,
+            max_keywords: 500,
+        }
+    }
+}
+
+impl TrimConfig {
+    /// Reads `BIBTEXTER_MAX_TITLE_LENGTH`, `BIBTEXTER_MAX_ABSTRACT_LENGTH` and
+    /// `BIBTEXTER_MAX_KEYWORDS_LENGTH`, keeping the default for any unset or invalid limit.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_title: env_limit("BIBTEXTER_MAX_TITLE_LENGTH").unwrap_or(defaults.max_title),
+            max_abstract: env_limit("BIBTEXTER_MAX_ABSTRACT_LENGTH")
+                .unwrap_or(defaults.max_abstract),
+            max_keywords: env_limit("BIBTEXTER_MAX_KEYWORDS_LENGTH")
+                .unwrap_or(defaults.max_keywords),
+        }
+    }
+}
+
+fn env_limit(name: &str) -> Option<usize> {
+    std::env::var(name).ok()?.trim().parse().ok()
+}
+
+/// Cuts overlong fields down to the configured limits. Text fields are cut at a word
+/// boundary and end in `...`; keywords are dropped whole from the end of the list.
+pub fn trim_bibtex_fields(entry: &mut ArticleMetadata, config: &TrimConfig) {
+    if let Some(title) = truncate_words(&entry.title, config.max_title) {
+        warn_truncated("title", &entry.title, &title);
+        entry.title = title;
+    }
+
+    if let Some(abstract_text) = &entry.abstract_text
+        && let Some(trimmed) = truncate_words(abstract_text, config.max_abstract)
+    {
+        warn_truncated("abstract", abstract_text, &trimmed);
+        entry.abstract_text = Some(trimmed);
+    }
+
+    // Keywords are emitted joined with ", ", so that is the length that counts.
+    let original_count = entry.keywords.len();
+    let mut length = 0;
+    let kept = entry
+        .keywords
+        .iter()
+        .take_while(|keyword| {
+            let separator = if length == 0 { 0 } else { 2 };
+            length += separator + keyword.chars().count();
+            length <= config.max_keywords
+        })
+        .count();
+    if kept < original_count {
+        entry.keywords.truncate(kept);
+        eprintln!(
+            "-> Warning: dropped {} of {} keywords to stay within {} characters.",
+            original_count - kept,
+            original_count,
+            config.max_keywords
+        );
+    }
+}
+
+/// Shortens `text` to at most `max` characters including the trailing `...`,
+/// or returns `None` if it already fits.
+pub fn truncate_words(text: &str, max: usize) -> Option<String> {
+    if text.chars().count() <= max {
+        return None;
+    }
+
+    let budget = max.saturating_sub(3);
+    let cut = text
+        .char_indices()
+        .nth(budget)
+        .map_or(text.len(), |(index, _)| index);
+    let head = &text[..cut];
+    // Back up to the last whitespace unless the cut already fell between words.
+    let head = if text[cut..].starts_with(char::is_whitespace) {
+        head
+    } else {
+        head.rfind(char::is_whitespace)
+            .map_or(head, |index| &head[..index])
+    };
+    let head = head.trim_end_matches(|c: char| c.is_whitespace() || c == ',' || c == ';');
+
+    Some(format!("{}...", head))
+}
+
+fn warn_truncated(field: &str, original: &str, trimmed: &str) {
+    eprintln!(
+        "-> Warning: truncated {} from {} to {} characters.",
+        field,
+        original.chars().count(),
+        trimmed.chars().count()
+    );
+}
@@ -0,0 +1,49 @@
+use serde::Deserialize;
+
+use crate::http::HttpClient;
+
+// Structs for parsing the Wayback Machine availability API.
+#[derive(Deserialize, Debug)]
+struct WaybackAvailability {
+    #[serde(default)]
+    archived_snapshots: WaybackSnapshots,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct WaybackSnapshots {
+    closest: Option<WaybackSnapshot>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WaybackSnapshot {
+    available: bool,
+    timestamp: String,
+}
+
+/// Whether unreachable pages should be retried via the Wayback Machine.
+/// Off by default because it costs two extra round trips.
+pub fn wayback_enabled() -> bool {
+    std::env::var("BIBTEXTER_USE_WAYBACK").is_ok_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+/// Asks the Wayback Machine for its latest snapshot of `url`, returning the
+/// `https://web.archive.org/web/{timestamp}/{url}` address to fetch instead.
+pub async fn find_snapshot(client: &impl HttpClient, url: &str) -> Option<String> {
+    let encoded: String = url::form_urlencoded::byte_serialize(url.as_bytes()).collect();
+    let api_url = format!("https://archive.org/wayback/available?url={}", encoded);
+
+    let res = client.get(&api_url, Some("application/json")).await.ok()?;
+    if !res.status.is_success() {
+        return None;
+    }
+
+    let availability = serde_json::from_str::<WaybackAvailability>(&res.body).ok()?;
+    let snapshot = availability
+        .archived_snapshots
+        .closest
+        .filter(|snapshot| snapshot.available)?;
+    Some(format!(
+        "https://web.archive.org/web/{}/{}",
+        snapshot.timestamp, url
+    ))
+}
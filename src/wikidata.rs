@@ -0,0 +1,290 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::authors::normalize_authors;
+use crate::bibtex::{BibtexEntryType, OutputMode};
+use crate::dates::parse_year;
+use crate::error::AppError;
+use crate::extract::{ArticleMetadata, ExtractionStrategy, format_bibtex};
+use crate::http::HttpClient;
+use crate::sites::fetch_json;
+
+// --- Wikidata ---
+
+// The properties read from an item, e.g. `P1476` is its title.
+const TITLE: &str = "P1476";
+const AUTHOR: &str = "P50";
+const AUTHOR_NAME: &str = "P2093";
+const PUBLICATION_DATE: &str = "P577";
+const PUBLISHED_IN: &str = "P1433";
+const VOLUME: &str = "P478";
+const ISSUE: &str = "P433";
+const PAGES: &str = "P304";
+const DOI: &str = "P356";
+const INSTANCE_OF: &str = "P31";
+// The qualifier giving an author's place in the author list.
+const SERIES_ORDINAL: &str = "P1545";
+
+// Classes whose instances are cited as books: book, version or edition, written work.
+const BOOK_CLASSES: &[&str] = &["Q571", "Q3331189", "Q47461344"];
+
+// Structs for parsing `Special:EntityData/{id}.json` and `wbgetentities`.
+#[derive(Deserialize, Debug)]
+struct EntityData {
+    entities: HashMap<String, Entity>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Entity {
+    #[serde(default)]
+    labels: HashMap<String, Label>,
+    #[serde(default)]
+    claims: HashMap<String, Vec<Claim>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Label {
+    value: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Claim {
+    mainsnak: Snak,
+    #[serde(default)]
+    qualifiers: HashMap<String, Vec<Snak>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Snak {
+    // Absent when the claim is "unknown value" or "no value".
+    datavalue: Option<DataValue>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DataValue {
+    value: serde_json::Value,
+}
+
+impl Snak {
+    /// The value of a string or external identifier property.
+    fn string(&self) -> Option<&str> {
+        self.datavalue.as_ref()?.value.as_str()
+    }
+
+    /// The item a property points at, e.g. `Q42`.
+    fn item_id(&self) -> Option<&str> {
+        self.datavalue.as_ref()?.value.get("id")?.as_str()
+    }
+
+    /// The text and language of a monolingual text property.
+    fn text(&self) -> Option<(&str, &str)> {
+        let value = &self.datavalue.as_ref()?.value;
+        Some((
+            value.get("text")?.as_str()?,
+            value.get("language")?.as_str()?,
+        ))
+    }
+
+    /// The timestamp of a time property, e.g. `+2019-08-26T00:00:00Z`.
+    fn time(&self) -> Option<&str> {
+        self.datavalue.as_ref()?.value.get("time")?.as_str()
+    }
+}
+
+impl Entity {
+    fn claims(&self, property: &str) -> impl Iterator<Item = &Claim> {
+        self.claims.get(property).into_iter().flatten()
+    }
+
+    fn first_string(&self, property: &str) -> Option<String> {
+        self.claims(property)
+            .find_map(|claim| claim.mainsnak.string())
+            .map(str::to_string)
+    }
+
+    /// The English label, or any label when there is no English one.
+    fn label(&self) -> Option<String> {
+        self.labels
+            .get("en")
+            .or_else(|| self.labels.values().next())
+            .map(|label| label.value.clone())
+    }
+}
+
+/// Reads a Wikidata item ID written as `Q12345`, `wd:Q12345` or an item's URL, e.g.
+/// `https://www.wikidata.org/wiki/Q12345`.
+pub fn parse_entity_id(input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('/');
+    let id = input
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(input)
+        .to_uppercase();
+    let digits = id.strip_prefix('Q')?;
+    (!digits.is_empty() && !digits.starts_with('0') && digits.chars().all(|c| c.is_ascii_digit()))
+        .then_some(id)
+}
+
+/// Looks an item up in Wikidata and maps its bibliographic claims to BibTeX fields.
+/// Authors and the journal are items of their own, whose English labels are fetched
+/// together afterwards.
+pub async fn lookup_wikidata(
+    client: &impl HttpClient,
+    id: &str,
+) -> Result<ArticleMetadata, AppError> {
+    let api_url = format!(
+        "https://www.wikidata.org/wiki/Special:EntityData/{}.json",
+        id
+    );
+    let data: EntityData = fetch_json(client, &api_url).await?;
+    // Merged items are served under the ID they were merged into.
+    let Some(entity) = data.entities.into_values().next() else {
+        return Err(AppError::ExtractionError(format!(
+            "Wikidata has no item {}",
+            id
+        )));
+    };
+
+    // Authors with items of their own (P50) and those known by name alone (P2093)
+    // share one numbering.
+    let mut authors: Vec<(u32, AuthorRef)> = entity
+        .claims(AUTHOR)
+        .filter_map(|claim| {
+            let item = claim.mainsnak.item_id()?;
+            Some((ordinal(claim), AuthorRef::Item(item.to_string())))
+        })
+        .chain(entity.claims(AUTHOR_NAME).filter_map(|claim| {
+            let name = claim.mainsnak.string()?;
+            Some((ordinal(claim), AuthorRef::Name(name.to_string())))
+        }))
+        .collect();
+    authors.sort_by_key(|(ordinal, _)| *ordinal);
+    let journal_id = entity
+        .claims(PUBLISHED_IN)
+        .find_map(|claim| claim.mainsnak.item_id())
+        .map(str::to_string);
+
+    let mut linked_ids: Vec<&str> = authors
+        .iter()
+        .filter_map(|(_, author)| match author {
+            AuthorRef::Item(id) => Some(id.as_str()),
+            AuthorRef::Name(_) => None,
+        })
+        .chain(journal_id.as_deref())
+        .collect();
+    linked_ids.sort_unstable();
+    linked_ids.dedup();
+    let labels = fetch_labels(client, &linked_ids).await?;
+
+    let names = authors
+        .into_iter()
+        .filter_map(|(_, author)| match author {
+            AuthorRef::Item(id) => labels.get(&id).cloned(),
+            AuthorRef::Name(name) => Some(name),
+        })
+        .collect();
+    let title = english_text(
+        entity
+            .claims(TITLE)
+            .filter_map(|claim| claim.mainsnak.text()),
+    )
+    .or_else(|| entity.label())
+    .unwrap_or_default();
+    let journal = journal_id.and_then(|id| labels.get(&id).cloned());
+    let is_book = entity
+        .claims(INSTANCE_OF)
+        .filter_map(|claim| claim.mainsnak.item_id())
+        .any(|class| BOOK_CLASSES.contains(&class));
+    let entry_type = if is_book {
+        BibtexEntryType::Book
+    } else if journal.is_some() {
+        BibtexEntryType::Article
+    } else {
+        BibtexEntryType::Misc
+    };
+
+    Ok(ArticleMetadata {
+        strategy: ExtractionStrategy::SiteApi,
+        entry_type,
+        title,
+        author: normalize_authors(names).join(" and "),
+        year: entity
+            .claims(PUBLICATION_DATE)
+            .find_map(|claim| claim.mainsnak.time())
+            .and_then(parse_year)
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        journal,
+        volume: entity.first_string(VOLUME),
+        number: entity.first_string(ISSUE),
+        page_range: entity
+            .first_string(PAGES)
+            .map(|pages| pages.replacen('-', "--", 1)),
+        doi: entity.first_string(DOI),
+        ..Default::default()
+    })
+}
+
+/// Generates an entry for a Wikidata item, given its ID in any common form.
+pub async fn bibtex_from_wikidata(
+    client: &impl HttpClient,
+    input: &str,
+    mode: OutputMode,
+) -> Result<String, AppError> {
+    let id = parse_entity_id(input).ok_or_else(|| {
+        AppError::InvalidInput(format!("'{}' is not a Wikidata item ID.", input.trim()))
+    })?;
+    let metadata = lookup_wikidata(client, &id).await?;
+    let url = format!("https://www.wikidata.org/wiki/{}", id);
+    format_bibtex(metadata, Some(&url), mode)
+}
+
+enum AuthorRef {
+    Item(String),
+    Name(String),
+}
+
+// Authors without a series ordinal go after those with one, in the order listed.
+fn ordinal(claim: &Claim) -> u32 {
+    claim
+        .qualifiers
+        .get(SERIES_ORDINAL)
+        .into_iter()
+        .flatten()
+        .find_map(|snak| snak.string()?.parse().ok())
+        .unwrap_or(u32::MAX)
+}
+
+// The English text among a property's values, or the first when none is English.
+fn english_text<'a>(mut texts: impl Iterator<Item = (&'a str, &'a str)>) -> Option<String> {
+    let first = texts.next()?;
+    let english = std::iter::once(first)
+        .chain(texts)
+        .find(|(_, language)| *language == "en" || language.starts_with("en-"));
+    Some(english.unwrap_or(first).0.to_string())
+}
+
+/// The labels of the given items, English where there is one.
+async fn fetch_labels(
+    client: &impl HttpClient,
+    ids: &[&str],
+) -> Result<HashMap<String, String>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    // `wbgetentities` takes at most 50 IDs at a time.
+    let mut labels = HashMap::new();
+    for chunk in ids.chunks(50) {
+        let api_url = format!(
+            "https://www.wikidata.org/w/api.php?action=wbgetentities&ids={}&props=labels&languages=en&languagefallback=1&format=json",
+            chunk.join("%7C")
+        );
+        let data: EntityData = fetch_json(client, &api_url).await?;
+        labels.extend(
+            data.entities
+                .into_iter()
+                .filter_map(|(id, entity)| Some((id, entity.label()?))),
+        );
+    }
+    Ok(labels)
+}
@@ -0,0 +1,110 @@
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, HeaderValue};
+use bibtexter::access_log::{AccessLogEntry, LogFormat, TrustedProxies, client_ip};
+use chrono::{Local, TimeZone};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+fn entry() -> AccessLogEntry {
+    AccessLogEntry {
+        timestamp: Local.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        client_ip: Some("203.0.113.7".into()),
+        method: "GET".into(),
+        path: "/api/bibtex".into(),
+        query: Some("url=https%3A%2F%2Farxiv.org%2Fabs%2F1706.03762".into()),
+        protocol: "HTTP/1.1".into(),
+        status: 200,
+        size: Some(512),
+        duration: Duration::from_micros(1500),
+        referer: None,
+        user_agent: Some("curl/8.0".into()),
+    }
+}
+
+#[test]
+fn log_format_is_parsed_strictly() {
+    assert_eq!("json".parse(), Ok(LogFormat::Json));
+    assert_eq!("combined".parse(), Ok(LogFormat::Combined));
+    assert!("apache".parse::<LogFormat>().is_err());
+}
+
+#[test]
+fn combined_line_follows_apache_layout() {
+    let line = entry().to_combined();
+
+    assert!(
+        line.starts_with("203.0.113.7 - - [15/Jan/2025:10:30:00 "),
+        "{}",
+        line
+    );
+    assert!(
+        line.ends_with(
+            "] \"GET /api/bibtex?url=https%3A%2F%2Farxiv.org%2Fabs%2F1706.03762 HTTP/1.1\" 200 512 \"-\" \"curl/8.0\" 1500"
+        ),
+        "{}",
+        line
+    );
+}
+
+#[test]
+fn json_line_has_every_field() {
+    let line = entry().to_json();
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+    assert!(!line.contains('\n'));
+    assert_eq!(json["client_ip"], "203.0.113.7");
+    assert_eq!(json["method"], "GET");
+    assert_eq!(json["path"], "/api/bibtex");
+    assert_eq!(json["status"], 200);
+    assert_eq!(json["size"], 512);
+    assert_eq!(json["duration_ms"], 1.5);
+    assert_eq!(json["referer"], serde_json::Value::Null);
+    assert_eq!(json["user_agent"], "curl/8.0");
+}
+
+#[test]
+fn client_ip_ignores_forwarded_for_from_untrusted_peers() {
+    let peer = ConnectInfo(SocketAddr::from(([10, 0, 0, 2], 51000)));
+    let untrusted = TrustedProxies::default();
+    let mut headers = HeaderMap::new();
+
+    assert_eq!(
+        client_ip(&headers, Some(&peer), &untrusted).as_deref(),
+        Some("10.0.0.2")
+    );
+    assert_eq!(client_ip(&headers, None, &untrusted), None);
+
+    headers.insert(
+        "x-forwarded-for",
+        HeaderValue::from_static("198.51.100.4, 10.0.0.1"),
+    );
+    assert_eq!(
+        client_ip(&headers, Some(&peer), &untrusted).as_deref(),
+        Some("10.0.0.2")
+    );
+}
+
+#[test]
+fn client_ip_takes_the_rightmost_untrusted_hop_behind_trusted_proxies() {
+    let peer = ConnectInfo(SocketAddr::from(([10, 0, 0, 2], 51000)));
+    let trusted = TrustedProxies::parse("10.0.0.0/8, fdaa::/16, not-an-address");
+    let mut headers = HeaderMap::new();
+
+    // The client wrote the first hop itself; the proxies added the others.
+    headers.insert(
+        "x-forwarded-for",
+        HeaderValue::from_static("1.2.3.4, 198.51.100.4, 10.0.0.1"),
+    );
+    assert_eq!(
+        client_ip(&headers, Some(&peer), &trusted).as_deref(),
+        Some("198.51.100.4")
+    );
+
+    headers.remove("x-forwarded-for");
+    assert_eq!(
+        client_ip(&headers, Some(&peer), &trusted).as_deref(),
+        Some("10.0.0.2")
+    );
+    assert!(trusted.contains("fdaa::1".parse().unwrap()));
+    assert!(!trusted.contains("11.0.0.1".parse().unwrap()));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use bibtexter::extract::{fetch_and_generate_bibtex, fetch_metadata};
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::ads::bibcode;
+use common::{field, fixture};
+
+const PAGE: &str = "https://ui.adsabs.harvard.edu/abs/2016PhRvL.116f1102A/abstract";
+const EXPORT: &str = "https://api.adsabs.harvard.edu/v1/export/bibtex/2016PhRvL.116f1102A";
+const SEARCH: &str = "https://api.adsabs.harvard.edu/v1/search/query?q=bibcode:2016PhRvL.116f1102A&fl=title,author,year,pub,volume,issue,page,doi,identifier,abstract,doctype";
+
+fn set_token() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("ADS_API_TOKEN", "test-token") };
+}
+
+#[test]
+fn finds_bibcodes_in_ads_urls() {
+    let id = |url: &str| bibcode(&url::Url::parse(url).unwrap());
+
+    assert_eq!(id(PAGE).as_deref(), Some("2016PhRvL.116f1102A"));
+    assert_eq!(
+        id("https://ui.adsabs.harvard.edu/abs/2004A%26A...424..727K").as_deref(),
+        Some("2004A&A...424..727K")
+    );
+    assert_eq!(
+        id("https://ui.adsabs.harvard.edu/search/q=black%20holes"),
+        None
+    );
+    assert_eq!(id("https://ui.adsabs.harvard.edu/abs/short"), None);
+}
+
+#[tokio::test]
+async fn export_api_bibtex_is_preferred() {
+    set_token();
+    let client = FixtureClient::new()
+        .with_fixture(EXPORT, fixture("ads_export.json"))
+        .with_fixture(SEARCH, fixture("ads_search.json"));
+
+    let bibtex = fetch_and_generate_bibtex(&client, PAGE).await.unwrap();
+
+    assert!(
+        bibtex.starts_with("@ARTICLE{2016PhRvL.116f1102A,"),
+        "{}",
+        bibtex
+    );
+    assert!(bibtex.contains("doi = {10.1103/PhysRevLett.116.061102}"));
+}
+
+#[tokio::test]
+async fn search_api_fills_in_when_export_fails() {
+    set_token();
+    let client = FixtureClient::new().with_fixture(SEARCH, fixture("ads_search.json"));
+
+    let bibtex = fetch_and_generate_bibtex(&client, PAGE).await.unwrap();
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Observation of Gravitational Waves from a Binary Black Hole Merger")
+    );
+    assert_eq!(field(&bibtex, "journal"), Some("Physical Review Letters"));
+    assert_eq!(field(&bibtex, "volume"), Some("116"));
+    assert_eq!(field(&bibtex, "number"), Some("6"));
+    assert_eq!(field(&bibtex, "pages"), Some("061102"));
+    assert_eq!(field(&bibtex, "year"), Some("2016"));
+    assert_eq!(
+        field(&bibtex, "doi"),
+        Some("10.1103/PhysRevLett.116.061102")
+    );
+    assert_eq!(field(&bibtex, "eprint"), Some("1602.03837"));
+
+    let metadata = fetch_metadata(&client, PAGE).await.unwrap();
+    assert_eq!(
+        metadata.author,
+        "Abbott, B. P. and Abbott, R. and Abbott, T. D."
+    );
+}
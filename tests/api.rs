@@ -0,0 +1,126 @@
+use bibtexter::api::{enabled_features, formats_handler, schema_handler, version_handler};
+use bibtexter::bibtex::OutputMode;
+use bibtexter::extract::{ArticleMetadata, ExtractionStrategy};
+use bibtexter::routes::BibtexQuery;
+
+#[tokio::test]
+async fn formats_lists_entry_types_and_strategies() {
+    let formats = serde_json::to_value(formats_handler().await.0).unwrap();
+
+    assert_eq!(formats["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        formats["output_formats"],
+        serde_json::json!(["bibtex", "biblatex", "biblatex_arxiv", "ris"])
+    );
+
+    let article = formats["entry_types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == "article")
+        .unwrap();
+    assert_eq!(
+        article["required_fields"],
+        serde_json::json!(["author", "title", "journal", "year"])
+    );
+
+    let online = formats["entry_types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == "online")
+        .unwrap();
+    assert_eq!(
+        online["required_fields"],
+        serde_json::json!(["title", "url"])
+    );
+
+    let strategies: Vec<_> = formats["strategies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|strategy| strategy["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(strategies, ["doi", "site_api", "json_ld", "meta_tags"]);
+}
+
+#[tokio::test]
+async fn schema_describes_formats_response() {
+    let schema = schema_handler().await.0;
+    let formats = serde_json::to_value(formats_handler().await.0).unwrap();
+
+    let formats_schema = &schema["$defs"]["FormatsResponse"];
+    for key in formats_schema["required"].as_array().unwrap() {
+        assert!(formats.get(key.as_str().unwrap()).is_some());
+    }
+}
+
+#[tokio::test]
+async fn schema_matches_query_fields_and_enums() {
+    let schema = schema_handler().await.0;
+    let query_schema = &schema["$defs"]["GetBibtexQuery"]["properties"];
+
+    let modes: Vec<&str> = OutputMode::ALL.iter().map(OutputMode::as_str).collect();
+    assert_eq!(query_schema["mode"]["enum"], serde_json::json!(modes));
+    let strategies: Vec<&str> = ExtractionStrategy::ALL
+        .iter()
+        .map(ExtractionStrategy::as_str)
+        .collect();
+    assert_eq!(
+        schema["$defs"]["PreviewResponse"]["properties"]["strategy"]["enum"],
+        serde_json::json!(strategies)
+    );
+
+    // Every field of the query is described, with the default it actually has.
+    let query: BibtexQuery =
+        serde_json::from_value(serde_json::json!({ "url": "https://example.com" })).unwrap();
+    let query = serde_json::to_value(query).unwrap();
+    let mut fields: Vec<&String> = query.as_object().unwrap().keys().collect();
+    let mut described: Vec<&String> = query_schema.as_object().unwrap().keys().collect();
+    fields.sort();
+    described.sort();
+    assert_eq!(fields, described);
+    for (field, property) in query_schema.as_object().unwrap() {
+        if let Some(default) = property.get("default") {
+            assert_eq!(&query[field], default, "default of {}", field);
+        }
+    }
+}
+
+#[tokio::test]
+async fn schema_describes_every_metadata_field() {
+    let schema = schema_handler().await.0;
+    let described = schema["$defs"]["PreviewResponse"]["properties"]["fields"]["properties"]
+        .as_object()
+        .unwrap();
+    let metadata = serde_json::to_value(ArticleMetadata::default()).unwrap();
+    let metadata = metadata.as_object().unwrap();
+
+    for field in metadata.keys() {
+        assert!(
+            described.contains_key(field),
+            "{} is not in the schema",
+            field
+        );
+    }
+    assert_eq!(described.len(), metadata.len());
+}
+
+#[tokio::test]
+async fn version_lists_only_enabled_features() {
+    let version = serde_json::to_value(version_handler().await.0).unwrap();
+
+    assert_eq!(version["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!version["git_commit"].as_str().unwrap().is_empty());
+    assert_eq!(
+        version["build_date"].as_str().unwrap().len(),
+        "2025-01-15".len()
+    );
+    let features = version["features"].as_array().unwrap();
+    assert!(features.contains(&serde_json::json!("doi_lookup")));
+    assert!(!features.contains(&serde_json::json!("wayback")));
+
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_USE_WAYBACK", "true") };
+    assert!(enabled_features().contains(&"wayback"));
+}
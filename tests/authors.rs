@@ -0,0 +1,56 @@
+use bibtexter::authors::{last_first, normalize_authors};
+
+fn names(authors: &[&str]) -> Vec<String> {
+    authors.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn exact_and_case_duplicates_are_dropped() {
+    assert_eq!(
+        normalize_authors(names(&["Jane Doe", "jane doe", "JANE DOE", "John Roe"])),
+        ["Jane Doe", "John Roe"]
+    );
+}
+
+#[test]
+fn last_first_order_matches_first_last() {
+    assert_eq!(
+        normalize_authors(names(&["Doe, Jane", "Jane Doe"])),
+        ["Doe, Jane"]
+    );
+}
+
+#[test]
+fn full_given_name_beats_initial() {
+    assert_eq!(
+        normalize_authors(names(&["J. Doe", "Maria Rossi", "Jane Doe"])),
+        ["Jane Doe", "Maria Rossi"]
+    );
+    assert_eq!(
+        normalize_authors(names(&["Jane Doe", "Doe, J."])),
+        ["Jane Doe"]
+    );
+}
+
+#[test]
+fn different_given_names_are_kept() {
+    assert_eq!(
+        normalize_authors(names(&["Jane Doe", "John Doe", "Jane Roe"])),
+        ["Jane Doe", "John Doe", "Jane Roe"]
+    );
+}
+
+#[test]
+fn blank_entries_are_removed() {
+    assert_eq!(
+        normalize_authors(names(&["", "  ", "Jane Doe"])),
+        ["Jane Doe"]
+    );
+}
+
+#[test]
+fn last_first_reorders_plain_names() {
+    assert_eq!(last_first("Jane Q. Doe"), "Doe, Jane Q.");
+    assert_eq!(last_first("Doe, Jane"), "Doe, Jane");
+    assert_eq!(last_first("Plato"), "Plato");
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use bibtexter::bib_json::bibtex_to_json;
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use common::{field, fixture};
+
+#[test]
+fn expands_string_macros_and_concatenation() {
+    let input = r#"
+@string{pacmpl = "Proc. ACM Program. Lang."}
+@STRING{ acm = {ACM} }
+
+@article{jung2017,
+  title = {{RustBelt}: Securing the Foundations of the {Rust} Programming Language},
+  journal = pacmpl,
+  publisher = acm # " Press",
+  month = jan,
+  year = 2017
+}
+"#;
+
+    let entries = bibtex_to_json(input).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.entry_type, "article");
+    assert_eq!(entry.key, "jung2017");
+    assert_eq!(
+        entry.fields["title"],
+        "{RustBelt}: Securing the Foundations of the {Rust} Programming Language"
+    );
+    assert_eq!(entry.fields["journal"], "Proc. ACM Program. Lang.");
+    assert_eq!(entry.fields["publisher"], "ACM Press");
+    assert_eq!(entry.fields["month"], "January");
+    assert_eq!(entry.fields["year"], "2017");
+}
+
+#[test]
+fn converts_every_entry_in_order() {
+    let input = "@book{a, title = {A}}\n% a comment\n@inproceedings(b, title = \"B\")\n@misc{c,}";
+
+    let keys: Vec<_> = bibtex_to_json(input)
+        .unwrap()
+        .into_iter()
+        .map(|entry| (entry.entry_type, entry.key))
+        .collect();
+
+    assert_eq!(
+        keys,
+        [
+            ("book".to_string(), "a".to_string()),
+            ("inproceedings".to_string(), "b".to_string()),
+            ("misc".to_string(), "c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn input_without_entries_is_rejected() {
+    assert!(bibtex_to_json("% nothing here\n").is_err());
+}
+
+#[tokio::test]
+async fn generated_entries_round_trip() {
+    let url = "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html";
+    let client = FixtureClient::new().with_fixture(url, fixture("blog.html"));
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+
+    let entries = bibtex_to_json(&bibtex).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    for name in ["title", "author", "year", "howpublished", "note"] {
+        assert_eq!(
+            entries[0].fields.get(name).map(String::as_str),
+            field(&bibtex, name),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn plain_text_strips_latex_but_not_urls() {
+    let input = r#"
+@article{h2o,
+  title = {Splitting H$_2$O with {\'E}cole \emph{catalysts}},
+  url = {https://example.org/~lab/paper_1},
+  doi = {10.1000/a_b}
+}
+"#;
+
+    let entry = bibtex_to_json(input).unwrap().remove(0).into_plain_text();
+
+    assert_eq!(entry.fields["title"], "Splitting H₂O with École catalysts");
+    assert_eq!(entry.fields["url"], "https://example.org/~lab/paper_1");
+    assert_eq!(entry.fields["doi"], "10.1000/a_b");
+}
@@ -0,0 +1,129 @@
+use bibtexter::bibtex::{
+    BibtexEntry, BibtexEntryType, FieldFilter, ValidationError, compact_bibtex,
+    filter_bibtex_fields, serialize_bibtex,
+};
+
+#[test]
+fn builds_fields_in_a_fixed_order() {
+    let bibtex = BibtexEntry::new(BibtexEntryType::Article, "Doe2025Title")
+        .year(2025)
+        .journal("Journal of Examples")
+        .author("Doe, Jane")
+        .title("A Title")
+        .field("custom", "last")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        bibtex,
+        "@article{Doe2025Title,\n  title = {A Title},\n  author = {Doe, Jane},\n  journal = {Journal of Examples},\n  year = {2025},\n  custom = {last},\n}"
+    );
+}
+
+#[test]
+fn later_values_replace_earlier_ones_and_empty_values_are_skipped() {
+    let entry = BibtexEntry::new(BibtexEntryType::Misc, "key")
+        .title("Draft")
+        .field("TITLE", "Final")
+        .author("")
+        .optional_field("note", None::<String>)
+        .comment("Generated from a preprint");
+
+    assert_eq!(entry.get("title"), Some("Final"));
+    assert_eq!(entry.get("author"), None);
+    assert_eq!(
+        entry.build().unwrap(),
+        "% Generated from a preprint\n@misc{key,\n  title = {Final},\n}"
+    );
+}
+
+#[test]
+fn build_requires_a_key_and_a_title() {
+    assert_eq!(
+        BibtexEntry::new(BibtexEntryType::Book, "")
+            .title("T")
+            .build(),
+        Err(ValidationError::MissingCitationKey)
+    );
+    assert_eq!(
+        BibtexEntry::new(BibtexEntryType::Book, "key")
+            .author("Doe, Jane")
+            .build(),
+        Err(ValidationError::MissingField {
+            entry_type: BibtexEntryType::Book,
+            field: "title",
+        })
+    );
+}
+
+#[test]
+fn reports_missing_required_fields() {
+    let entry = BibtexEntry::new(BibtexEntryType::Article, "key")
+        .title("T")
+        .author("Doe, Jane");
+
+    assert_eq!(entry.missing_fields(), vec!["journal", "year"]);
+    assert!(entry.build().is_ok());
+}
+
+#[test]
+fn compact_serialization_fits_on_one_line() {
+    let entry = BibtexEntry::new(BibtexEntryType::Misc, "Doe2025Title")
+        .year(2025)
+        .title("A Title, {Braced}")
+        .author("Doe, Jane");
+
+    assert_eq!(
+        serialize_bibtex(&entry, false),
+        "@misc{Doe2025Title,title={A Title, {Braced}},author={Doe, Jane},year={2025}}"
+    );
+    assert_eq!(serialize_bibtex(&entry, true), entry.build().unwrap());
+}
+
+#[test]
+fn compacts_finished_entries() {
+    let ours =
+        "% NOTE: no authors\n@misc{Unknown2025Post,\n  title = {A Post},\n  year = {2025},\n}";
+    assert_eq!(
+        compact_bibtex(ours),
+        "% NOTE: no authors\n@misc{Unknown2025Post,title={A Post},year={2025}}"
+    );
+
+    let negotiated =
+        " @inproceedings{Jung_2017, title={RustBelt}, DOI={10.1145/3158154}, year={2017}}";
+    assert_eq!(
+        compact_bibtex(negotiated),
+        "@inproceedings{Jung_2017,title={RustBelt},year={2017},doi={10.1145/3158154}}"
+    );
+
+    assert_eq!(
+        compact_bibtex("@phdthesis{Roe2020,\n  title = {Thesis},\n}"),
+        "@phdthesis{Roe2020, title = {Thesis}, }"
+    );
+}
+
+#[test]
+fn filters_fields_of_finished_entries() {
+    let bibtex = "@article{Doe2025Title,\n  title = {A Title},\n  author = {Doe, Jane},\n  journal = {J. Tests},\n  year = {2025},\n  abstract = {Long text.},\n  keywords = {a, b},\n}";
+
+    let excluded =
+        filter_bibtex_fields(bibtex, &FieldFilter::new(None, Some("abstract, Keywords")));
+    assert_eq!(
+        excluded,
+        "@article{Doe2025Title,\n  title = {A Title},\n  author = {Doe, Jane},\n  journal = {J. Tests},\n  year = {2025},\n}"
+    );
+
+    let included = filter_bibtex_fields(
+        bibtex,
+        &FieldFilter::new(Some("title,author,year,doi"), Some("author")),
+    );
+    assert_eq!(
+        included,
+        "% WARNING: excluded fields required by @article: author, journal\n@article{Doe2025Title,\n  title = {A Title},\n  year = {2025},\n}"
+    );
+
+    assert_eq!(
+        filter_bibtex_fields(bibtex, &FieldFilter::default()),
+        bibtex
+    );
+}
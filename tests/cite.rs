@@ -0,0 +1,83 @@
+use bibtexter::bibtex::BibtexEntryType;
+use bibtexter::cite::{CitationStyle, format_citation};
+use bibtexter::extract::ArticleMetadata;
+
+fn article() -> ArticleMetadata {
+    ArticleMetadata {
+        entry_type: BibtexEntryType::Article,
+        title: "{RustBelt}: Securing the Foundations of the {Rust} Programming Language".into(),
+        author: "Jung, Ralf and Jourdan, Jacques-Henri and Krebbers, Robbert and Dreyer, Derek"
+            .into(),
+        year: "2017".into(),
+        journal: Some("Proceedings of the ACM on Programming Languages".into()),
+        volume: Some("2".into()),
+        number: Some("POPL".into()),
+        page_range: Some("1--34".into()),
+        doi: Some("10.1145/3158154".into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn formats_a_journal_article_in_each_style() {
+    let metadata = article();
+    let cite = |style| format_citation(&metadata, None, style);
+
+    assert_eq!(
+        cite(CitationStyle::Apa),
+        "Jung, R., Jourdan, J.-H., Krebbers, R., & Dreyer, D. (2017). RustBelt: Securing the Foundations of the Rust Programming Language. Proceedings of the ACM on Programming Languages, 2(POPL), 1–34. https://doi.org/10.1145/3158154"
+    );
+    assert_eq!(
+        cite(CitationStyle::Mla),
+        "Jung, Ralf, et al. “RustBelt: Securing the Foundations of the Rust Programming Language.” Proceedings of the ACM on Programming Languages, vol. 2, no. POPL, 2017, pp. 1-34. https://doi.org/10.1145/3158154."
+    );
+    assert_eq!(
+        cite(CitationStyle::Chicago),
+        "Jung, Ralf, Jacques-Henri Jourdan, Robbert Krebbers, and Derek Dreyer. “RustBelt: Securing the Foundations of the Rust Programming Language.” Proceedings of the ACM on Programming Languages 2, no. POPL (2017): 1–34. https://doi.org/10.1145/3158154."
+    );
+    assert_eq!(
+        cite(CitationStyle::Ieee),
+        "R. Jung, J.-H. Jourdan, R. Krebbers, and D. Dreyer, “RustBelt: Securing the Foundations of the Rust Programming Language,” Proceedings of the ACM on Programming Languages, vol. 2, no. POPL, pp. 1–34, 2017, doi: 10.1145/3158154."
+    );
+}
+
+#[test]
+fn formats_a_web_page_without_a_date() {
+    let metadata = ArticleMetadata {
+        title: "Announcing Rust 1.80.0".into(),
+        author: "Jane Doe".into(),
+        publisher: "Rust Blog".into(),
+        ..Default::default()
+    };
+    let url = Some("https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html");
+
+    assert_eq!(
+        format_citation(&metadata, url, CitationStyle::Apa),
+        "Doe, J. (n.d.). Announcing Rust 1.80.0. Rust Blog. https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html"
+    );
+    assert_eq!(
+        format_citation(&metadata, url, CitationStyle::Ieee),
+        "J. Doe, “Announcing Rust 1.80.0,” Rust Blog. [Online]. Available: https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html"
+    );
+}
+
+#[test]
+fn organizations_are_not_split_into_names() {
+    let metadata = ArticleMetadata {
+        entry_type: BibtexEntryType::Book,
+        title: "World Health Statistics 2023".into(),
+        author: "{World Health Organization}".into(),
+        year: "2023".into(),
+        publisher: "WHO Press".into(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        format_citation(&metadata, None, CitationStyle::Chicago),
+        "World Health Organization. World Health Statistics 2023. WHO Press, 2023."
+    );
+    assert_eq!(
+        format_citation(&metadata, None, CitationStyle::Mla),
+        "World Health Organization. World Health Statistics 2023. WHO Press, 2023."
+    );
+}
@@ -0,0 +1,80 @@
+use bibtexter::clean::{clean_metadata_field, clean_metadata_fields, strip_bibtex_duplicates};
+use bibtexter::extract::ArticleMetadata;
+
+#[test]
+fn decodes_entities_and_strips_tags() {
+    assert_eq!(
+        clean_metadata_field("Rock &amp; Roll: Shannon&#8217;s <em>Theory</em>"),
+        "Rock & Roll: Shannon’s Theory"
+    );
+    assert_eq!(
+        clean_metadata_field("<p>First line<br/>\n   second <i class=\"x\">line</i></p>"),
+        "First line second line"
+    );
+}
+
+#[test]
+fn comparisons_are_not_mistaken_for_tags() {
+    assert_eq!(
+        clean_metadata_field("Bounds for a &lt; b and c > d"),
+        "Bounds for a < b and c > d"
+    );
+}
+
+#[test]
+fn cleans_text_fields_but_not_identifiers() {
+    let mut entry = ArticleMetadata {
+        title: "Deep <b>Learning</b>".to_string(),
+        abstract_text: Some("We  show &quot;this&quot;.".to_string()),
+        keywords: vec!["<i>AI</i>".to_string(), "<br>".to_string()],
+        pdf: Some("https://example.com/a.pdf?x=1&amp;y=2".to_string()),
+        ..Default::default()
+    };
+
+    clean_metadata_fields(&mut entry);
+
+    assert_eq!(entry.title, "Deep Learning");
+    assert_eq!(entry.abstract_text.as_deref(), Some("We show \"this\"."));
+    assert_eq!(entry.keywords, vec!["AI"]);
+    assert_eq!(
+        entry.pdf.as_deref(),
+        Some("https://example.com/a.pdf?x=1&amp;y=2")
+    );
+}
+
+#[test]
+fn strips_values_repeated_across_strategies() {
+    let mut entry = ArticleMetadata {
+        author: "J. Doe and Richard Roe and Jane Doe".to_string(),
+        editor: Some("Smith, Ann and Ann Smith".to_string()),
+        year: "2021 2019".to_string(),
+        keywords: vec!["Soil".to_string(), "soil".to_string(), "Water".to_string()],
+        orcid: Some(vec![
+            "0000-0002-1825-0097".to_string(),
+            "0000-0002-1825-0097".to_string(),
+        ]),
+        ..Default::default()
+    };
+
+    strip_bibtex_duplicates(&mut entry);
+
+    assert_eq!(entry.author, "Jane Doe and Richard Roe");
+    assert_eq!(entry.editor.as_deref(), Some("Smith, Ann"));
+    assert_eq!(entry.year, "2019");
+    assert_eq!(entry.keywords, ["Soil", "Water"]);
+    assert_eq!(entry.orcid, Some(vec!["0000-0002-1825-0097".to_string()]));
+}
+
+#[test]
+fn leaves_single_values_alone() {
+    let mut entry = ArticleMetadata {
+        author: "Jane Doe".to_string(),
+        year: "2019-08-26".to_string(),
+        ..Default::default()
+    };
+
+    strip_bibtex_duplicates(&mut entry);
+
+    assert_eq!(entry.author, "Jane Doe");
+    assert_eq!(entry.year, "2019-08-26");
+}
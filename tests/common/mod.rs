@@ -0,0 +1,15 @@
+// Helpers shared by the integration tests. Not every test binary uses all of them.
+#![allow(dead_code)]
+
+pub fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// Pulls the braced value of `name` out of a generated entry.
+pub fn field<'a>(bibtex: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = format!("  {} = {{", name);
+    bibtex
+        .lines()
+        .find_map(|line| line.strip_prefix(prefix.as_str()))
+        .and_then(|rest| rest.strip_suffix("},"))
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use bibtexter::crossref::{DoiAuthor, doi_metadata};
+use bibtexter::http::FixtureClient;
+use common::fixture;
+
+const WORK: &str = "https://api.crossref.org/works/10.1038%2Fs41467-019-11786-6";
+
+#[tokio::test]
+async fn crossref_work_is_normalized() {
+    let client = FixtureClient::new().with_fixture(WORK, fixture("crossref_work.json"));
+
+    let metadata = doi_metadata(&client, "https://doi.org/10.1038/s41467-019-11786-6")
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.doi, "10.1038/s41467-019-11786-6");
+    assert_eq!(metadata.work_type.as_deref(), Some("journal-article"));
+    assert_eq!(
+        metadata.authors,
+        vec![
+            DoiAuthor {
+                given: Some("Methodios".to_string()),
+                family: Some("Ximerakis".to_string()),
+                name: None,
+                orcid: Some("0000-0002-1825-0097".to_string()),
+            },
+            DoiAuthor {
+                given: None,
+                family: None,
+                name: Some("Tabula Muris Consortium".to_string()),
+                orcid: None,
+            },
+        ]
+    );
+    assert_eq!(metadata.published.as_deref(), Some("2019-08-26"));
+    assert_eq!(metadata.year, Some(2019));
+    assert_eq!(
+        metadata.container_title.as_deref(),
+        Some("Nature Communications")
+    );
+    assert_eq!(metadata.page.as_deref(), Some("3814"));
+    assert_eq!(metadata.subjects.len(), 2);
+    assert_eq!(
+        metadata.abstract_text.as_deref(),
+        Some("The ageing brain undergoes a decline in cognitive function.")
+    );
+    assert_eq!(metadata.issn, vec!["2041-1723"]);
+
+    // The second lookup is answered from the cache.
+    let cached = doi_metadata(&FixtureClient::new(), "10.1038/S41467-019-11786-6")
+        .await
+        .unwrap();
+    assert_eq!(cached, metadata);
+
+    let json = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(json["type"], "journal-article");
+    assert!(json["abstract"].is_string());
+}
+
+#[tokio::test]
+async fn doi_is_encoded_as_one_path_segment() {
+    // Old Wiley DOIs contain `<`, `>`, `;` and even `#`.
+    let client = FixtureClient::new().with_fixture(
+        "https://api.crossref.org/works/10.1002%2F1521-3773(20010316)40:6%3C1046::AID-ANIE1046%3E3.0.CO;2-%23",
+        fixture("crossref_work.json"),
+    );
+
+    let metadata = doi_metadata(
+        &client,
+        "10.1002/1521-3773(20010316)40:6<1046::AID-ANIE1046>3.0.CO;2-#",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(metadata.work_type.as_deref(), Some("journal-article"));
+}
+
+#[tokio::test]
+async fn malformed_doi_is_refused() {
+    assert!(
+        doi_metadata(&FixtureClient::new(), "not-a-doi")
+            .await
+            .is_err()
+    );
+}
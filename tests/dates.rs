@@ -0,0 +1,104 @@
+use bibtexter::dates::parse_year;
+
+#[test]
+fn iso_and_machine_dates() {
+    for input in [
+        "2024",
+        "2024-03",
+        "2024-03-15",
+        "2024/03/15",
+        "2024.03.15",
+        "20240315",
+        "2024-03-15T10:00:00Z",
+        "2024-03-15T10:00:00+09:00",
+        "2024-03-15T10:00:00.123456Z",
+        "2024-03-15T10:00:00",
+        "2024-03-15 10:00:00",
+        "  2024-03-15  ",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+}
+
+#[test]
+fn rfc_2822_dates() {
+    for input in [
+        "Mon, 15 Mar 2024 10:00:00 GMT",
+        "Fri, 15 Mar 2024 10:00:00 +0000",
+        "15 Mar 2024 10:00:00 -0500",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+}
+
+#[test]
+fn numeric_day_and_month_first_dates() {
+    for input in [
+        "15/03/2024",
+        "03/15/2024",
+        "03/04/2024",
+        "15-03-2024",
+        "15.03.2024",
+        "15/03/24",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+}
+
+#[test]
+fn written_out_months() {
+    for input in [
+        "March 15, 2024",
+        "March 15 2024",
+        "Mar 15, 2024",
+        "Mar. 15, 2024",
+        "15 March 2024",
+        "15 Mar 2024",
+        "Mar 2024",
+        "March 2024",
+        "mars 2024",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+}
+
+#[test]
+fn quarters_and_seasons() {
+    for input in [
+        "Q1 2024",
+        "q3 2024",
+        "Q4/2024",
+        "2024-Q2",
+        "2024 Q3",
+        "Spring 2024",
+        "Summer, 2024",
+        "Fall 2024",
+        "Autumn 2024",
+        "Winter 2024/25",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+}
+
+#[test]
+fn loose_text_around_year() {
+    for input in [
+        "Published: 2024",
+        "(2024)",
+        "[2024]",
+        "c. 2024",
+        "2024a",
+        "2024-2025",
+        "Vol. 12, 2024",
+    ] {
+        assert_eq!(parse_year(input), Some(2024), "{}", input);
+    }
+    assert_eq!(parse_year("1687"), Some(1687));
+}
+
+#[test]
+fn unparseable_or_implausible_dates() {
+    for input in ["", "   ", "n.d.", "unknown", "24", "12345", "0042", "9999"] {
+        assert_eq!(parse_year(input), None, "{}", input);
+    }
+}
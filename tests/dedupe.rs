@@ -0,0 +1,82 @@
+use bibtexter::dedupe::{BatchEntry, deduplicate_entries, title_fingerprint};
+
+fn entry(url: &str, bibtex: &str) -> BatchEntry {
+    BatchEntry {
+        url: url.to_string(),
+        bibtex: bibtex.to_string(),
+    }
+}
+
+#[test]
+fn fingerprint_ignores_case_and_punctuation() {
+    assert_eq!(
+        title_fingerprint("Attention Is All You Need!"),
+        title_fingerprint("attention is   all you need")
+    );
+    assert_eq!(title_fingerprint(&"a".repeat(200)).len(), 80);
+}
+
+#[test]
+fn entries_with_the_same_doi_are_merged() {
+    let batch = deduplicate_entries(vec![
+        entry(
+            "https://doi.org/10.1145/3158154",
+            "@article{Jung_2017, title={RustBelt}, DOI={10.1145/3158154}, year={2017}, author={Jung, Ralf}}",
+        ),
+        entry(
+            "https://dl.acm.org/doi/10.1145/3158154",
+            "@misc{Jung2017RustBelt,\n  title = {RustBelt: Securing the Foundations},\n  doi = {10.1145/3158154},\n}",
+        ),
+    ]);
+
+    assert_eq!(batch.entries.len(), 1);
+    assert_eq!(batch.entries[0].url, "https://doi.org/10.1145/3158154");
+    assert_eq!(
+        batch.duplicates_merged,
+        vec![vec![
+            "https://doi.org/10.1145/3158154".to_string(),
+            "https://dl.acm.org/doi/10.1145/3158154".to_string(),
+        ]]
+    );
+}
+
+#[test]
+fn fields_after_non_ascii_text_are_read() {
+    // The Kelvin sign lowercases to a one-byte `k`, which once shifted field offsets.
+    let batch = deduplicate_entries(vec![
+        entry(
+            "https://doi.org/10.1000/kelvin",
+            "@article{a, doi={10.1000/kelvin}, title={Cooling to 4 \u{212A}}}",
+        ),
+        entry(
+            "https://example.com/kelvin",
+            "@misc{b, DOI={10.1000/KELVIN}, title={\u{212A}}}",
+        ),
+    ]);
+
+    assert_eq!(batch.entries.len(), 1);
+    assert_eq!(batch.entries[0].url, "https://doi.org/10.1000/kelvin");
+}
+
+#[test]
+fn entries_with_matching_titles_keep_the_richest() {
+    let batch = deduplicate_entries(vec![
+        entry(
+            "https://a.example",
+            "@misc{A,\n  title = {Deep Learning},\n}",
+        ),
+        entry(
+            "https://b.example",
+            "@misc{B,\n  title = {Deep learning.},\n  author = {LeCun, Yann},\n  year = {2015},\n}",
+        ),
+        entry(
+            "https://c.example",
+            "@misc{C,\n  booktitle = {Deep Learning},\n  title = {Other},\n}",
+        ),
+    ]);
+
+    assert_eq!(batch.entries.len(), 2);
+    assert_eq!(batch.entries[0].url, "https://b.example");
+    assert_eq!(batch.entries[1].url, "https://c.example");
+    assert_eq!(batch.duplicates_merged.len(), 1);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use bibtexter::doi_check::{check_doi, is_valid_doi};
+use bibtexter::http::FixtureClient;
+use common::fixture;
+
+#[test]
+fn validates_doi_syntax() {
+    assert!(is_valid_doi("10.1145/3158154"));
+    assert!(is_valid_doi("10.1000.10/abc(def)"));
+    assert!(is_valid_doi(
+        "10.1002/(SICI)1097-4571(199806)49:8<693::AID-ASI4>3.0.CO;2-0"
+    ));
+
+    assert!(!is_valid_doi("11.1145/3158154"));
+    assert!(!is_valid_doi("10.abc/3158154"));
+    assert!(!is_valid_doi("10.1145/"));
+    assert!(!is_valid_doi("10.1145/has space"));
+}
+
+#[tokio::test]
+async fn malformed_doi_is_not_looked_up() {
+    let check = check_doi(&FixtureClient::new(), "not-a-doi").await;
+
+    assert!(!check.valid_format);
+    assert_eq!(check.status, None);
+    assert!(!check.resolves);
+    assert!(!check.crossref_indexed);
+}
+
+#[tokio::test]
+async fn reports_resolution_open_access_and_crossref() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_UNPAYWALL_EMAIL", "test@example.com") };
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://doi.org/10.1145%2F3158154",
+            fixture("doi_rustbelt.bib"),
+        )
+        .with_fixture(
+            "https://api.unpaywall.org/v2/10.1145%2F3158154?email=test%40example.com",
+            fixture("unpaywall_oa.json"),
+        )
+        .with_fixture(
+            "https://api.crossref.org/works/10.1145%2F3158154",
+            fixture("crossref_work.json"),
+        );
+
+    let check = check_doi(&client, "https://doi.org/10.1145/3158154").await;
+
+    assert_eq!(check.doi, "10.1145/3158154");
+    assert!(check.valid_format);
+    assert_eq!(check.status, Some(200));
+    assert!(check.resolves);
+    assert_eq!(check.open_access, Some(true));
+    assert!(check.crossref_indexed);
+    assert_eq!(check.relations, Default::default());
+}
+
+#[tokio::test]
+async fn unknown_doi_does_not_resolve() {
+    let check = check_doi(&FixtureClient::new(), "10.9999/does-not-exist").await;
+
+    assert!(check.valid_format);
+    assert_eq!(check.status, Some(404));
+    assert!(!check.resolves);
+    assert!(!check.crossref_indexed);
+}
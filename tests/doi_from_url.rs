@@ -0,0 +1,93 @@
+mod common;
+
+use bibtexter::doi_from_url::{Confidence, FoundDoi, find_doi};
+use bibtexter::http::FixtureClient;
+use common::fixture;
+
+fn found(doi: &str, confidence: Confidence) -> Option<FoundDoi> {
+    Some(FoundDoi {
+        doi: doi.to_string(),
+        confidence,
+    })
+}
+
+async fn lookup(url: &str, fixture_name: &str) -> Option<FoundDoi> {
+    let client = FixtureClient::new().with_fixture(url, fixture(fixture_name));
+    find_doi(&client, url).await.unwrap()
+}
+
+#[tokio::test]
+async fn doi_links_are_answered_without_fetching() {
+    let client = FixtureClient::new();
+
+    assert_eq!(
+        find_doi(&client, "https://doi.org/10.1145/3158154")
+            .await
+            .unwrap(),
+        found("10.1145/3158154", Confidence::High)
+    );
+    assert_eq!(
+        find_doi(&client, "doi:10.1145/3158154").await.unwrap(),
+        found("10.1145/3158154", Confidence::High)
+    );
+}
+
+#[tokio::test]
+async fn highwire_doi_is_high_confidence() {
+    assert_eq!(
+        lookup("https://f1000research.com/articles/9-245/v2", "f1000.html").await,
+        found("10.12688/f1000research.24567.2", Confidence::High)
+    );
+}
+
+#[tokio::test]
+async fn doi_in_publisher_path_is_medium_confidence() {
+    assert_eq!(
+        lookup(
+            "https://onlinelibrary.wiley.com/doi/full/10.1002/asi.24567",
+            "blog.html"
+        )
+        .await,
+        found("10.1002/asi.24567", Confidence::Medium)
+    );
+}
+
+#[tokio::test]
+async fn dublin_core_and_schema_org_are_medium_confidence() {
+    assert_eq!(
+        lookup(
+            "https://catalog.data.gov/dataset/electric-vehicle-population-data",
+            "data_gov.html"
+        )
+        .await,
+        found("10.00000/ev-population", Confidence::Medium)
+    );
+    assert_eq!(
+        lookup(
+            "https://peerj.com/articles/14021/",
+            "schema_identifier.html"
+        )
+        .await,
+        found("10.7717/peerj.14021", Confidence::Medium)
+    );
+}
+
+#[tokio::test]
+async fn linked_doi_is_low_confidence() {
+    assert_eq!(
+        lookup("https://example.com/reading-list", "references_only.html").await,
+        found("10.1145/3158154", Confidence::Low)
+    );
+}
+
+#[tokio::test]
+async fn page_without_doi_finds_nothing() {
+    assert_eq!(
+        lookup(
+            "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html",
+            "blog.html"
+        )
+        .await,
+        None
+    );
+}
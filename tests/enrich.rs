@@ -0,0 +1,108 @@
+mod common;
+
+use bibtexter::enrich::{BibItem, enrich_bib, lookup_target, merge_fields, parse_bib};
+use bibtexter::error::AppError;
+use bibtexter::http::FixtureClient;
+use common::fixture;
+
+fn entries(input: &str) -> Vec<bibtexter::enrich::BibEntry> {
+    parse_bib(input)
+        .into_iter()
+        .filter_map(|item| match item {
+            BibItem::Entry(entry) => Some(entry),
+            BibItem::Text(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn parses_braced_quoted_and_bare_values() {
+    let parsed =
+        entries(r#"@Article{key1, Title = {The {Rust} Book}, journal = "J. {Prog}", year = 2019}"#);
+
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].entry_type, "article");
+    assert_eq!(parsed[0].key, "key1");
+    assert_eq!(parsed[0].field("title"), Some("The {Rust} Book"));
+    assert_eq!(parsed[0].field("journal"), Some("J. {Prog}"));
+    assert_eq!(parsed[0].field("year"), Some("2019"));
+}
+
+#[test]
+fn keeps_comments_and_string_blocks_as_text() {
+    let input =
+        "% note to self: mail me@example.com\n@string{acm = \"ACM\"}\n@misc{a, title = {A}}\n";
+    let items = parse_bib(input);
+
+    assert_eq!(items.len(), 3);
+    assert!(
+        matches!(&items[0], BibItem::Text(text) if text.contains("me@example.com") && text.contains("@string"))
+    );
+    assert!(matches!(&items[1], BibItem::Entry(_)));
+    assert_eq!(items[2], BibItem::Text("\n".into()));
+}
+
+#[test]
+fn finds_url_in_howpublished_and_note() {
+    let parsed = entries(
+        "@misc{a, howpublished = {\\url{https://example.com/post}}}\n@misc{b, note = {See https://example.org/x.}}",
+    );
+
+    assert_eq!(
+        lookup_target(&parsed[0]).as_deref(),
+        Some("https://example.com/post")
+    );
+    assert_eq!(
+        lookup_target(&parsed[1]).as_deref(),
+        Some("https://example.org/x")
+    );
+}
+
+#[test]
+fn merge_adds_only_missing_fields() {
+    let entry = &entries("@article{a,\n  title = {Mine},\n  doi = {10.1/x}\n}")[0];
+    let found = &entries("@article{b, title={Theirs}, journal={J}, year={2020}}")[0];
+
+    let enriched = merge_fields(entry, found);
+
+    assert_eq!(enriched.added, vec!["year", "journal"]);
+    assert_eq!(
+        enriched.bibtex,
+        "@article{a,\n  title = {Mine},\n  doi = {10.1/x},\n  year = {2020},\n  journal = {J},\n}"
+    );
+}
+
+#[tokio::test]
+async fn enriches_entries_and_leaves_the_rest_unchanged() {
+    let client = FixtureClient::new().with_fixture(
+        "https://doi.org/10.1145/3158154",
+        fixture("doi_rustbelt.bib"),
+    );
+    let input = std::fs::read_to_string(fixture("to_enrich.bib")).unwrap();
+
+    let output = enrich_bib(&client, &input, 50).await.unwrap();
+
+    assert!(output.starts_with(
+        "% Enriched 1 of 2 entries.\n% jung2017: author, year, booktitle, publisher\n"
+    ));
+    assert!(output.contains("@string{acm = \"ACM\"}"));
+    assert!(output.contains("  booktitle = {Proceedings of the ACM on Programming Languages},"));
+    assert!(
+        output.contains(
+            "@misc{offline,\n  title = {No Way to Look This Up},\n  author = {Nobody},\n}"
+        )
+    );
+}
+
+#[tokio::test]
+async fn files_with_too_many_lookups_are_refused() {
+    let client = FixtureClient::new();
+    let input = std::fs::read_to_string(fixture("to_enrich.bib")).unwrap();
+
+    // Only one of the file's entries has anything to look up.
+    assert!(enrich_bib(&client, &input, 1).await.is_ok());
+    assert!(matches!(
+        enrich_bib(&client, &input, 0).await,
+        Err(AppError::InvalidInput(_))
+    ));
+}
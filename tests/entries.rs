@@ -0,0 +1,233 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use bibtexter::AppState;
+use bibtexter::api::{bibtex_file_handler, export_bibtex_batch_handler, export_ris_batch_handler};
+use bibtexter::entries::{EntryStore, citation_key};
+use bibtexter::extract::ExtractionStrategy;
+
+const ENTRY: &str = "@misc{Doe.Roe2025Title,\n  title = {Title},\n}";
+
+#[test]
+fn reads_citation_key_past_comments() {
+    assert_eq!(citation_key(ENTRY), Some("Doe.Roe2025Title"));
+    assert_eq!(
+        citation_key("% NOTE: no authors\n@misc{Unknown2024Draft,\n}"),
+        Some("Unknown2024Draft")
+    );
+    assert_eq!(citation_key("not bibtex"), None);
+}
+
+#[test]
+fn store_keeps_latest_entry_per_key() {
+    let mut store = EntryStore::default();
+    store.insert(ENTRY);
+    store.insert("@misc{Doe.Roe2025Title,\n  title = {Title, revised},\n}");
+
+    assert!(store.get("Doe.Roe2025Title").unwrap().contains("revised"));
+    assert_eq!(store.get("Missing2020"), None);
+}
+
+#[test]
+fn full_store_forgets_the_oldest_entry() {
+    let mut store = EntryStore::new(2);
+    store.insert("@misc{First2025,\n}");
+    store.insert("@misc{Second2025,\n}");
+    // Replacing an entry that is already stored makes no room.
+    store.insert("@misc{Second2025,\n  note = {again},\n}");
+    store.insert("@misc{Third2025,\n}");
+
+    assert_eq!(store.get("First2025"), None);
+    assert!(store.get("Second2025").unwrap().contains("again"));
+    assert!(store.get("Third2025").is_some());
+}
+
+#[tokio::test]
+async fn serves_stored_entry_as_bib_file() {
+    let state = AppState::new(reqwest::Client::new());
+    state.entries.lock().unwrap().insert(ENTRY);
+
+    let response = bibtex_file_handler(State(state), admin(), Path("Doe.Roe2025Title.bib".into()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"Doe.Roe2025Title.bib\""
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, format!("{}\n", ENTRY).as_bytes());
+}
+
+#[tokio::test]
+async fn unknown_key_is_not_found() {
+    let state = AppState::new(reqwest::Client::new());
+
+    let response = bibtex_file_handler(State(state), admin(), Path("Nobody2020.bib".into()))
+        .await
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn stored_entry_requires_the_admin_token() {
+    let state = AppState::new(reqwest::Client::new());
+    state.entries.lock().unwrap().insert(ENTRY);
+    admin();
+
+    let response = bibtex_file_handler(
+        State(state),
+        HeaderMap::new(),
+        Path("Doe.Roe2025Title.bib".into()),
+    )
+    .await
+    .into_response();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+const ARTICLE: &str = "@article{Roe2024Tides,\n  title = {Tides},\n  journal = {Oceans},\n}";
+
+/// Headers presenting the admin token, which is set for this whole suite.
+fn admin() -> HeaderMap {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_ADMIN_TOKEN", "secret") };
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-key", "secret".parse().unwrap());
+    headers
+}
+
+async fn export(state: &AppState, query: &str) -> (String, String) {
+    let uri = format!("http://localhost/api/export-bibtex-batch?{}", query)
+        .parse()
+        .unwrap();
+    let response = export_bibtex_batch_handler(
+        State(state.clone()),
+        admin(),
+        Query::try_from_uri(&uri).unwrap(),
+    )
+    .await
+    .into_response();
+    let disposition = response.headers()[header::CONTENT_DISPOSITION]
+        .to_str()
+        .unwrap_or_default()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (disposition, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn exports_all_stored_entries_as_one_bib_file() {
+    let state = AppState::new(reqwest::Client::new());
+    {
+        let mut entries = state.entries.lock().unwrap();
+        entries.insert_extracted(ENTRY, Some(ExtractionStrategy::MetaTags));
+        entries.insert_extracted(ARTICLE, Some(ExtractionStrategy::DoiContentNegotiation));
+    }
+
+    let (disposition, body) = export(&state, "").await;
+    assert_eq!(disposition, "attachment; filename=\"bibtexter-export.bib\"");
+    assert!(body.contains(ENTRY) && body.contains(ARTICLE), "{}", body);
+
+    let (_, body) = export(&state, "strategy=doi").await;
+    assert_eq!(body, format!("{}\n", ARTICLE));
+
+    let (_, body) = export(&state, "entry_type=misc").await;
+    assert_eq!(body, format!("{}\n", ENTRY));
+
+    let (_, body) = export(&state, "after=2999-01-01").await;
+    assert_eq!(body, "");
+}
+
+#[tokio::test]
+async fn export_merges_entries_for_the_same_work() {
+    let state = AppState::new(reqwest::Client::new());
+    {
+        let mut entries = state.entries.lock().unwrap();
+        entries.insert("@article{Jung_2017, title={RustBelt}, doi={10.1145/3158154}}");
+        entries.insert(
+            "@misc{Jung2017RustBelt,\n  title = {RustBelt},\n  doi = {10.1145/3158154},\n  year = {2017},\n}",
+        );
+    }
+    let uri = "http://localhost/api/export-bibtex-batch".parse().unwrap();
+
+    let response =
+        export_bibtex_batch_handler(State(state), admin(), Query::try_from_uri(&uri).unwrap())
+            .await
+            .unwrap();
+
+    assert_eq!(response.headers()["x-duplicates-merged"], "1");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("Jung2017RustBelt") && !body.contains("Jung_2017"));
+}
+
+#[tokio::test]
+async fn export_rejects_malformed_dates() {
+    let state = AppState::new(reqwest::Client::new());
+    let uri = "http://localhost/api/export-ris-batch?after=last-week"
+        .parse()
+        .unwrap();
+
+    let response =
+        export_ris_batch_handler(State(state), admin(), Query::try_from_uri(&uri).unwrap())
+            .await
+            .into_response();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn export_requires_the_admin_token() {
+    let state = AppState::new(reqwest::Client::new());
+    state.entries.lock().unwrap().insert(ARTICLE);
+    admin();
+    let uri = "http://localhost/api/export-bibtex-batch".parse().unwrap();
+
+    let response = export_bibtex_batch_handler(
+        State(state.clone()),
+        HeaderMap::new(),
+        Query::try_from_uri(&uri).unwrap(),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = export_ris_batch_handler(
+        State(state),
+        HeaderMap::new(),
+        Query::try_from_uri(&uri).unwrap(),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn exports_stored_entries_as_ris() {
+    let state = AppState::new(reqwest::Client::new());
+    state.entries.lock().unwrap().insert(ARTICLE);
+    let uri = "http://localhost/api/export-ris-batch".parse().unwrap();
+
+    let response =
+        export_ris_batch_handler(State(state), admin(), Query::try_from_uri(&uri).unwrap())
+            .await
+            .unwrap();
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/x-research-info-systems; charset=utf-8"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.starts_with(b"TY  - JOUR\nID  - Roe2024Tides\n"));
+}
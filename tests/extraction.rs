@@ -0,0 +1,1199 @@
+mod common;
+
+use bibtexter::bibtex::{BibtexEntryType, OutputMode, bcp47_to_biblatex_langid};
+use bibtexter::error::AppError;
+use bibtexter::extract::{
+    ArticleMetadata, ExtractionStrategy, URL_TITLE_WARNING, canonical_link,
+    discover_doi_from_headers, expand_jsonld_context, fetch_and_generate_bibtex,
+    fetch_and_generate_entry, fetch_metadata, format_bibtex, generate_citation_key,
+    parse_page_count, require_real_title, scrape_metadata,
+};
+use bibtexter::http::{FixtureClient, HttpResponse, decode_body};
+use common::{field, fixture};
+use reqwest::{StatusCode, header};
+use url::Url;
+
+/// Runs the extractor against a single fixture page served for `url`.
+async fn extract(url: &str, fixture_name: &str) -> String {
+    let client = FixtureClient::new().with_fixture(url, fixture(fixture_name));
+    fetch_and_generate_bibtex(&client, url)
+        .await
+        .expect("extraction should succeed")
+}
+
+#[tokio::test]
+async fn page_without_metadata_follows_its_canonical_link() {
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://blog.rust-lang.org/tags/releases/",
+            fixture("tag_page_canonical.html"),
+        )
+        .with_fixture(
+            "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html",
+            fixture("blog.html"),
+        );
+
+    let metadata = fetch_metadata(&client, "https://blog.rust-lang.org/tags/releases/")
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.title, "Announcing Rust 1.80.0");
+    assert_eq!(metadata.author, "The Rust Release Team");
+    assert_eq!(metadata.year, "2024");
+}
+
+#[tokio::test]
+async fn unreachable_canonical_page_keeps_the_original() {
+    let client = FixtureClient::new().with_fixture(
+        "https://blog.rust-lang.org/tags/releases/",
+        fixture("tag_page_canonical.html"),
+    );
+
+    let metadata = fetch_metadata(&client, "https://blog.rust-lang.org/tags/releases/")
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.title, "Releases | Rust Blog");
+    assert_eq!(metadata.author, "");
+}
+
+#[test]
+fn canonical_link_must_name_another_page() {
+    let url = Url::parse("https://example.com/posts/1/#comments").unwrap();
+    let canonical = |href: &str| {
+        let html = format!("<head><link rel=\"canonical\" href=\"{}\"></head>", href);
+        canonical_link(&scraper::Html::parse_document(&html), &url)
+    };
+
+    assert_eq!(
+        canonical("/posts/one").as_deref(),
+        Some("https://example.com/posts/one")
+    );
+    assert_eq!(canonical("https://example.com/posts/1"), None);
+    assert_eq!(canonical("javascript:void(0)"), None);
+}
+
+#[tokio::test]
+async fn jsonld_with_custom_context_is_read_as_schema_org() {
+    let url = "https://shoreline.example.org/papers/coastal-erosion";
+    let client = FixtureClient::new().with_fixture(url, fixture("jsonld_custom_context.html"));
+
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+
+    assert_eq!(metadata.strategy, ExtractionStrategy::JsonLd);
+    assert_eq!(metadata.entry_type, BibtexEntryType::Article);
+    assert_eq!(metadata.title, "Coastal erosion after winter storms");
+    assert_eq!(metadata.author, "Ines Moreau and Tobias Lind");
+    assert_eq!(metadata.year, "2023");
+}
+
+#[test]
+fn jsonld_context_expansion_keeps_standard_names() {
+    let plain = serde_json::json!({
+        "@context": "https://schema.org/",
+        "@type": "Article",
+        "headline": "Title"
+    });
+    assert_eq!(expand_jsonld_context(plain.clone()), plain);
+
+    let remapped = expand_jsonld_context(serde_json::json!({
+        "@context": ["https://schema.org/", {"title": "https://schema.org/headline"}],
+        "@type": "Article",
+        "title": "Local",
+        "headline": "Standard",
+        "http://schema.org/datePublished": "2020"
+    }));
+    assert_eq!(remapped["headline"], "Standard");
+    assert_eq!(remapped["datePublished"], "2020");
+}
+
+#[tokio::test]
+async fn arxiv_uses_opengraph_title() {
+    let bibtex = extract("https://arxiv.org/abs/1706.03762", "arxiv.html").await;
+
+    assert!(bibtex.starts_with("@unpublished{UnknownNDAttention,"));
+    assert_eq!(field(&bibtex, "title"), Some("Attention Is All You Need"));
+    assert_eq!(field(&bibtex, "author"), None);
+    assert_eq!(field(&bibtex, "publisher"), Some("arxiv.org"));
+    assert_eq!(field(&bibtex, "orcid"), Some("0000-0001-5109-3700"));
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Preprint, arXiv:1706.03762; Accessed: ")
+    );
+    assert_eq!(field(&bibtex, "eprint"), Some("1706.03762"));
+    assert_eq!(field(&bibtex, "archivePrefix"), Some("arXiv"));
+    assert_eq!(field(&bibtex, "primaryClass"), Some("cs.CL"));
+}
+
+#[tokio::test]
+async fn arxiv_eprint_fields_follow_biblatex_conventions() {
+    let url = "https://arxiv.org/abs/1706.03762";
+    let client = FixtureClient::new().with_fixture(url, fixture("arxiv.html"));
+
+    let biblatex = fetch_and_generate_entry(&client, url, OutputMode::Biblatex)
+        .await
+        .unwrap()
+        .bibtex;
+    assert!(biblatex.starts_with("@unpublished{"), "{}", biblatex);
+    assert_eq!(field(&biblatex, "eprint"), Some("1706.03762"));
+    assert_eq!(field(&biblatex, "eprinttype"), Some("arXiv"));
+    assert_eq!(field(&biblatex, "eprintclass"), Some("cs.CL"));
+    assert_eq!(field(&biblatex, "archivePrefix"), None);
+
+    let online = fetch_and_generate_entry(&client, url, OutputMode::BiblatexArxiv)
+        .await
+        .unwrap()
+        .bibtex;
+    assert!(online.starts_with("@online{"), "{}", online);
+    assert_eq!(field(&online, "eprinttype"), Some("arXiv"));
+}
+
+#[tokio::test]
+async fn personal_homepage_draft_is_unpublished() {
+    let bibtex = extract(
+        "https://www.cs.example.edu/~jdoe/drafts/gradual-effects.html",
+        "personal_draft.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@unpublished{Doe2024Gradual,"));
+    assert_eq!(
+        field(&bibtex, "url"),
+        Some("https://www.cs.example.edu/~jdoe/drafts/gradual-effects.html")
+    );
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Manuscript; Accessed: ")
+    );
+}
+
+#[tokio::test]
+async fn read_the_docs_page_is_a_manual() {
+    let bibtex = extract(
+        "https://requests.readthedocs.io/en/v2.31.0/user/quickstart/",
+        "readthedocs.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@manual{Reitz2023Quickstart,"));
+    assert_eq!(field(&bibtex, "organization"), Some("requests"));
+    assert_eq!(field(&bibtex, "version"), Some("v2.31.0"));
+    assert_eq!(
+        field(&bibtex, "url"),
+        Some("https://requests.readthedocs.io/en/v2.31.0/user/quickstart/")
+    );
+}
+
+#[tokio::test]
+async fn versioned_website_is_a_manual() {
+    let bibtex = extract("https://tokio.example.com/config", "versioned_docs.html").await;
+
+    assert!(bibtex.starts_with("@manual{"));
+    assert_eq!(field(&bibtex, "organization"), Some("Tokio"));
+    assert_eq!(field(&bibtex, "version"), Some("1.38"));
+}
+
+#[tokio::test]
+async fn conference_volume_without_authors_is_proceedings() {
+    let bibtex = extract(
+        "https://dl.example.org/doi/proceedings/10.1145/3600000",
+        "proceedings_volume.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@proceedings{Lopez.Chen2023Proceedings,"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Proceedings of the 2023 Workshop on Systems Programming")
+    );
+    assert_eq!(field(&bibtex, "editor"), Some("Lopez, Maria and Chen, Wei"));
+    assert_eq!(field(&bibtex, "booktitle"), None);
+    assert_eq!(field(&bibtex, "publisher"), Some("ACM"));
+}
+
+#[tokio::test]
+async fn edited_book_json_ld_is_a_collection() {
+    let bibtex = extract(
+        "https://books.example.com/essays-on-type-theory",
+        "edited_collection.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@collection{Bove.Dybjer2019Essays,"));
+    assert_eq!(
+        field(&bibtex, "editor"),
+        Some("Bove, Ana and Dybjer, Peter")
+    );
+    assert_eq!(field(&bibtex, "numpages"), Some("320"));
+    assert_eq!(field(&bibtex, "author"), None);
+}
+
+#[tokio::test]
+async fn overleaf_share_link_uses_project_name() {
+    let url = "https://www.overleaf.com/read/kzxqvbdmnrtp";
+    let bibtex = extract(url, "overleaf_read.html").await;
+
+    assert!(bibtex.starts_with(
+        "% NOTE: Author information not available for Overleaf shared links\n@misc{UnknownNDThesis,"
+    ));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Thesis Draft: Effect Systems in Practice")
+    );
+    assert_eq!(
+        field(&bibtex, "howpublished"),
+        Some(&*format!("\\url{{{}}}", url))
+    );
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Overleaf LaTeX document; Accessed: ")
+    );
+    assert_eq!(field(&bibtex, "abstract"), None);
+}
+
+#[tokio::test]
+async fn creative_commons_link_sets_license() {
+    let url = "https://notebooks.example.org/open-notebooks";
+    let client = FixtureClient::new().with_fixture(url, fixture("cc_licensed_article.html"));
+
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+    assert_eq!(metadata.license.as_deref(), Some("CC-BY-SA-4.0"));
+    assert_eq!(metadata.open_access, Some(true));
+
+    let bibtex = fetch_and_generate_entry(&client, url, OutputMode::Bibtex)
+        .await
+        .unwrap()
+        .bibtex;
+    assert_eq!(field(&bibtex, "license"), Some("CC-BY-SA-4.0"));
+
+    let biblatex = fetch_and_generate_entry(&client, url, OutputMode::Biblatex)
+        .await
+        .unwrap()
+        .bibtex;
+    assert_eq!(field(&biblatex, "creativecommons"), Some("CC-BY-SA-4.0"));
+    assert_eq!(field(&biblatex, "license"), None);
+}
+
+#[tokio::test]
+async fn url_fragment_cites_the_section_heading() {
+    let cases = [
+        ("section-8.8.3", "8.8.3. ETag"),
+        ("name-validator-fields", "8.8. Validator Fields"),
+        ("appendix-a", "Collected ABNF"),
+        ("security", "Security Considerations"),
+    ];
+    for (fragment, heading) in cases {
+        let url = format!("https://www.rfc-editor.org/rfc/rfc9110.html#{}", fragment);
+        let bibtex = extract(&url, "rfc_sections.html").await;
+
+        assert_eq!(
+            field(&bibtex, "chapter"),
+            Some(heading),
+            "for #{}",
+            fragment
+        );
+    }
+
+    let bibtex = extract(
+        "https://www.rfc-editor.org/rfc/rfc9110.html#no-such-section",
+        "rfc_sections.html",
+    )
+    .await;
+    assert_eq!(field(&bibtex, "chapter"), None);
+}
+
+#[tokio::test]
+async fn springer_falls_back_from_non_article_json_ld() {
+    let bibtex = extract(
+        "https://journalofbigdata.springeropen.com/articles/10.1186/s40537-021-00444-8",
+        "springer.html",
+    )
+    .await;
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some(
+            "Review of deep learning: concepts, CNN architectures, challenges, applications, future directions - Journal of Big Data"
+        )
+    );
+    assert!(bibtex.starts_with("@article{"));
+    assert_eq!(field(&bibtex, "journal"), Some("Journal of Big Data"));
+    assert_eq!(field(&bibtex, "address"), Some("Berlin, Germany"));
+    assert_eq!(field(&bibtex, "pages"), Some("53"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Springer"));
+}
+
+#[tokio::test]
+async fn acm_uses_site_override() {
+    let bibtex = extract("https://dl.acm.org/doi/10.1145/2692956.2663188", "acm.html").await;
+
+    assert_eq!(field(&bibtex, "title"), Some("The Rust Language"));
+    assert_eq!(
+        field(&bibtex, "publisher"),
+        Some("Association for Computing Machinery")
+    );
+}
+
+#[tokio::test]
+async fn acm_proceedings_use_json_ld_series() {
+    let bibtex = extract(
+        "https://dl.acm.org/doi/proceedings/10.1145/3453483",
+        "acm_proceedings.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@proceedings{"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some(
+            "PLDI 2021: Proceedings of the 42nd ACM SIGPLAN International Conference on Programming Language Design and Implementation"
+        )
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2021"));
+    assert_eq!(
+        field(&bibtex, "editor"),
+        Some("Freund, Stephen N. and Yahav, Eran")
+    );
+    assert_eq!(field(&bibtex, "publisher"), Some("ACM"));
+    assert_eq!(field(&bibtex, "isbn"), Some("9781450383912"));
+    assert_eq!(
+        field(&bibtex, "series"),
+        Some("PLDI: Programming Language Design and Implementation")
+    );
+    assert_eq!(field(&bibtex, "doi"), Some("10.1145/3453483"));
+}
+
+#[tokio::test]
+async fn acm_workshop_series_comes_from_breadcrumbs() {
+    let bibtex = extract(
+        "https://dl.acm.org/doi/proceedings/10.1145/3623504",
+        "acm_workshop.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@proceedings{"));
+    assert_eq!(field(&bibtex, "year"), Some("2023"));
+    assert_eq!(
+        field(&bibtex, "editor"),
+        Some("Chasins, Sarah and Glassman, Elena")
+    );
+    assert_eq!(field(&bibtex, "series"), Some("SPLASH"));
+    assert_eq!(field(&bibtex, "isbn"), Some("9798400703817"));
+}
+
+#[tokio::test]
+async fn ieee_uses_site_override() {
+    let bibtex = extract("https://ieeexplore.ieee.org/document/7780459", "ieee.html").await;
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Deep Residual Learning for Image Recognition")
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2016"));
+}
+
+#[tokio::test]
+async fn nature_prefers_json_ld_article() {
+    let bibtex = extract(
+        "https://www.nature.com/articles/d41586-024-02391-9",
+        "nature.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@misc{Smith.Doe2024The,"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("The race to make AI weather forecasts more accurate")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Jane Smith and John Doe"));
+    assert_eq!(field(&bibtex, "year"), Some("2024"));
+    assert_eq!(field(&bibtex, "orcid"), Some("0000-0002-1825-0097"));
+}
+
+#[tokio::test]
+async fn repeated_meta_authors_are_deduplicated() {
+    let bibtex = extract(
+        "https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4600000",
+        "ssrn.html",
+    )
+    .await;
+
+    assert_eq!(field(&bibtex, "author"), Some("Doe, Jane and Maria Rossi"));
+    assert_eq!(field(&bibtex, "year"), Some("2023"));
+}
+
+#[tokio::test]
+async fn coins_span_describes_the_article() {
+    let bibtex = extract("https://www.jstor.org/stable/1879431", "coins.html").await;
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("The Market for \"Lemons\": Quality Uncertainty and the Market Mechanism")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Akerlof, George A."));
+    assert_eq!(field(&bibtex, "year"), Some("1970"));
+    assert_eq!(
+        field(&bibtex, "journal"),
+        Some("The Quarterly Journal of Economics")
+    );
+    assert_eq!(field(&bibtex, "volume"), Some("84"));
+    assert_eq!(field(&bibtex, "number"), Some("3"));
+    assert_eq!(field(&bibtex, "pages"), Some("488--500"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.2307/1879431"));
+}
+
+#[tokio::test]
+async fn twitter_card_fills_in_for_missing_opengraph() {
+    let url = "https://www.nytimes.com/2024/03/02/opinion/slow-science.html";
+    let client = FixtureClient::new().with_fixture(url, fixture("twitter_card.html"));
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+
+    assert_eq!(metadata.title, "The Case for Slower Science");
+    assert_eq!(
+        metadata.abstract_text.as_deref(),
+        Some("Researchers argue that fewer, more careful studies would serve the public better.")
+    );
+    assert_eq!(metadata.publisher, "NYTimes");
+}
+
+#[tokio::test]
+async fn wikipedia_ignores_unparseable_json_ld() {
+    let bibtex = extract(
+        "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        "wikipedia.html",
+    )
+    .await;
+
+    assert_eq!(field(&bibtex, "title"), Some("Rust (programming language)"));
+    assert_eq!(field(&bibtex, "publisher"), Some("en.wikipedia.org"));
+}
+
+#[tokio::test]
+async fn github_uses_opengraph_title() {
+    let bibtex = extract("https://github.com/rust-lang/rust", "github.html").await;
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some(
+            "GitHub - rust-lang/rust: Empowering everyone to build reliable and efficient software."
+        )
+    );
+    assert_eq!(field(&bibtex, "publisher"), Some("github.com"));
+}
+
+#[tokio::test]
+async fn blog_reads_author_and_year_from_meta_tags() {
+    let bibtex = extract(
+        "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html",
+        "blog.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@misc{Team2024Announcing,"));
+    assert_eq!(field(&bibtex, "author"), Some("The Rust Release Team"));
+    assert_eq!(field(&bibtex, "year"), Some("2024"));
+}
+
+#[tokio::test]
+async fn doi_content_negotiation_returns_bibtex_verbatim() {
+    let client = FixtureClient::new().with_fixture(
+        "https://doi.org/10.1145/3158154",
+        fixture("doi_rustbelt.bib"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(&client, "https://doi.org/10.1145/3158154")
+        .await
+        .expect("extraction should succeed");
+
+    assert!(bibtex.starts_with("@inproceedings{Jung_2017,"));
+}
+
+#[tokio::test]
+async fn retracted_works_are_flagged_in_the_note() {
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://doi.org/10.1145/3158154",
+            fixture("doi_rustbelt.bib"),
+        )
+        .with_fixture(
+            "https://api.crossref.org/works/10.1145%2F3158154",
+            fixture("crossref_work_retracted.json"),
+        );
+
+    let bibtex = fetch_and_generate_bibtex(&client, "https://doi.org/10.1145/3158154")
+        .await
+        .expect("extraction should succeed");
+
+    assert!(bibtex.starts_with("@inproceedings{Jung_2017,"));
+    assert!(
+        bibtex.contains("\n  note = {RETRACTED: see doi:10.1145/3999999},"),
+        "{}",
+        bibtex
+    );
+}
+
+#[tokio::test]
+async fn missing_page_is_an_extraction_error() {
+    let client = FixtureClient::new();
+
+    let result = fetch_and_generate_bibtex(&client, "https://example.com/missing").await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn paywall_landing_page_is_reported() {
+    let url = "https://www.sciencedirect.com/science/article/pii/S0092867424000011";
+    let client = FixtureClient::new().with_fixture(url, fixture("paywall.html"));
+
+    let result = fetch_and_generate_bibtex(&client, url).await;
+
+    match result {
+        Err(AppError::PaywallDetected {
+            url: paywalled,
+            open_access_url,
+        }) => {
+            assert_eq!(paywalled, url);
+            assert_eq!(open_access_url, None);
+        }
+        other => panic!("expected a paywall error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn springer_paywall_retries_through_shared_it() {
+    let url = "https://link.springer.com/article/10.1007/s10994-021-06000-0";
+    let client = FixtureClient::new()
+        .with_fixture(url, fixture("springer_paywall.html"))
+        .with_fixture(
+            "https://link.springer.com/article/10.1007/s10994-021-06000-0?shared-article-renderer",
+            fixture("springer_shared_it.html"),
+        );
+
+    let bibtex = fetch_and_generate_bibtex(&client, url)
+        .await
+        .expect("extraction should succeed");
+
+    assert!(bibtex.starts_with("@article{Okafor.Brandt2021Learning,"));
+    assert_eq!(field(&bibtex, "journal"), Some("Machine Learning"));
+    assert_eq!(field(&bibtex, "url"), Some(url));
+}
+
+#[tokio::test]
+async fn springer_paywall_without_shared_it_is_reported() {
+    let url = "https://link.springer.com/article/10.1007/s10994-021-06000-0";
+    let client = FixtureClient::new().with_fixture(url, fixture("springer_paywall.html"));
+
+    let result = fetch_and_generate_bibtex(&client, url).await;
+
+    assert!(matches!(
+        result,
+        Err(AppError::PaywallDetected { url: paywalled, .. }) if paywalled == url
+    ));
+}
+
+#[tokio::test]
+async fn scrape_metadata_reports_strategy_and_warnings() {
+    let url = "https://github.com/rust-lang/rust";
+    let client = FixtureClient::new().with_fixture(url, fixture("github.html"));
+
+    let metadata = scrape_metadata(&client, url).await.unwrap();
+
+    assert_eq!(metadata.strategy, ExtractionStrategy::MetaTags);
+    assert_eq!(metadata.publisher, "github.com");
+    assert_eq!(metadata.warnings(), ["missing author", "missing year"]);
+}
+
+#[tokio::test]
+async fn json_ld_book_reports_page_count() {
+    let bibtex = extract(
+        "https://nostarch.com/rust-programming-language-2nd-edition",
+        "book.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@book{Klabnik.Nichols2023The,"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("The Rust Programming Language")
+    );
+    assert_eq!(field(&bibtex, "numpages"), Some("560"));
+    assert_eq!(field(&bibtex, "pages"), None);
+}
+
+#[test]
+fn page_counts_accept_common_suffixes() {
+    for input in [
+        "274",
+        "274 pages",
+        "274pp",
+        "274 pp.",
+        "274 p.",
+        " 274 Pages ",
+    ] {
+        assert_eq!(parse_page_count(input), Some(274), "{}", input);
+    }
+    assert_eq!(parse_page_count("pages 1-20"), None);
+}
+
+#[tokio::test]
+async fn scholarly_article_reads_pages_doi_and_free_access() {
+    let bibtex = extract(
+        "https://journal.example.org/articles/rivers",
+        "scholarly_article.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Sediment transport in braided rivers")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Ana Lima and Tom Berg"));
+    assert_eq!(field(&bibtex, "pages"), Some("112--129"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.5555/rivers.2023.112"));
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Open access; Accessed: ")
+    );
+}
+
+#[tokio::test]
+async fn json_ld_chapter_becomes_incollection() {
+    let bibtex = extract(
+        "https://link.springer.com/chapter/10.1007/978-3-642-36946-9_3",
+        "chapter_jsonld.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@incollection{"));
+    assert_eq!(field(&bibtex, "title"), Some("Ownership Types"));
+    assert_eq!(
+        field(&bibtex, "booktitle"),
+        Some("Aliasing in Object-Oriented Programming")
+    );
+    assert_eq!(
+        field(&bibtex, "editor"),
+        Some("Dave Clarke and James Noble")
+    );
+    assert_eq!(field(&bibtex, "chapter"), Some("3"));
+    assert_eq!(field(&bibtex, "pages"), Some("15--58"));
+    assert_eq!(field(&bibtex, "isbn"), Some("978-3-642-36946-9"));
+    assert_eq!(
+        field(&bibtex, "publisher"),
+        Some("Springer, Berlin, Heidelberg")
+    );
+}
+
+#[tokio::test]
+async fn highwire_inbook_title_becomes_incollection() {
+    let bibtex = extract(
+        "https://press.example.edu/handbook/concurrency",
+        "chapter_highwire.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@incollection{"));
+    assert_eq!(
+        field(&bibtex, "booktitle"),
+        Some("The Handbook of Systems Programming")
+    );
+    assert_eq!(field(&bibtex, "editor"), Some("Grace Hopper and Alan Kay"));
+    assert_eq!(field(&bibtex, "pages"), Some("101--142"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Example Academic Press"));
+}
+
+#[tokio::test]
+async fn shift_jis_page_is_decoded_from_meta_charset() {
+    let bibtex = extract("https://www.ipsj.or.jp/paper/12345", "shift_jis.html").await;
+
+    assert_eq!(field(&bibtex, "title"), Some("深層学習による画像認識"));
+    assert_eq!(field(&bibtex, "author"), Some("山田 太郎"));
+}
+
+#[tokio::test]
+async fn latin1_page_is_decoded_from_http_equiv() {
+    let bibtex = extract("https://www.uni-example.de/berechenbarkeit", "latin1.html").await;
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Über die Grenzen der Berechenbarkeit")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Jürgen Müller"));
+}
+
+#[test]
+fn content_type_charset_wins_over_default() {
+    let bytes = b"<p>caf\xe9</p>";
+
+    assert_eq!(
+        decode_body(bytes, Some("text/html; charset=\"windows-1252\"")),
+        "<p>café</p>"
+    );
+    assert_eq!(decode_body("<p>café</p>".as_bytes(), None), "<p>café</p>");
+}
+
+#[tokio::test]
+async fn schema_org_dataset_is_cited_as_dataset() {
+    let bibtex = extract(
+        "https://catalog.data.gov/dataset/electric-vehicle-population-data",
+        "data_gov.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@misc{"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Electric Vehicle Population Data")
+    );
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Washington State Department of Licensing")
+    );
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "publisher"), Some("State of Washington"));
+    assert_eq!(field(&bibtex, "license"), Some("ODbL-1.0"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.00000/ev-population"));
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Accessed from catalog.data.gov; Accessed: ")
+    );
+}
+
+#[test]
+fn datasets_are_dataset_entries_in_biblatex_and_misc_in_bibtex() {
+    let dataset = || ArticleMetadata {
+        entry_type: BibtexEntryType::Dataset,
+        resource_type: Some("Dataset".to_string()),
+        title: "Global soil moisture observations".to_string(),
+        author: "Maria Garcia".to_string(),
+        year: "2017".to_string(),
+        publisher: "figshare".to_string(),
+        ..Default::default()
+    };
+    let url = Some("https://figshare.com/articles/dataset/5616445");
+
+    let biblatex = format_bibtex(dataset(), url, OutputMode::Biblatex).unwrap();
+    assert!(biblatex.starts_with("@dataset{"), "{}", biblatex);
+    assert_eq!(field(&biblatex, "type"), None);
+    assert_eq!(
+        field(&biblatex, "url"),
+        Some("https://figshare.com/articles/dataset/5616445")
+    );
+
+    let bibtex = format_bibtex(dataset(), url, OutputMode::Bibtex).unwrap();
+    assert!(bibtex.starts_with("@misc{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+}
+
+#[tokio::test]
+async fn zenodo_dataset_cites_the_record_version() {
+    let bibtex = extract("https://zenodo.org/records/11200345", "zenodo_dataset.html").await;
+
+    assert!(bibtex.starts_with("@misc{"));
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "version"), Some("1.2.0"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.5281/zenodo.11200345"));
+}
+
+#[tokio::test]
+async fn citation_data_version_marks_a_dataset() {
+    let bibtex = extract(
+        "https://beta.ukdataservice.ac.uk/datacatalogue/studies/study?id=9012",
+        "data_version_meta.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@misc{"));
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "version"), Some("4"));
+}
+
+#[tokio::test]
+async fn dcat_dataset_falls_back_to_dublin_core() {
+    let bibtex = extract(
+        "https://data.europa.eu/data/datasets/air-quality-statistics",
+        "dcat_europa.html",
+    )
+    .await;
+
+    assert_eq!(field(&bibtex, "title"), Some("Air quality statistics"));
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("European Environment Agency")
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2022"));
+    assert_eq!(
+        field(&bibtex, "publisher"),
+        Some("European Environment Agency")
+    );
+    assert_eq!(field(&bibtex, "license"), Some("CC-BY-4.0"));
+    assert_eq!(field(&bibtex, "keywords"), Some("air quality, pollution"));
+}
+
+#[test]
+fn link_header_doi_requires_citation_rel() {
+    let response = |link: &'static str| {
+        let mut headers = header::HeaderMap::new();
+        headers.append(header::LINK, header::HeaderValue::from_static(link));
+        HttpResponse {
+            status: StatusCode::OK,
+            headers,
+            body: String::new(),
+        }
+    };
+
+    for link in [
+        r#"<https://doi.org/10.1145/3158154>; rel="cite-as""#,
+        r#"<https://doi.org/10.1145/3158154>; rel=describedby; type="application/x-bibtex""#,
+        r#"<https://example.com/api>; rel="next", <https://doi.org/10.1145/3158154>; rel="canonical""#,
+        r#"<https://dx.doi.org/10.1145/3158154>; rel="alternate cite-as""#,
+    ] {
+        assert_eq!(
+            discover_doi_from_headers(&response(link)).as_deref(),
+            Some("10.1145/3158154"),
+            "{}",
+            link
+        );
+    }
+    assert_eq!(
+        discover_doi_from_headers(&response(
+            r#"<https://doi.org/10.1145/3158154>; rel="alternate""#
+        )),
+        None
+    );
+    assert_eq!(
+        discover_doi_from_headers(&response(
+            r#"<https://example.com/records/42>; rel="cite-as""#
+        )),
+        None
+    );
+}
+
+#[tokio::test]
+async fn link_header_doi_uses_content_negotiation() {
+    let url = "https://zenodo.org/records/1234567";
+    let client = FixtureClient::new()
+        .with_fixture(url, fixture("blog.html"))
+        .with_header(
+            url,
+            header::LINK,
+            r#"<https://doi.org/10.1145/3158154>; rel="cite-as""#,
+        )
+        .with_fixture(
+            "https://doi.org/10.1145/3158154",
+            fixture("doi_rustbelt.bib"),
+        );
+
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+
+    assert!(bibtex.starts_with("@inproceedings{Jung_2017,"));
+}
+
+#[tokio::test]
+async fn link_header_doi_is_kept_when_negotiation_fails() {
+    let url = "https://zenodo.org/records/1234567";
+    let client = FixtureClient::new()
+        .with_fixture(url, fixture("blog.html"))
+        .with_header(
+            url,
+            header::LINK,
+            r#"<https://doi.org/10.5281/zenodo.1234567>; rel="cite-as""#,
+        );
+
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+
+    assert_eq!(field(&bibtex, "title"), Some("Announcing Rust 1.80.0"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.5281/zenodo.1234567"));
+}
+
+#[tokio::test]
+async fn f1000_version_is_recorded_with_versioned_doi() {
+    let bibtex = extract("https://f1000research.com/articles/9-245/v2", "f1000.html").await;
+
+    assert!(bibtex.starts_with("@article{"));
+    assert_eq!(field(&bibtex, "journal"), Some("F1000Research"));
+    assert_eq!(field(&bibtex, "version"), Some("2"));
+    assert_eq!(
+        field(&bibtex, "doi"),
+        Some("10.12688/f1000research.24567.2")
+    );
+    assert!(
+        field(&bibtex, "note")
+            .unwrap()
+            .starts_with("Version 2; peer reviewed; Accessed: ")
+    );
+}
+
+#[tokio::test]
+async fn f1000_family_version_falls_back_to_url() {
+    let bibtex = extract(
+        "https://wellcomeopenresearch.org/articles/6-112/v3",
+        "wellcome_open_research.html",
+    )
+    .await;
+
+    assert_eq!(field(&bibtex, "version"), Some("3"));
+    assert_eq!(
+        field(&bibtex, "doi"),
+        Some("10.12688/wellcomeopenres.17012.3")
+    );
+}
+
+#[tokio::test]
+async fn biblatex_mode_sets_langid_from_html_lang() {
+    for (url, fixture_name, langid) in [
+        (
+            "https://www.ipsj.or.jp/paper/12345",
+            "shift_jis.html",
+            "japanese",
+        ),
+        (
+            "https://www.uni-example.de/berechenbarkeit",
+            "latin1.html",
+            "german",
+        ),
+        (
+            "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html",
+            "blog.html",
+            "english",
+        ),
+    ] {
+        let client = FixtureClient::new().with_fixture(url, fixture(fixture_name));
+        let bibtex = fetch_and_generate_entry(&client, url, OutputMode::Biblatex)
+            .await
+            .unwrap()
+            .bibtex;
+        assert_eq!(field(&bibtex, "langid"), Some(langid), "{}", fixture_name);
+    }
+
+    let bibtex = extract("https://www.ipsj.or.jp/paper/12345", "shift_jis.html").await;
+    assert_eq!(field(&bibtex, "langid"), None);
+}
+
+#[tokio::test]
+async fn biblatex_cites_web_pages_as_online() {
+    let url = "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html";
+    let client = FixtureClient::new().with_fixture(url, fixture("blog.html"));
+    let bibtex = fetch_and_generate_entry(&client, url, OutputMode::Biblatex)
+        .await
+        .unwrap()
+        .bibtex;
+
+    assert!(bibtex.starts_with("@online{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "url"), Some(url));
+    assert_eq!(field(&bibtex, "howpublished"), None);
+    assert_eq!(field(&bibtex, "note"), None);
+    let urldate = field(&bibtex, "urldate").unwrap();
+    assert!(chrono::NaiveDate::parse_from_str(urldate, "%Y-%m-%d").is_ok());
+
+    // Articles keep their own type.
+    let url = "https://f1000research.com/articles/9-245/v2";
+    let client = FixtureClient::new().with_fixture(url, fixture("f1000.html"));
+    let bibtex = fetch_and_generate_entry(&client, url, OutputMode::Biblatex)
+        .await
+        .unwrap()
+        .bibtex;
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+}
+
+#[test]
+fn biblatex_langid_defaults_to_english() {
+    let metadata = ArticleMetadata {
+        title: "Untitled".into(),
+        ..Default::default()
+    };
+    let bibtex = format_bibtex(metadata, None, OutputMode::Biblatex).unwrap();
+
+    assert_eq!(field(&bibtex, "langid"), Some("english"));
+}
+
+#[test]
+fn bcp47_tags_map_to_biblatex_langids() {
+    for (code, langid) in [
+        ("en", "english"),
+        ("en-GB", "english"),
+        ("fr", "french"),
+        ("de-AT", "german"),
+        ("zh-Hant", "chinese"),
+        ("ja", "japanese"),
+        ("pt_BR", "portuguese"),
+        ("NB", "norwegian"),
+        ("tlh", "english"),
+        ("", "english"),
+    ] {
+        assert_eq!(bcp47_to_biblatex_langid(code), langid, "{}", code);
+    }
+}
+
+#[test]
+fn citation_keys_use_author_last_names() {
+    for (author, key) in [
+        ("Jane Doe", "Doe2025Attention"),
+        ("Doe, Jane", "Doe2025Attention"),
+        ("van der Berg, Jan", "vanderBerg2025Attention"),
+        ("Jane Doe and Smith, John", "Doe.Smith2025Attention"),
+        (
+            "Jane Doe and John Smith and Ana Jones",
+            "Doe.Smith.Jones2025Attention",
+        ),
+        (
+            "Jane Doe and John Smith and Ana Jones and Li Wei",
+            "Doe+2025Attention",
+        ),
+        ("", "Unknown2025Attention"),
+    ] {
+        assert_eq!(
+            generate_citation_key(author, "2025", "Attention Is All You Need"),
+            key,
+            "{}",
+            author
+        );
+    }
+}
+
+#[tokio::test]
+async fn taylor_francis_keeps_last_first_authors_and_strips_doi_prefix() {
+    let url = "https://www.tandfonline.com/doi/full/10.1080/09640568.2021.1900000";
+    let client = FixtureClient::new().with_fixture(url, fixture("tandfonline.html"));
+
+    let metadata = scrape_metadata(&client, url).await.unwrap();
+    assert_eq!(metadata.open_access, Some(true));
+
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+    assert!(bibtex.starts_with("@article{Okonkwo.Fernández2022Urban,"));
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Okonkwo, Chidi and Fernández, Lucía")
+    );
+    assert_eq!(field(&bibtex, "doi"), Some("10.1080/09640568.2021.1900000"));
+    assert_eq!(field(&bibtex, "year"), Some("2022"));
+    assert_eq!(field(&bibtex, "pages"), Some("601--627"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Routledge"));
+}
+
+#[test]
+fn taylor_francis_doi_is_read_from_url() {
+    let url = url::Url::parse("https://www.tandfonline.com/doi/abs/10.1080/00207543.2020.1750000")
+        .unwrap();
+
+    assert_eq!(
+        bibtexter::sites::taylor_francis::doi_from_url(&url).as_deref(),
+        Some("10.1080/00207543.2020.1750000")
+    );
+}
+
+#[tokio::test]
+async fn oup_reads_nested_journal_and_highwire_pages() {
+    let bibtex = extract(
+        "https://academic.oup.com/brain/article/147/4/1450/7600000",
+        "oup.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@article{Whitcombe.Ortiz2024Sleep,"));
+    assert_eq!(field(&bibtex, "journal"), Some("Brain"));
+    assert_eq!(field(&bibtex, "year"), Some("2024"));
+    assert_eq!(field(&bibtex, "pages"), Some("1450--1463"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.1093/brain/awae012"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Oxford University Press"));
+}
+
+#[tokio::test]
+async fn oup_journal_falls_back_to_prism() {
+    let bibtex = extract(
+        "https://academic.oup.com/brain/article/147/4/1450/7600000",
+        "oup_no_jsonld.html",
+    )
+    .await;
+
+    assert!(bibtex.starts_with("@article{"));
+    assert_eq!(field(&bibtex, "journal"), Some("Brain"));
+}
+
+#[tokio::test]
+async fn body_markup_supplies_missing_authors() {
+    for (fixture_name, author) in [
+        ("byline_hcard.html", "Maya Lindgren"),
+        ("byline_microdata.html", "Omar Haddad"),
+        ("byline_microdata_plain.html", "Grace Adeyemi"),
+        ("byline_rel_author.html", "Tomasz Nowak"),
+        ("byline_cite.html", "Ines Carvalho"),
+    ] {
+        let bibtex = extract("https://blog.example.org/post", fixture_name).await;
+        assert_eq!(field(&bibtex, "author"), Some(author), "{}", fixture_name);
+    }
+}
+
+#[tokio::test]
+async fn news_sections_and_tags_become_keywords() {
+    let url = "https://news.example.com/2025/06/11/chip-cooling";
+    let client = FixtureClient::new().with_fixture(url, fixture("article_section_news.html"));
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+
+    assert_eq!(
+        metadata.keywords,
+        vec!["Technology", "Semiconductors", "Data centers"]
+    );
+    assert_eq!(metadata.chapter, None);
+}
+
+#[tokio::test]
+async fn journal_sections_become_chapters() {
+    let url = "https://journals.example.org/jse/article/42";
+    let client = FixtureClient::new().with_fixture(url, fixture("article_section_journal.html"));
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+
+    assert_eq!(metadata.chapter.as_deref(), Some("Research Article"));
+    assert_eq!(metadata.keywords, vec!["drought", "microbiome"]);
+}
+
+#[tokio::test]
+async fn journal_issns_prefer_the_electronic_edition() {
+    let url = "https://estuaries.example.org/articles/17";
+    let client = FixtureClient::new()
+        .with_fixture(url, fixture("journal_issns.html"))
+        .with_fixture(
+            "https://portal.issn.org/resource/ISSN/3456-7895?format=json",
+            fixture("issn_portal_3456-7895.json"),
+        );
+    let metadata = fetch_metadata(&client, url).await.unwrap();
+
+    assert_eq!(metadata.issn.as_deref(), Some("2345-6787"));
+    assert_eq!(metadata.eissn.as_deref(), Some("3456-7895"));
+    assert_eq!(metadata.issn_l.as_deref(), Some("2345-6787"));
+    assert_eq!(metadata.publisher, "Coastal Sciences Society");
+
+    let bibtex = format_bibtex(metadata, Some(url), OutputMode::Bibtex).unwrap();
+    assert_eq!(field(&bibtex, "issn"), Some("3456-7895"));
+}
+
+#[tokio::test]
+async fn untitled_page_takes_its_title_from_the_url() {
+    let url = "https://example.com/blog/machine-learning-intro";
+    let client = FixtureClient::new().with_fixture(url, fixture("untitled.html"));
+
+    let entry = fetch_and_generate_entry(&client, url, OutputMode::Bibtex)
+        .await
+        .unwrap();
+
+    assert!(
+        entry
+            .bibtex
+            .starts_with(&format!("% {}\n@", URL_TITLE_WARNING)),
+        "{}",
+        entry.bibtex
+    );
+    assert_eq!(
+        field(&entry.bibtex, "title"),
+        Some("Machine Learning Intro")
+    );
+    assert!(require_real_title(entry.clone(), false).is_ok());
+    assert!(require_real_title(entry, true).is_err());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bibtexter::bibtex::OutputMode;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::google_scholar::{
+    ScholarPublication, parse_profile, profile_user, scholar_profile_bibtex,
+};
+use common::{field, fixture};
+use url::Url;
+
+const PROFILE_URL: &str = "https://scholar.google.com/citations?user=AbC123xyz&hl=en";
+
+fn profile_page() -> scraper::Html {
+    let html = std::fs::read_to_string(fixture("scholar_profile.html")).unwrap();
+    scraper::Html::parse_document(&html)
+}
+
+#[test]
+fn finds_the_user_in_profile_urls() {
+    let user = |url: &str| profile_user(&Url::parse(url).unwrap());
+    assert_eq!(user(PROFILE_URL).as_deref(), Some("AbC123xyz"));
+    assert_eq!(
+        user("https://scholar.google.co.uk/citations?hl=en&user=AbC123xyz").as_deref(),
+        Some("AbC123xyz")
+    );
+    assert_eq!(user("https://scholar.google.com/scholar?q=soil"), None);
+    assert_eq!(user("https://example.com/citations?user=AbC123xyz"), None);
+}
+
+#[test]
+fn reads_publication_rows() {
+    let publications = parse_profile(&profile_page(), 10);
+
+    assert_eq!(publications.len(), 3);
+    assert_eq!(
+        publications[0],
+        ScholarPublication {
+            title: "Deep learning for soil moisture retrieval".to_string(),
+            authors: vec!["J Doe".into(), "R Roe".into(), "A Smith".into()],
+            venue: Some("Remote Sensing of Environment 250, 112-130".to_string()),
+            year: Some("2020".to_string()),
+            cited_by: Some(412),
+            url: Some(
+                "https://scholar.google.com/citations?view_op=view_citation&hl=en&user=AbC123xyz&citation_for_view=AbC123xyz:u5HHmVD_uO8C"
+                    .to_string()
+            ),
+        }
+    );
+    assert_eq!(publications[1].venue, None);
+    assert_eq!(publications[1].year, None);
+    assert_eq!(publications[1].cited_by, None);
+
+    assert_eq!(parse_profile(&profile_page(), 2).len(), 2);
+}
+
+#[tokio::test]
+async fn profiles_are_read_only_when_enabled() {
+    let client = FixtureClient::new().with_fixture(
+        "https://scholar.google.com/citations?user=AbC123xyz&hl=en&cstart=0&pagesize=100",
+        fixture("scholar_profile.html"),
+    );
+    assert!(
+        scholar_profile_bibtex(&client, PROFILE_URL, 2, OutputMode::Bibtex)
+            .await
+            .is_err()
+    );
+
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_ENABLE_SCHOLAR", "true") };
+    let bibtex = scholar_profile_bibtex(&client, PROFILE_URL, 2, OutputMode::Bibtex)
+        .await
+        .unwrap();
+
+    let entries: Vec<&str> = bibtex.split("\n\n").collect();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].starts_with("@article{"), "{}", bibtex);
+    assert_eq!(
+        field(entries[0], "journal"),
+        Some("Remote Sensing of Environment 250, 112-130")
+    );
+    assert_eq!(
+        field(entries[0], "author"),
+        Some("J Doe and R Roe and A Smith")
+    );
+    assert!(
+        field(entries[0], "note")
+            .unwrap()
+            .starts_with("Cited by 412 on Google Scholar; Accessed: ")
+    );
+    assert!(entries[1].starts_with("@misc{"), "{}", bibtex);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use bibtexter::http::{ClientConfig, DomainDelays, FixtureClient, HttpClient, ThrottledClient};
+use common::fixture;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[test]
+fn client_config_reads_env_and_falls_back_to_defaults() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe {
+        std::env::set_var("BIBTEXTER_POOL_SIZE", "64");
+        std::env::set_var("BIBTEXTER_CONNECT_TIMEOUT_MS", "2500");
+        std::env::set_var("BIBTEXTER_READ_TIMEOUT_MS", "soon");
+    }
+
+    let config = ClientConfig::from_env();
+
+    assert_eq!(config.pool_size, 64);
+    assert_eq!(config.connect_timeout, Duration::from_millis(2500));
+    assert_eq!(config.read_timeout, ClientConfig::default().read_timeout);
+    assert!(config.build_client().is_ok());
+}
+
+#[test]
+fn domain_delays_read_env_and_match_subdomains() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe {
+        std::env::set_var("BIBTEXTER_DOMAIN_DELAY_MS", "100");
+        std::env::set_var(
+            "BIBTEXTER_DOMAIN_DELAYS",
+            "pubs.acs.org=2000, jstor.org = 1500,broken=soon",
+        );
+    }
+
+    let delays = DomainDelays::from_env();
+
+    assert_eq!(
+        delays.delay_for("pubs.acs.org"),
+        Duration::from_millis(2000)
+    );
+    assert_eq!(
+        delays.delay_for("www.jstor.org"),
+        Duration::from_millis(1500)
+    );
+    assert_eq!(delays.delay_for("broken"), Duration::from_millis(100));
+    assert_eq!(delays.delay_for("arxiv.org"), Duration::from_millis(100));
+}
+
+#[test]
+fn reservations_are_spaced_one_interval_apart() {
+    let delays = DomainDelays::new(
+        Duration::ZERO,
+        HashMap::from([("pubs.acs.org".to_string(), Duration::from_secs(2))]),
+    );
+
+    assert_eq!(delays.reserve("pubs.acs.org"), Duration::ZERO);
+    let second = delays.reserve("pubs.acs.org");
+    assert!(second > Duration::from_millis(1900) && second <= Duration::from_secs(2));
+    let third = delays.reserve("pubs.acs.org");
+    assert!(third > Duration::from_millis(3900));
+    assert_eq!(delays.reserve("arxiv.org"), Duration::ZERO);
+}
+
+#[tokio::test]
+async fn throttled_client_waits_between_requests_to_a_host() {
+    let url = "https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html";
+    let client = ThrottledClient::new(
+        FixtureClient::new().with_fixture(url, fixture("blog.html")),
+        DomainDelays::new(Duration::from_millis(50), HashMap::new()),
+    );
+
+    let started = Instant::now();
+    assert!(client.get(url, None).await.unwrap().status.is_success());
+    assert!(client.get(url, None).await.unwrap().status.is_success());
+
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,143 @@
+//! Checks extraction from live pages against `tests/known_urls.toml`. These tests make
+//! real network calls, so they are ignored by default; run them with
+//! `make test-integration`.
+
+use bibtexter::AppState;
+use bibtexter::enrich::{BibItem, parse_bib};
+use bibtexter::http::ClientConfig;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+/// One `[[test]]` table: the `url` to extract and the `expected_*` fields.
+type KnownUrl = HashMap<String, String>;
+
+/// Reads the subset of TOML the file uses: `[[test]]` headers, `key = "string"` pairs
+/// and `#` comments.
+fn read_known_urls() -> Vec<KnownUrl> {
+    let path = format!("{}/tests/known_urls.toml", env!("CARGO_MANIFEST_DIR"));
+    let text = std::fs::read_to_string(&path).unwrap();
+    let mut tests: Vec<KnownUrl> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "[[test]]" {
+            tests.push(HashMap::new());
+            continue;
+        }
+        let parsed = line.split_once('=').and_then(|(key, value)| {
+            let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+            Some((
+                key.trim().to_string(),
+                value.replace("\\\"", "\"").replace("\\\\", "\\"),
+            ))
+        });
+        match (parsed, tests.last_mut()) {
+            (Some((key, value)), Some(test)) => {
+                test.insert(key, value);
+            }
+            _ => panic!("{}:{}: cannot read `{}`", path, number + 1, line),
+        }
+    }
+    tests
+}
+
+/// Serves the app on a free local port and returns its address.
+async fn start_server() -> String {
+    let state = AppState::new(ClientConfig::from_env().build_client().unwrap());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            bibtexter::app(state).into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+    address
+}
+
+/// Lowercases a title and drops BibTeX braces, which doi.org adds to protect capitals.
+fn comparable_title(title: &str) -> String {
+    title.replace(['{', '}'], "").to_lowercase()
+}
+
+/// Compares an extracted entry with a test's expectations, describing each mismatch.
+fn mismatches(test: &KnownUrl, bibtex: &str) -> Vec<String> {
+    let Some(BibItem::Entry(entry)) = parse_bib(bibtex)
+        .into_iter()
+        .find(|item| matches!(item, BibItem::Entry(_)))
+    else {
+        return vec![format!("no entry in {:?}", bibtex)];
+    };
+    let mut problems = Vec::new();
+    let mut check = |expected_key: &str, actual: Option<&str>, normalize: fn(&str) -> String| {
+        if let Some(expected) = test.get(expected_key)
+            && actual.map(normalize) != Some(normalize(expected))
+        {
+            problems.push(format!(
+                "{}: expected {:?}, got {:?}",
+                expected_key, expected, actual
+            ));
+        }
+    };
+    check("expected_title", entry.field("title"), comparable_title);
+    check("expected_doi", entry.field("doi"), str::to_lowercase);
+    check(
+        "expected_type",
+        Some(entry.entry_type.as_str()),
+        str::to_lowercase,
+    );
+    problems
+}
+
+#[tokio::test]
+#[ignore = "fetches live pages; run with `make test-integration`"]
+async fn known_urls_extract_as_expected() {
+    let tests = read_known_urls();
+    assert!(!tests.is_empty());
+    let address = start_server().await;
+    let client = reqwest::Client::new();
+
+    let mut failures = Vec::new();
+    for test in &tests {
+        let url = test.get("url").expect("every [[test]] needs a url");
+        let response = client
+            .get(format!("{}/api/bibtex", address))
+            .query(&[("url", url)])
+            .send()
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.text().await.unwrap();
+        if !status.is_success() {
+            failures.push(format!("{}: status {}: {}", url, status, body));
+            continue;
+        }
+        failures.extend(
+            mismatches(test, &body)
+                .into_iter()
+                .map(|problem| format!("{}: {}", url, problem)),
+        );
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} known URLs failed:\n{}",
+        failures.len(),
+        tests.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn known_urls_file_is_readable() {
+    let tests = read_known_urls();
+    assert!(!tests.is_empty());
+    assert!(tests.iter().all(|test| test.contains_key("url")));
+    assert!(tests.iter().all(|test| {
+        test.keys()
+            .all(|key| key == "url" || key.starts_with("expected_"))
+    }));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use bibtexter::bibtex::OutputMode;
+use bibtexter::error::AppError;
+use bibtexter::http::FixtureClient;
+use bibtexter::isbn::{
+    bibtex_from_isbn, isbn10_to_isbn13, lookup_isbn, normalize_isbn, parse_worldcat_atom,
+};
+use common::{field, fixture};
+
+const OPEN_LIBRARY: &str =
+    "https://openlibrary.org/api/books?bibkeys=ISBN:9780262033848&format=json&jscmd=data";
+const GOOGLE_BOOKS: &str = "https://www.googleapis.com/books/v1/volumes?q=isbn:9780262033848";
+
+#[test]
+fn normalizes_and_validates_isbns() {
+    assert_eq!(
+        normalize_isbn("978-0-262-03384-8").as_deref(),
+        Some("9780262033848")
+    );
+    assert_eq!(
+        normalize_isbn("ISBN 0-262-03384-4").as_deref(),
+        Some("0262033844")
+    );
+    assert_eq!(normalize_isbn("080442957x").as_deref(), Some("080442957X"));
+
+    assert_eq!(normalize_isbn("978-0-262-03384-9"), None);
+    assert_eq!(normalize_isbn("0262033845"), None);
+    assert_eq!(normalize_isbn("12345"), None);
+}
+
+#[test]
+fn converts_isbn10_to_isbn13() {
+    assert_eq!(isbn10_to_isbn13("0262033844"), "9780262033848");
+    assert_eq!(isbn10_to_isbn13("080442957X"), "9780804429573");
+    assert_eq!(isbn10_to_isbn13("9780262033848"), "9780262033848");
+}
+
+#[tokio::test]
+async fn isbn10_is_looked_up_as_isbn13() {
+    let client = FixtureClient::new().with_fixture(OPEN_LIBRARY, fixture("openlibrary_isbn.json"));
+
+    let metadata = lookup_isbn(&client, "0-262-03384-4").await.unwrap();
+
+    assert_eq!(metadata.title, "Introduction to Algorithms");
+    assert_eq!(metadata.isbn.as_deref(), Some("9780262033848"));
+}
+
+#[test]
+fn reads_worldcat_atom_entries() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>OCLC Worldcat Search: bn:9780262033848</title>
+  <entry>
+    <author><name>Cormen, Thomas H.</name></author>
+    <author><name>Leiserson, Charles E.</name></author>
+    <title>Introduction to algorithms</title>
+    <id>http://worldcat.org/oclc/311310321</id>
+  </entry>
+</feed>"#;
+
+    let metadata = parse_worldcat_atom(xml, "9780262033848").unwrap();
+
+    assert_eq!(metadata.title, "Introduction to algorithms");
+    assert_eq!(
+        metadata.author,
+        "Cormen, Thomas H. and Leiserson, Charles E."
+    );
+    assert_eq!(metadata.isbn.as_deref(), Some("9780262033848"));
+    assert!(parse_worldcat_atom("<feed></feed>", "9780262033848").is_none());
+}
+
+#[tokio::test]
+async fn open_library_record_makes_a_book_entry() {
+    let client = FixtureClient::new()
+        .with_fixture(OPEN_LIBRARY, fixture("openlibrary_isbn.json"))
+        .with_fixture(GOOGLE_BOOKS, fixture("google_books_isbn.json"));
+
+    let bibtex = bibtex_from_isbn(&client, "978-0-262-03384-8", OutputMode::Bibtex)
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@book{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "title"), Some("Introduction to Algorithms"));
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Thomas H. Cormen and Charles E. Leiserson and Ronald L. Rivest and Clifford Stein")
+    );
+    assert_eq!(field(&bibtex, "publisher"), Some("MIT Press"));
+    assert_eq!(field(&bibtex, "address"), Some("Cambridge, Mass"));
+    assert_eq!(field(&bibtex, "year"), Some("2009"));
+    assert_eq!(field(&bibtex, "numpages"), Some("1292"));
+    assert_eq!(field(&bibtex, "isbn"), Some("9780262033848"));
+}
+
+#[tokio::test]
+async fn falls_back_to_google_books() {
+    let client = FixtureClient::new()
+        .with_fixture(OPEN_LIBRARY, fixture("openlibrary_empty.json"))
+        .with_fixture(GOOGLE_BOOKS, fixture("google_books_isbn.json"));
+
+    let metadata = lookup_isbn(&client, "9780262033848").await.unwrap();
+
+    assert_eq!(metadata.title, "Introduction to Algorithms: Third Edition");
+    assert_eq!(metadata.publisher, "MIT Press");
+    assert_eq!(metadata.year, "2009");
+    assert_eq!(metadata.pages, Some(1313));
+    assert_eq!(metadata.language.as_deref(), Some("en"));
+}
+
+#[tokio::test]
+async fn invalid_isbn_is_not_looked_up() {
+    assert!(matches!(
+        lookup_isbn(&FixtureClient::new(), "not-an-isbn").await,
+        Err(AppError::InvalidIsbn(_))
+    ));
+}
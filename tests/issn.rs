@@ -0,0 +1,53 @@
+use bibtexter::issn::{extract_issn, page_issns};
+use scraper::Html;
+
+#[test]
+fn extracts_and_validates_issns() {
+    assert_eq!(extract_issn("0028-0836").as_deref(), Some("0028-0836"));
+    assert_eq!(
+        extract_issn("ISSN 1476 4687 (Online)").as_deref(),
+        Some("1476-4687")
+    );
+    assert_eq!(extract_issn("2050084x").as_deref(), Some("2050-084X"));
+
+    // Wrong check digits.
+    assert_eq!(extract_issn("0028-0837"), None);
+    assert_eq!(extract_issn("2050-0840"), None);
+    assert_eq!(extract_issn("12345"), None);
+}
+
+#[test]
+fn skips_invalid_candidates() {
+    assert_eq!(
+        extract_issn("Print: 1234-5678, Online: 1234-5679").as_deref(),
+        Some("1234-5679")
+    );
+}
+
+#[test]
+fn page_issns_tell_print_from_electronic() {
+    let document = Html::parse_document(
+        r#"<meta name="prism.issn" content="0028-0836">
+        <meta name="prism.eIssn" content="1476-4687">"#,
+    );
+    assert_eq!(
+        page_issns(&document),
+        (Some("0028-0836".to_string()), Some("1476-4687".to_string()))
+    );
+
+    // A repeated `citation_issn` is read as print, then electronic.
+    let document = Html::parse_document(
+        r#"<meta name="citation_issn" content="0028-0836">
+        <meta name="citation_issn" content="1476-4687">"#,
+    );
+    assert_eq!(
+        page_issns(&document),
+        (Some("0028-0836".to_string()), Some("1476-4687".to_string()))
+    );
+
+    let document = Html::parse_document(
+        r#"<meta name="citation_fulltext_issn" content="1476-4687">
+        <meta name="citation_issn" content="1476-4687">"#,
+    );
+    assert_eq!(page_issns(&document), (None, Some("1476-4687".to_string())));
+}
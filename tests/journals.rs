@@ -0,0 +1,35 @@
+use bibtexter::journals::{expand_journal_abbreviations, resolve_abbreviation};
+
+#[test]
+fn resolves_abbreviations_and_acronyms() {
+    let jacm = resolve_abbreviation("J. ACM").unwrap();
+    assert_eq!(jacm.abbrev, "J. ACM");
+    assert_eq!(jacm.full, "Journal of the ACM");
+    assert_eq!(jacm.issn.as_deref(), Some("0004-5411"));
+
+    assert_eq!(
+        resolve_abbreviation("CACM").unwrap().full,
+        "Communications of the ACM"
+    );
+    assert_eq!(
+        resolve_abbreviation("proc vldb endow").unwrap().full,
+        "Proceedings of the VLDB Endowment"
+    );
+    assert_eq!(resolve_abbreviation("Unheard Of J."), None);
+}
+
+#[test]
+fn expands_journal_field_in_either_layout() {
+    assert_eq!(
+        expand_journal_abbreviations("@article{a,\n  journal = {Commun. ACM},\n}"),
+        "@article{a,\n  journal = {Communications of the ACM},\n}"
+    );
+    assert_eq!(
+        expand_journal_abbreviations("@article{a, journal={J. ACM}, booktitle={J. ACM}}"),
+        "@article{a, journal={Journal of the ACM}, booktitle={J. ACM}}"
+    );
+    assert_eq!(
+        expand_journal_abbreviations("@article{a, journal={Nowhere J.}}"),
+        "@article{a, journal={Nowhere J.}}"
+    );
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use bibtexter::api::known_sites_handler;
+use bibtexter::extract::fetch_metadata;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::known::KNOWN_SITES;
+use common::fixture;
+use url::Url;
+
+// The recorded responses each known site's example page needs: the page itself, or
+// the API the site's extractor calls.
+const RECORDINGS: &[(&str, &str)] = &[
+    (
+        "https://academic.oup.com/brain/article/147/4/1450/7600000",
+        "oup.html",
+    ),
+    ("https://arxiv.org/abs/1706.03762", "arxiv.html"),
+    (
+        "https://api.mendeley.com/datasets/8gx2mk6x7w",
+        "mendeley_dataset.json",
+    ),
+    (
+        "https://datadryad.org/api/v2/datasets/doi%3A10.5061%2Fdryad.2bvq83bn5",
+        "dryad_dataset.json",
+    ),
+    ("https://dl.acm.org/doi/10.1145/2692956.2663188", "acm.html"),
+    (
+        "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        "wikipedia.html",
+    ),
+    ("https://f1000research.com/articles/9-245/v2", "f1000.html"),
+    (
+        "https://api.figshare.com/v2/articles/5616445",
+        "figshare_article.json",
+    ),
+    ("https://github.com/rust-lang/rust", "github.html"),
+    ("https://ieeexplore.ieee.org/document/7780459", "ieee.html"),
+    (
+        "https://link.springer.com/article/10.1007/s10994-021-06000-0",
+        "springer_shared_it.html",
+    ),
+    (
+        "https://www.nature.com/articles/d41586-024-02391-9",
+        "nature.html",
+    ),
+    (
+        "https://www.nber.org/api/v1/working_paper/contentType/working_paper/id/w29000",
+        "nber_paper.json",
+    ),
+    (
+        "https://www.overleaf.com/read/kzxqvbdmnrtp",
+        "overleaf_read.html",
+    ),
+    (
+        "https://requests.readthedocs.io/en/v2.31.0/user/quickstart/",
+        "readthedocs.html",
+    ),
+    (
+        "https://api.semanticscholar.org/graph/v1/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776?fields=title,authors,year,venue,externalIds,publicationTypes,openAccessPdf",
+        "s2_paper.json",
+    ),
+    (
+        "https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4712345",
+        "ssrn.html",
+    ),
+    (
+        "https://www.tandfonline.com/doi/full/10.1080/09640568.2021.1900000",
+        "tandfonline.html",
+    ),
+    ("https://www.jstor.org/stable/1879431", "coins.html"),
+    ("https://zenodo.org/records/11200345", "zenodo_dataset.html"),
+];
+
+#[tokio::test]
+async fn known_sites_match_their_recorded_pages() {
+    let client = RECORDINGS
+        .iter()
+        .fold(FixtureClient::new(), |client, (url, name)| {
+            client.with_fixture(url, fixture(name))
+        });
+
+    for site in KNOWN_SITES {
+        let host = Url::parse(site.example_url)
+            .unwrap()
+            .host_str()
+            .unwrap()
+            .to_string();
+        assert!(
+            host == site.domain || host.ends_with(&format!(".{}", site.domain)),
+            "{}'s example is on {}",
+            site.domain,
+            host
+        );
+
+        let metadata = fetch_metadata(&client, site.example_url)
+            .await
+            .unwrap_or_else(|err| panic!("{}: {:?}", site.domain, err));
+        assert_eq!(metadata.strategy, site.strategy, "{}", site.domain);
+        assert_eq!(metadata.entry_type, site.entry_type, "{}", site.domain);
+    }
+}
+
+#[tokio::test]
+async fn known_sites_are_listed_alphabetically_with_their_limitations() {
+    let sites = serde_json::to_value(known_sites_handler().await.0).unwrap();
+    let sites = sites.as_array().unwrap();
+
+    let domains: Vec<_> = sites
+        .iter()
+        .map(|site| site["domain"].as_str().unwrap())
+        .collect();
+    let mut sorted = domains.clone();
+    sorted.sort();
+    assert_eq!(domains, sorted);
+
+    let springer = sites
+        .iter()
+        .find(|site| site["domain"] == "link.springer.com")
+        .unwrap();
+    assert_eq!(springer["strategy"], "meta_tags");
+    assert_eq!(springer["quality"], "good");
+    assert_eq!(springer["entry_type"], "article");
+    assert_eq!(springer["limitations"], "may miss volume for some articles");
+    assert!(springer.get("example_url").is_none());
+
+    let zenodo = sites
+        .iter()
+        .find(|site| site["domain"] == "zenodo.org")
+        .unwrap();
+    assert!(zenodo.get("limitations").is_none());
+}
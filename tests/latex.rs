@@ -0,0 +1,59 @@
+use bibtexter::latex::strip_latex_commands;
+
+#[test]
+fn strips_braces_and_formatting_commands() {
+    assert_eq!(
+        strip_latex_commands(r"{A Survey of {\LaTeX} Tools}"),
+        "A Survey of LaTeX Tools"
+    );
+    assert_eq!(
+        strip_latex_commands(r"On \textit{Drosophila} and \emph{in vivo} \textbf{Methods}"),
+        "On Drosophila and in vivo Methods"
+    );
+    assert_eq!(
+        strip_latex_commands(r"Smith \& Sons, 50\% off"),
+        "Smith & Sons, 50% off"
+    );
+}
+
+#[test]
+fn expands_accent_commands() {
+    assert_eq!(strip_latex_commands(r"Caf\'{e}"), "Café");
+    assert_eq!(
+        strip_latex_commands(r#"Erd\H{o}s and G{\"o}del"#),
+        "Erdős and Gödel"
+    );
+    assert_eq!(strip_latex_commands(r"Dvo\v{r}\'ak"), "Dvořák");
+    assert_eq!(strip_latex_commands(r"Fran\c coise"), "Françoise");
+    assert_eq!(
+        strip_latex_commands(r"Mart{\'\i}nez and Ma\~{n}ana"),
+        "Martínez and Mañana"
+    );
+    assert_eq!(
+        strip_latex_commands(r"Stra{\ss}e, {\O}re, {\aa}"),
+        "Straße, Øre, å"
+    );
+}
+
+#[test]
+fn converts_math_scripts_to_unicode() {
+    assert_eq!(strip_latex_commands(r"Splitting H$_2$O"), "Splitting H₂O");
+    assert_eq!(
+        strip_latex_commands(r"CO$^{2}$ and x$^{n+1}$"),
+        "CO² and xⁿ⁺¹"
+    );
+    assert_eq!(strip_latex_commands(r"$\alpha$-helices"), "α-helices");
+    // Letters with no subscript form are left as they are.
+    assert_eq!(strip_latex_commands(r"$T_{max}$"), "Tmax");
+}
+
+#[test]
+fn converts_dashes_and_spacing() {
+    assert_eq!(strip_latex_commands("pages 10--20"), "pages 10–20");
+    assert_eq!(strip_latex_commands("yes---no"), "yes—no");
+    assert_eq!(
+        strip_latex_commands("Dr.~Who  and\n  friends"),
+        "Dr. Who and friends"
+    );
+    assert_eq!(strip_latex_commands("plain text"), "plain text");
+}
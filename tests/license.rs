@@ -0,0 +1,42 @@
+use bibtexter::license::{is_creative_commons, normalize_license};
+
+#[test]
+fn normalizes_creative_commons_urls_codes_and_names() {
+    assert_eq!(
+        normalize_license("https://creativecommons.org/licenses/by-nc-sa/4.0/"),
+        "CC-BY-NC-SA-4.0"
+    );
+    assert_eq!(
+        normalize_license("http://creativecommons.org/publicdomain/zero/1.0/"),
+        "CC0-1.0"
+    );
+    assert_eq!(normalize_license("CC BY 4.0"), "CC-BY-4.0");
+    assert_eq!(normalize_license("cc-by-nd-3.0"), "CC-BY-ND-3.0");
+    assert_eq!(
+        normalize_license("Creative Commons Attribution-NonCommercial 4.0 International"),
+        "CC-BY-NC-4.0"
+    );
+}
+
+#[test]
+fn normalizes_common_software_and_data_licenses() {
+    assert_eq!(normalize_license("The MIT License"), "MIT");
+    assert_eq!(
+        normalize_license("Apache License, Version 2.0"),
+        "Apache-2.0"
+    );
+    assert_eq!(
+        normalize_license("http://opendefinition.org/licenses/odc-odbl/"),
+        "ODbL-1.0"
+    );
+}
+
+#[test]
+fn leaves_unrecognised_licenses_alone() {
+    assert_eq!(
+        normalize_license("All rights reserved"),
+        "All rights reserved"
+    );
+    assert!(!is_creative_commons("MIT"));
+    assert!(is_creative_commons("CC0-1.0"));
+}
@@ -0,0 +1,53 @@
+use bibtexter::note::{DEFAULT_NOTE_TEMPLATE, NoteContext, NoteTemplate};
+use chrono::{Local, TimeZone};
+
+fn context() -> NoteContext<'static> {
+    NoteContext {
+        accessed: Local.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        site_name: "arxiv.org",
+        doi: Some("10.48550/arXiv.1706.03762"),
+    }
+}
+
+#[test]
+fn default_template_matches_access_date_note() {
+    let template: NoteTemplate = DEFAULT_NOTE_TEMPLATE.parse().unwrap();
+
+    assert_eq!(template.render(&context()), "Accessed: 2025-01-15");
+}
+
+#[test]
+fn custom_template_fills_every_placeholder() {
+    let template: NoteTemplate = "Retrieved {accessed_date} from {site_name} (doi:{doi})"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        template.render(&context()),
+        "Retrieved 2025-01-15 from arxiv.org (doi:10.48550/arXiv.1706.03762)"
+    );
+
+    let template: NoteTemplate = "{accessed_iso}".parse().unwrap();
+    assert!(
+        template
+            .render(&context())
+            .starts_with("2025-01-15T10:30:00")
+    );
+}
+
+#[test]
+fn missing_doi_renders_empty() {
+    let template: NoteTemplate = "doi: {doi}".parse().unwrap();
+    let context = NoteContext {
+        doi: None,
+        ..context()
+    };
+
+    assert_eq!(template.render(&context), "doi: ");
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    for template in ["Seen {accessed}", "Retrieved {site_name", "{}"] {
+        assert!(template.parse::<NoteTemplate>().is_err(), "{}", template);
+    }
+}
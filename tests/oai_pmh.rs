@@ -0,0 +1,87 @@
+mod common;
+
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::oai_pmh::{parse_oai_dc, record_identifier};
+use common::{field, fixture};
+use url::Url;
+
+#[test]
+fn derives_oai_identifiers_from_landing_pages() {
+    let id = |url: &str| record_identifier(&Url::parse(url).unwrap());
+
+    assert_eq!(
+        id("https://dspace.mit.edu/handle/1721.1/12345"),
+        Some("oai:dspace.mit.edu:1721.1/12345".to_string())
+    );
+    assert_eq!(
+        id("https://eprints.example.ac.uk/id/eprint/4821/"),
+        Some("oai:eprints.example.ac.uk:4821".to_string())
+    );
+    assert_eq!(
+        id("https://digitalcommons.example.edu/etd/312/"),
+        Some("oai:digitalcommons.example.edu:etd-312".to_string())
+    );
+    assert_eq!(id("https://eprints.example.ac.uk/about.html"), None);
+}
+
+#[test]
+fn parses_dublin_core_record() {
+    let xml = std::fs::read_to_string(fixture("oai_getrecord.xml")).unwrap();
+    let metadata = parse_oai_dc(&xml).unwrap();
+
+    assert_eq!(metadata.title, "Tidal Energy & Coastal Sediment Transport");
+    assert_eq!(metadata.author, "Hughes, Gareth and Nakamura, Emi");
+    assert_eq!(metadata.year, "2021");
+    assert_eq!(
+        metadata.doi.as_deref(),
+        Some("10.1016/j.renene.2021.09.001")
+    );
+    assert_eq!(metadata.keywords, ["oceanography", "renewable energy"]);
+    assert_eq!(metadata.language.as_deref(), Some("en"));
+}
+
+#[tokio::test]
+async fn repository_item_is_harvested_over_discovered_endpoint() {
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://eprints.example.ac.uk/oai2?verb=Identify",
+            fixture("oai_identify.xml"),
+        )
+        .with_fixture(
+            "https://eprints.example.ac.uk/oai2?verb=GetRecord&metadataPrefix=oai_dc&identifier=oai%3Aeprints.example.ac.uk%3A4821",
+            fixture("oai_getrecord.xml"),
+        );
+
+    let bibtex =
+        fetch_and_generate_bibtex(&client, "https://eprints.example.ac.uk/id/eprint/4821/")
+            .await
+            .unwrap();
+
+    assert!(bibtex.starts_with("@misc{Hughes.Nakamura2021Tidal,"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Example University"));
+}
+
+#[tokio::test]
+async fn configured_endpoint_is_used_for_any_host() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe {
+        std::env::set_var(
+            "BIBTEXTER_OAI_ENDPOINTS",
+            "https://research.example.org/cgi/oai2, https://other.example.org/oai",
+        )
+    };
+    let client = FixtureClient::new().with_fixture(
+        "https://research.example.org/cgi/oai2?verb=GetRecord&metadataPrefix=oai_dc&identifier=oai%3Aresearch.example.org%3A4821",
+        fixture("oai_getrecord.xml"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(&client, "https://research.example.org/4821/")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Tidal Energy & Coastal Sediment Transport")
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use bibtexter::bibtex::BibtexEntryType;
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::overrides::{bundled, find_override, parse_override};
+use common::{field, fixture};
+
+#[test]
+fn parses_override_file() {
+    let site = parse_override(
+        "example",
+        r#"
+        # A comment
+        hosts = ["example.org", 'papers.example.org']  # trailing comment
+        entry_type = "inproceedings"
+        ignore = ["abstract"]
+
+        [selectors]
+        title = "h1.paper-title"
+        author = "meta[name=\"byline\"]@content"
+
+        [[tests]]
+        url = "https://example.org/paper/1"
+        title = "A Paper"
+
+        [[tests]]
+        url = "https://example.org/paper/2"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(site.hosts, ["example.org", "papers.example.org"]);
+    assert_eq!(site.entry_type, Some(BibtexEntryType::InProceedings));
+    assert_eq!(site.ignore, ["abstract"]);
+    assert_eq!(site.selectors["title"], "h1.paper-title");
+    assert_eq!(site.selectors["author"], "meta[name=\"byline\"]@content");
+    assert_eq!(site.tests.len(), 2);
+    assert_eq!(site.tests[0].expected["title"], "A Paper");
+}
+
+#[test]
+fn rejects_malformed_override_files() {
+    for source in [
+        "entry_type = \"article\"",
+        "hosts = [\"example.org\"]\nentry_type = \"essay\"",
+        "hosts = [\"example.org\"]\n[selectors]\nsubtitle = \"h2\"",
+        "hosts = [\"example.org\"]\n[extras]\nfoo = \"bar\"",
+        "hosts = [\"example.org\"\nentry_type = \"article\"",
+        "hosts = [\"example.org\"]\nhosts = [\"example.com\"]",
+        "hosts = [\"example.org\"]\n[[tests]]\ntitle = \"No URL\"",
+    ] {
+        assert!(parse_override("bad", source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn overrides_match_subdomains() {
+    assert_eq!(find_override("dl.acm.org").unwrap().name, "acm");
+    assert_eq!(find_override("www.dl.acm.org").unwrap().name, "acm");
+    assert!(find_override("acm.org").is_none());
+}
+
+// Every bundled override's examples are checked against the saved page `{name}.html`.
+#[tokio::test]
+async fn bundled_override_examples_hold() {
+    for site in bundled() {
+        for test in &site.tests {
+            let client = FixtureClient::new()
+                .with_fixture(&test.url, fixture(&format!("{}.html", site.name)));
+            let bibtex = fetch_and_generate_bibtex(&client, &test.url).await.unwrap();
+
+            for (name, expected) in &test.expected {
+                if name == "entry_type" {
+                    assert!(
+                        bibtex.starts_with(&format!("@{}{{", expected)),
+                        "{}",
+                        bibtex
+                    );
+                } else {
+                    assert_eq!(field(&bibtex, name), Some(expected.as_str()), "{}", bibtex);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use bibtexter::error::AppError;
+use bibtexter::http::FixtureClient;
+use bibtexter::pdf::{bibtex_from_pdf, extract_pdf_metadata};
+use common::{field, fixture};
+
+fn read_fixture(name: &str) -> Vec<u8> {
+    std::fs::read(fixture(name)).unwrap()
+}
+
+#[test]
+fn xmp_packet_provides_title_authors_and_year() {
+    let metadata = extract_pdf_metadata(&read_fixture("paper_xmp.pdf"));
+
+    assert_eq!(metadata.title, "Graph Neural Networks & Molecules");
+    assert_eq!(metadata.author, "Amara Okafor and Lin Wei");
+    assert_eq!(metadata.year, "2022");
+    assert_eq!(metadata.doi, None);
+}
+
+#[test]
+fn doi_is_found_in_xmp_and_info_dictionary() {
+    for name in ["paper_xmp_doi.pdf", "paper_info_doi.pdf"] {
+        let metadata = extract_pdf_metadata(&read_fixture(name));
+        assert_eq!(metadata.doi.as_deref(), Some("10.1145/3158154"), "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn pdf_with_doi_uses_content_negotiation() {
+    let client = FixtureClient::new().with_fixture(
+        "https://doi.org/10.1145/3158154",
+        fixture("doi_rustbelt.bib"),
+    );
+
+    let bibtex = bibtex_from_pdf(&client, &read_fixture("paper_info_doi.pdf"))
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@inproceedings{Jung_2017,"));
+}
+
+#[tokio::test]
+async fn pdf_without_doi_is_cited_from_xmp() {
+    let bibtex = bibtex_from_pdf(&FixtureClient::new(), &read_fixture("paper_xmp.pdf"))
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@misc{Okafor.Wei2022Graph,"));
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Graph Neural Networks & Molecules")
+    );
+    assert_eq!(field(&bibtex, "howpublished"), None);
+    assert_eq!(field(&bibtex, "publisher"), None);
+}
+
+#[tokio::test]
+async fn non_pdf_upload_is_rejected() {
+    let result = bibtex_from_pdf(&FixtureClient::new(), b"<html></html>").await;
+
+    assert!(matches!(result, Err(AppError::InvalidInput(_))));
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use bibtexter::extract::fetch_metadata;
+use bibtexter::http::FixtureClient;
+use bibtexter::preflight::{RecommendedStrategy, is_blocked, preflight, robots_allows};
+use bibtexter::robots::{RobotsCache, RobotsRules};
+use common::fixture;
+use reqwest::StatusCode;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+use url::Url;
+
+const ROBOTS: &str = "User-agent: Googlebot
+Disallow: /
+
+User-agent: *
+Disallow: /private/ # staff only
+Allow: /private/papers/
+";
+
+#[test]
+fn robots_rules_for_other_agents_are_ignored() {
+    assert!(robots_allows(ROBOTS, "/articles/1"));
+    assert!(robots_allows("User-agent: Googlebot\nDisallow: /\n", "/"));
+}
+
+#[test]
+fn longest_robots_rule_wins() {
+    assert!(!robots_allows(ROBOTS, "/private/notes"));
+    assert!(robots_allows(ROBOTS, "/private/papers/1"));
+    assert!(robots_allows("User-agent: *\nDisallow:\n", "/anything"));
+}
+
+#[test]
+fn missing_robots_allows_and_rate_limited_robots_disallows() {
+    assert!(RobotsRules::from_response(StatusCode::NOT_FOUND, "").allows("/private/"));
+    assert!(!RobotsRules::from_response(StatusCode::TOO_MANY_REQUESTS, "").allows("/"));
+    assert!(!RobotsRules::from_response(StatusCode::OK, ROBOTS).allows("/private/notes"));
+}
+
+#[tokio::test]
+async fn robots_txt_is_fetched_once_per_host() {
+    let page = Url::parse("https://journal.example.org/private/notes").unwrap();
+    let cache = RobotsCache::default();
+    let client = FixtureClient::new().with_fixture(
+        "https://journal.example.org/robots.txt",
+        fixture("robots.txt"),
+    );
+    assert!(!cache.rules(&client, &page).await.allows(page.path()));
+
+    // Without the fixture the host would have no robots.txt, so the cached copy answers.
+    assert!(
+        !cache
+            .rules(&FixtureClient::new(), &page)
+            .await
+            .allows(page.path())
+    );
+}
+
+#[tokio::test]
+async fn least_recently_used_host_is_evicted() {
+    let cache = RobotsCache::new(1, Duration::from_secs(60));
+    let first = Url::parse("https://journal.example.org/private/notes").unwrap();
+    let second = Url::parse("https://other.example.org/").unwrap();
+    let client = FixtureClient::new().with_fixture(
+        "https://journal.example.org/robots.txt",
+        fixture("robots.txt"),
+    );
+    cache.rules(&client, &first).await;
+    cache.rules(&client, &second).await;
+
+    assert!(
+        cache
+            .rules(&FixtureClient::new(), &first)
+            .await
+            .allows(first.path())
+    );
+}
+
+#[test]
+fn subdomains_of_blocked_domains_are_blocked() {
+    let blocked = vec!["example.com".to_string()];
+
+    assert!(is_blocked("example.com", &blocked));
+    assert!(is_blocked("www.Example.com", &blocked));
+    assert!(!is_blocked("notexample.com", &blocked));
+}
+
+#[tokio::test]
+async fn reachable_html_page_is_extractable() {
+    let url = "https://journal.example.org/articles/1";
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://journal.example.org/robots.txt",
+            fixture("robots.txt"),
+        )
+        .with_fixture(url, fixture("coins.html"))
+        .with_header(url, CONTENT_TYPE, "text/html; charset=utf-8");
+
+    let result = preflight(&client, &RobotsCache::default(), url)
+        .await
+        .unwrap();
+
+    assert!(result.extractable);
+    assert_eq!(result.strategy, RecommendedStrategy::HtmlScrape);
+    assert!(!result.domain_blocked);
+}
+
+#[tokio::test]
+async fn disallowed_unreachable_and_non_html_pages_are_not_extractable() {
+    let pdf = "https://journal.example.org/articles/1.pdf";
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://journal.example.org/robots.txt",
+            fixture("robots.txt"),
+        )
+        .with_fixture(
+            "https://journal.example.org/private/notes",
+            fixture("coins.html"),
+        )
+        .with_fixture(pdf, fixture("coins.html"))
+        .with_header(pdf, CONTENT_TYPE, "application/pdf");
+
+    for url in [
+        "https://journal.example.org/private/notes",
+        "https://journal.example.org/missing",
+        pdf,
+    ] {
+        assert!(
+            !preflight(&client, &RobotsCache::default(), url)
+                .await
+                .unwrap()
+                .extractable,
+            "{}",
+            url
+        );
+    }
+}
+
+#[tokio::test]
+async fn doi_links_recommend_content_negotiation() {
+    let result = preflight(
+        &FixtureClient::new(),
+        &RobotsCache::default(),
+        "https://doi.org/10.1145/3158154",
+    )
+    .await
+    .unwrap();
+
+    assert!(result.extractable);
+    assert_eq!(result.strategy, RecommendedStrategy::Doi);
+}
+
+#[tokio::test]
+async fn malformed_url_is_an_error() {
+    assert!(
+        preflight(&FixtureClient::new(), &RobotsCache::default(), "not a url")
+            .await
+            .is_err()
+    );
+    assert!(
+        preflight(
+            &FixtureClient::new(),
+            &RobotsCache::default(),
+            "ftp://example.com/paper"
+        )
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn blocked_domains_are_reported_and_refused() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_BLOCKED_DOMAINS", "blocked.example") };
+    let client = FixtureClient::new();
+
+    let result = preflight(
+        &client,
+        &RobotsCache::default(),
+        "https://www.blocked.example/paper",
+    )
+    .await
+    .unwrap();
+    assert!(result.domain_blocked);
+    assert!(!result.extractable);
+
+    assert!(
+        fetch_metadata(&client, "https://blocked.example/paper")
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn canonical_links_to_blocked_domains_are_not_followed() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_BLOCKED_DOMAINS", "blocked.example") };
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://mirror.example.org/rust-1.80",
+            fixture("mirror_page_canonical.html"),
+        )
+        .with_fixture(
+            "https://blocked.example/2024/07/25/Rust-1.80.0.html",
+            fixture("blog.html"),
+        );
+
+    let metadata = fetch_metadata(&client, "https://mirror.example.org/rust-1.80")
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.title, "Mirror | Paper Archive");
+    assert_eq!(metadata.author, "");
+}
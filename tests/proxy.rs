@@ -0,0 +1,131 @@
+use bibtexter::http::{ClientConfig, HttpClient};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Answers one request with `body` and returns the request head it received. Standing in
+/// for a proxy, it sees the absolute URL of the page being fetched in the request line.
+async fn serve_once(body: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0; 1024];
+        while !head.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..read]);
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8(head).unwrap()
+    });
+    (address, handle)
+}
+
+#[tokio::test]
+async fn requests_go_through_the_configured_proxy() {
+    let (proxy, requests) = serve_once("from the proxy").await;
+    let client = ClientConfig {
+        http_proxy: Some(proxy),
+        ..Default::default()
+    }
+    .build_client()
+    .unwrap();
+
+    let response = HttpClient::get(&client, "http://journal.example/article/1", None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.body, "from the proxy");
+    let head = requests.await.unwrap();
+    assert!(head.starts_with("GET http://journal.example/article/1 HTTP/1.1\r\n"));
+}
+
+#[tokio::test]
+async fn proxy_credentials_are_sent_to_the_proxy() {
+    let (proxy, requests) = serve_once("ok").await;
+    let client = ClientConfig {
+        http_proxy: Some(proxy.replace("http://", "http://alice:secret@")),
+        ..Default::default()
+    }
+    .build_client()
+    .unwrap();
+
+    HttpClient::get(&client, "http://journal.example/", None)
+        .await
+        .unwrap();
+
+    let head = requests.await.unwrap().to_lowercase();
+    // base64("alice:secret")
+    assert!(head.contains("proxy-authorization: basic ywxpy2u6c2vjcmv0"));
+}
+
+#[tokio::test]
+async fn no_proxy_hosts_are_fetched_directly() {
+    let (page, requests) = serve_once("direct").await;
+    let client = ClientConfig {
+        // Nothing listens here, so a proxied request would fail.
+        http_proxy: Some("http://127.0.0.1:9".to_string()),
+        no_proxy: Some("localhost, 127.0.0.1".to_string()),
+        ..Default::default()
+    }
+    .build_client()
+    .unwrap();
+
+    let response = HttpClient::get(&client, &format!("{}/article", page), None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.body, "direct");
+    assert!(
+        requests
+            .await
+            .unwrap()
+            .starts_with("GET /article HTTP/1.1\r\n")
+    );
+}
+
+#[tokio::test]
+async fn standard_proxy_variables_are_read_from_the_environment() {
+    let (proxy, requests) = serve_once("from the proxy").await;
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe {
+        std::env::set_var("HTTP_PROXY", &proxy);
+        std::env::set_var("BIBTEXTER_NO_PROXY", "internal.example");
+    }
+
+    let config = ClientConfig::from_env();
+    assert_eq!(config.http_proxy.as_deref(), Some(proxy.as_str()));
+    assert_eq!(config.no_proxy.as_deref(), Some("internal.example"));
+
+    let client = config.build_client().unwrap();
+    let response = HttpClient::get(&client, "http://journal.example/", None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.body, "from the proxy");
+    assert!(
+        requests
+            .await
+            .unwrap()
+            .starts_with("GET http://journal.example/ HTTP/1.1\r\n")
+    );
+}
+
+#[test]
+fn invalid_proxy_urls_are_rejected() {
+    let config = ClientConfig {
+        https_proxy: Some("not a url".to_string()),
+        ..Default::default()
+    };
+
+    assert!(config.build_client().is_err());
+}
@@ -0,0 +1,88 @@
+use bibtexter::http::FixtureClient;
+use bibtexter::publisher::{publisher_address, resolve_journal, resolve_publisher};
+
+#[tokio::test]
+async fn known_issn_resolves_without_network() {
+    let client = FixtureClient::new();
+
+    let publisher = resolve_publisher(&client, "1476-4687").await;
+
+    assert_eq!(publisher.as_deref(), Some("Springer Nature"));
+}
+
+#[tokio::test]
+async fn unknown_issn_is_looked_up_in_issn_portal() {
+    let client = FixtureClient::new().with_fixture(
+        "https://portal.issn.org/resource/ISSN/1234-5679?format=json",
+        format!(
+            "{}/tests/fixtures/issn_portal_1234-5679.json",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+    );
+
+    let publisher = resolve_publisher(&client, "1234-5679").await;
+
+    assert_eq!(publisher.as_deref(), Some("Fixture University Press"));
+}
+
+#[tokio::test]
+async fn unresolvable_issn_returns_none() {
+    let client = FixtureClient::new();
+
+    assert_eq!(resolve_publisher(&client, "9999-9999").await, None);
+}
+
+#[test]
+fn publisher_address_matches_names_loosely() {
+    assert_eq!(
+        publisher_address("Elsevier BV", None),
+        Some("Amsterdam, Netherlands")
+    );
+    assert_eq!(
+        publisher_address(
+            "Institute of Electrical and Electronics Engineers (IEEE)",
+            None
+        ),
+        Some("New York, NY, USA")
+    );
+    assert_eq!(
+        publisher_address("Association for Computing Machinery", None),
+        Some("New York, NY, USA")
+    );
+    assert_eq!(publisher_address("Fixture University Press", None), None);
+}
+
+#[test]
+fn publisher_address_falls_back_to_hostname() {
+    assert_eq!(
+        publisher_address("onlinelibrary.wiley.com", Some("onlinelibrary.wiley.com")),
+        Some("Hoboken, NJ, USA")
+    );
+    assert_eq!(
+        publisher_address("", Some("www.sciencedirect.com")),
+        Some("Amsterdam, Netherlands")
+    );
+    assert_eq!(publisher_address("", Some("arxiv.org")), None);
+}
+
+#[tokio::test]
+async fn journal_record_is_shared_by_its_editions() {
+    let client = FixtureClient::new().with_fixture(
+        "https://portal.issn.org/resource/ISSN/3456-7895?format=json",
+        format!(
+            "{}/tests/fixtures/issn_portal_3456-7895.json",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+    );
+
+    let journal = resolve_journal(&client, &["3456-7895", "2345-6787"]).await;
+    assert_eq!(journal.issn_l.as_deref(), Some("2345-6787"));
+    assert_eq!(
+        journal.publisher.as_deref(),
+        Some("Coastal Sciences Society")
+    );
+
+    // The print edition is now answered from the cache, without a fixture to fetch.
+    let offline = FixtureClient::new();
+    assert_eq!(resolve_journal(&offline, &["2345-6787"]).await, journal);
+}
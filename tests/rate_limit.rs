@@ -0,0 +1,115 @@
+use axum::Router;
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::middleware;
+use axum::routing::get;
+use bibtexter::rate_limit::{ApiKey, ApiKeys, RateLimiter, rate_limit};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn clients_are_limited_separately() {
+    let limiter = RateLimiter::new(2, Duration::from_secs(60));
+
+    assert!(limiter.check("203.0.113.7"));
+    assert!(limiter.check("203.0.113.7"));
+    assert!(!limiter.check("203.0.113.7"));
+    assert!(limiter.check("198.51.100.2"));
+}
+
+#[test]
+fn limit_resets_after_the_window() {
+    let limiter = RateLimiter::new(1, Duration::from_millis(20));
+
+    assert!(limiter.check("203.0.113.7"));
+    assert!(!limiter.check("203.0.113.7"));
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(limiter.check("203.0.113.7"));
+}
+
+#[test]
+fn api_keys_are_read_with_their_limits() {
+    let keys = ApiKeys::from_vars([
+        (
+            "BIBTEXTER_API_KEY_ALICE".to_string(),
+            "key123:1000rpm".to_string(),
+        ),
+        (
+            "BIBTEXTER_API_KEY_GLOBAL".to_string(),
+            "key456:30".to_string(),
+        ),
+        ("BIBTEXTER_API_KEY_BROKEN".to_string(), "key789".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ]);
+
+    assert_eq!(
+        keys.get("key123"),
+        Some(&ApiKey {
+            name: "ALICE".to_string(),
+            limit: 1000
+        })
+    );
+    assert_eq!(keys.get("key456").map(|key| key.limit), Some(30));
+    assert_eq!(keys.get("key789"), None);
+}
+
+#[test]
+fn api_key_holders_are_limited_per_key_not_per_address() {
+    let keys = ApiKeys::from_vars([(
+        "BIBTEXTER_API_KEY_LAB".to_string(),
+        "labkey:3rpm".to_string(),
+    )]);
+    let limiter = RateLimiter::new(1, Duration::from_secs(60)).with_api_keys(keys);
+    let mut with_key = HeaderMap::new();
+    with_key.insert("x-api-key", HeaderValue::from_static("labkey"));
+    let mut with_bearer = HeaderMap::new();
+    with_bearer.insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_static("Bearer labkey"),
+    );
+
+    // Everyone behind the same address shares the anonymous allowance...
+    assert!(limiter.check_request(&HeaderMap::new(), "203.0.113.7"));
+    assert!(!limiter.check_request(&HeaderMap::new(), "203.0.113.7"));
+    // ...but a key brings its own.
+    assert!(limiter.check_request(&with_key, "203.0.113.7"));
+    assert!(limiter.check_request(&with_bearer, "203.0.113.7"));
+    assert!(limiter.check_request(&with_key, "198.51.100.2"));
+    assert!(!limiter.check_request(&with_key, "203.0.113.7"));
+
+    // Unknown keys count against the address.
+    let mut unknown = HeaderMap::new();
+    unknown.insert("x-api-key", HeaderValue::from_static("guess"));
+    assert!(!limiter.check_request(&unknown, "203.0.113.7"));
+}
+
+#[tokio::test]
+async fn spoofed_forwarded_for_does_not_reset_the_count() {
+    let limiter = Arc::new(RateLimiter::new(2, Duration::from_secs(60)));
+    let app = Router::new()
+        .route("/", get(|| async { "ok" }))
+        .layer(middleware::from_fn_with_state(limiter, rate_limit));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    });
+
+    let client = reqwest::Client::new();
+    let mut statuses = Vec::new();
+    for forwarded in ["203.0.113.1", "203.0.113.2", "203.0.113.3"] {
+        let response = client
+            .get(&address)
+            .header("x-forwarded-for", forwarded)
+            .send()
+            .await
+            .unwrap();
+        statuses.push(response.status().as_u16());
+    }
+    assert_eq!(statuses, [200, 200, 429]);
+}
@@ -0,0 +1,62 @@
+use bibtexter::relations::{Relations, parse_relations, prepend_note};
+use serde_json::Value;
+
+fn retracted_work() -> Value {
+    let path = format!(
+        "{}/tests/fixtures/crossref_work_retracted.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn reads_crossref_relations() {
+    let relations = parse_relations(&retracted_work());
+
+    assert_eq!(relations.retracted_by, vec!["10.1145/3999999"]);
+    assert_eq!(relations.expression_of_concern, vec!["10.1145/3888888"]);
+    // Only DOIs are kept.
+    assert_eq!(relations.preprint_of, vec!["10.1145/3777777"]);
+}
+
+#[test]
+fn retraction_outranks_expression_of_concern() {
+    let relations = parse_relations(&retracted_work());
+    assert_eq!(
+        relations.warning_note().as_deref(),
+        Some("RETRACTED: see doi:10.1145/3999999")
+    );
+
+    let concern = Relations {
+        expression_of_concern: vec!["10.1145/3888888".into()],
+        ..Default::default()
+    };
+    assert_eq!(
+        concern.warning_note().as_deref(),
+        Some("EXPRESSION OF CONCERN: see doi:10.1145/3888888")
+    );
+
+    let preprint = Relations {
+        preprint_of: vec!["10.1145/3777777".into()],
+        ..Default::default()
+    };
+    assert_eq!(preprint.warning_note(), None);
+}
+
+#[test]
+fn prepends_to_existing_note_or_adds_one() {
+    assert_eq!(
+        prepend_note(
+            "@misc{Doe2025Title,\n  title = {Title},\n  note = {Accessed: 2025-01-01},\n}",
+            "RETRACTED: see doi:10.1/x"
+        ),
+        "@misc{Doe2025Title,\n  title = {Title},\n  note = {RETRACTED: see doi:10.1/x; Accessed: 2025-01-01},\n}"
+    );
+    assert_eq!(
+        prepend_note(
+            "@article{Jung_2017, title={RustBelt}, annote={ok}}",
+            "RETRACTED: see doi:10.1/x"
+        ),
+        "@article{Jung_2017,\n  note = {RETRACTED: see doi:10.1/x}, title={RustBelt}, annote={ok}}"
+    );
+}
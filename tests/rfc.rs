@@ -0,0 +1,57 @@
+mod common;
+
+use bibtexter::bibtex::OutputMode;
+use bibtexter::http::FixtureClient;
+use bibtexter::rfc::{bibtex_for_rfc, parse_rfc_number};
+use common::{field, fixture};
+
+#[test]
+fn parses_rfc_numbers() {
+    assert_eq!(parse_rfc_number("8574"), Some(8574));
+    assert_eq!(parse_rfc_number("RFC 8574"), Some(8574));
+    assert_eq!(parse_rfc_number(" rfc8574 "), Some(8574));
+
+    assert_eq!(parse_rfc_number("RFC"), None);
+    assert_eq!(parse_rfc_number("0"), None);
+    assert_eq!(parse_rfc_number("draft-ietf-quic"), None);
+}
+
+#[tokio::test]
+async fn rfc_is_cited_as_ietf_techreport() {
+    let client = FixtureClient::new().with_fixture(
+        "https://www.rfc-editor.org/rfc/rfc8574.json",
+        fixture("rfc8574.json"),
+    );
+
+    let bibtex = bibtex_for_rfc(&client, "RFC 8574", OutputMode::Bibtex)
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@techreport{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("cite-as: A Link Relation to Convey a Preferred URI for Referencing")
+    );
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("H. Van de Sompel and M. Nelson and G. Bilder and J. Kunze and S. Warner")
+    );
+    assert_eq!(field(&bibtex, "institution"), Some("IETF"));
+    assert_eq!(field(&bibtex, "number"), Some("RFC 8574"));
+    assert_eq!(field(&bibtex, "type"), Some("RFC"));
+    assert_eq!(field(&bibtex, "year"), Some("2019"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.17487/RFC8574"));
+    assert_eq!(
+        field(&bibtex, "url"),
+        Some("https://www.rfc-editor.org/info/rfc8574")
+    );
+}
+
+#[tokio::test]
+async fn non_numeric_rfc_is_rejected() {
+    assert!(
+        bibtex_for_rfc(&FixtureClient::new(), "quic", OutputMode::Bibtex)
+            .await
+            .is_err()
+    );
+}
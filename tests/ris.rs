@@ -0,0 +1,60 @@
+use bibtexter::ris::bibtex_to_ris;
+
+#[test]
+fn converts_an_article_to_ris() {
+    let ris = bibtex_to_ris(
+        "@article{Doe2024Tidal,
+  title = {Tidal Mixing in {Shallow} Estuaries},
+  author = {Doe, Jane and Roe, Richard},
+  journal = {Estuarine Dynamics},
+  volume = {12},
+  number = {3},
+  pages = {101--118},
+  year = {2024},
+  doi = {10.1234/ed.2024.17},
+  keywords = {estuaries, mixing},
+}",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ris,
+        "TY  - JOUR
+ID  - Doe2024Tidal
+AU  - Doe, Jane
+AU  - Roe, Richard
+TI  - Tidal Mixing in Shallow Estuaries
+JO  - Estuarine Dynamics
+VL  - 12
+IS  - 3
+PY  - 2024
+DO  - 10.1234/ed.2024.17
+SP  - 101
+EP  - 118
+KW  - estuaries
+KW  - mixing
+ER  - \n"
+    );
+}
+
+#[test]
+fn web_pages_keep_their_address() {
+    let ris = bibtex_to_ris(
+        "@misc{Roe2025Post,
+  title = {A Post},
+  howpublished = {\\url{https://blog.example.com/a-post}},
+  year = {2025},
+}
+@book{Knuth1997Art,
+  title = {The Art of Computer Programming},
+  publisher = {Addison-Wesley},
+}",
+    )
+    .unwrap();
+
+    let records: Vec<&str> = ris.split("ER  - \n").collect();
+    assert!(records[0].starts_with("TY  - ELEC\n"));
+    assert!(records[0].contains("UR  - https://blog.example.com/a-post\n"));
+    assert!(records[1].starts_with("\nTY  - BOOK\n"));
+    assert!(records[1].contains("PB  - Addison-Wesley\n"));
+}
@@ -0,0 +1,100 @@
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use bibtexter::extract::{ArticleMetadata, ExtractionStrategy, URL_TITLE_WARNING};
+use bibtexter::routes::{render_preview_page, render_result_page, show_form};
+
+#[tokio::test]
+async fn form_is_cacheable_with_a_stable_etag() {
+    let response = show_form(HeaderMap::new()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "public, max-age=86400"
+    );
+    let etag = response.headers()[header::ETAG].to_str().unwrap();
+    assert_eq!(etag.len(), 18);
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+    let again = show_form(HeaderMap::new()).await;
+    assert_eq!(again.headers()[header::ETAG], etag);
+}
+
+#[tokio::test]
+async fn matching_if_none_match_returns_not_modified() {
+    let etag = show_form(HeaderMap::new()).await.headers()[header::ETAG].clone();
+
+    let mut headers = HeaderMap::new();
+    let weak = format!("\"other\", W/{}", etag.to_str().unwrap());
+    headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&weak).unwrap());
+    let response = show_form(headers).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn stale_if_none_match_returns_the_page() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""));
+
+    assert_eq!(show_form(headers).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn healthz_answers_ok() {
+    assert_eq!(bibtexter::routes::healthz().await, "ok");
+}
+
+#[test]
+fn result_page_escapes_the_source_url() {
+    let entry = "@misc{Doe2025,\n  title = {<script>alert(1)</script>},\n}";
+
+    let page = render_result_page(r#"https://example.com/?q=" onclick="alert(1)"#, entry);
+    assert!(page.contains(
+        r#"href="https:&#x2F;&#x2F;example.com&#x2F;?q=&quot; onclick=&quot;alert(1)">"#
+    ));
+    assert!(page.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(!page.contains("<script>alert(1)"));
+
+    let page = render_result_page("javascript:alert(1)", entry);
+    assert!(!page.contains(r#"href="javascript"#), "{}", page);
+    assert!(page.contains("Source URL: javascript:alert(1)</p>"));
+
+    let page = render_result_page("https://example.com/paper", entry);
+    assert!(page.contains(
+        r#"<a href="https:&#x2F;&#x2F;example.com&#x2F;paper">https://example.com/paper</a>"#
+    ));
+}
+
+#[test]
+fn preview_page_colours_found_fallback_and_missing_fields() {
+    let metadata = ArticleMetadata {
+        strategy: ExtractionStrategy::MetaTags,
+        title: "Machine Learning Intro".to_string(),
+        publisher: "Example <Press>".to_string(),
+        comment: Some(URL_TITLE_WARNING.to_string()),
+        ..Default::default()
+    };
+
+    let page = render_preview_page(
+        "https://example.com/blog/machine-learning-intro",
+        &metadata,
+        Some("@misc{Intro,\n}"),
+    );
+
+    assert!(page.contains(r#"<tr class="strategy"><th>strategy</th><td>meta_tags</td></tr>"#));
+    assert!(
+        page.contains(r#"<tr class="fallback"><th>title</th><td>Machine Learning Intro</td></tr>"#)
+    );
+    assert!(
+        page.contains(r#"<tr class="found"><th>publisher</th><td>Example &lt;Press&gt;</td></tr>"#)
+    );
+    assert!(page.contains(r#"<tr class="missing"><th>author</th><td></td></tr>"#));
+    assert!(!page.contains("<th>journal</th>"));
+    assert!(page.contains("<li>missing author</li>"));
+    assert!(page.contains("<pre><code>@misc{Intro,"));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use bibtexter::http::FixtureClient;
+use bibtexter::search::{match_title, search_papers, title_similarity};
+use common::fixture;
+
+const CROSSREF_URL: &str = "https://api.crossref.org/works?query=rust+safety&rows=10&select=DOI,title,author,issued,container-title";
+const S2_URL: &str = "https://api.semanticscholar.org/graph/v1/paper/search?query=rust+safety&limit=10&fields=title,authors,year,externalIds,venue,url";
+const OPENALEX_URL: &str = "https://api.openalex.org/works?search=rust+safety&per-page=10";
+
+#[tokio::test]
+async fn results_are_merged_by_doi_and_ranked_across_sources() {
+    let client = FixtureClient::new()
+        .with_fixture(CROSSREF_URL, fixture("crossref_search.json"))
+        .with_fixture(S2_URL, fixture("s2_search.json"))
+        .with_fixture(OPENALEX_URL, fixture("openalex_search.json"));
+
+    let results = search_papers(&client, "rust safety", 10).await.unwrap();
+
+    assert_eq!(results.len(), 3);
+    // Found by all three sources, so it ranks first.
+    assert_eq!(results[0].doi.as_deref(), Some("10.1145/3158154"));
+    assert_eq!(results[0].authors, ["Ralf Jung", "Jacques-Henri Jourdan"]);
+    assert_eq!(results[0].year, Some(2017));
+    assert_eq!(
+        results[0].venue.as_deref(),
+        Some("Proceedings of the ACM on Programming Languages")
+    );
+    assert_eq!(
+        results[0].bibtex_url,
+        "/api/bibtex?url=https%3A%2F%2Fdoi.org%2F10.1145%2F3158154"
+    );
+
+    assert_eq!(results[1].title, "Oxide: The Essence of Rust");
+    assert_eq!(results[1].doi, None);
+    assert_eq!(results[1].venue, None);
+    assert_eq!(
+        results[1].bibtex_url,
+        "/api/bibtex?url=https%3A%2F%2Fwww.semanticscholar.org%2Fpaper%2F8c7a9b0f1e2d3c4b5a69788796a5b4c3d2e1f0a9"
+    );
+
+    assert_eq!(results[2].title, "Safe systems programming in Rust");
+    assert_eq!(results[2].authors, ["Ralf Jung", "The Rust Team"]);
+}
+
+#[tokio::test]
+async fn failing_sources_are_skipped() {
+    let client = FixtureClient::new().with_fixture(OPENALEX_URL, fixture("openalex_search.json"));
+
+    let results = search_papers(&client, "rust safety", 10).await.unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].authors, ["Ralf Jung"], "only OpenAlex answered");
+}
+
+#[tokio::test]
+async fn search_fails_when_every_source_does() {
+    let client = FixtureClient::new();
+
+    assert!(search_papers(&client, "rust safety", 10).await.is_err());
+    assert!(search_papers(&client, "  ", 10).await.is_err());
+}
+
+#[test]
+fn title_similarity_ignores_case_and_punctuation() {
+    assert_eq!(
+        title_similarity(
+            "Safe Systems Programming in Rust.",
+            "safe systems programming in rust"
+        ),
+        1.0
+    );
+    let typo = title_similarity(
+        "Safe sytems programming in Rust",
+        "Safe systems programming in Rust",
+    );
+    assert!(typo > 0.9 && typo < 1.0, "{}", typo);
+    assert!(title_similarity("RustBelt", "Safe systems programming in Rust") < 0.5);
+    assert_eq!(title_similarity("", ""), 0.0);
+}
+
+#[tokio::test]
+async fn title_lookup_picks_the_closest_crossref_result() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.crossref.org/works?query.bibliographic=safe+systems+programming+in+rust&rows=5&select=DOI,title,author,issued,container-title&query.author=Jung&filter=from-pub-date:2021,until-pub-date:2021",
+        fixture("crossref_search.json"),
+    );
+
+    let (paper, score) = match_title(
+        &client,
+        "safe systems programming in rust",
+        Some("Jung"),
+        Some(2021),
+    )
+    .await
+    .unwrap();
+
+    // Crossref ranked RustBelt first, but its title is further off.
+    assert_eq!(paper.doi.as_deref(), Some("10.1145/3443420"));
+    assert_eq!(score, 1.0);
+}
+
+#[tokio::test]
+async fn title_lookup_without_results_fails() {
+    let result = match_title(&FixtureClient::new(), "Nothing Like This", None, None).await;
+    assert!(result.is_err());
+
+    let result = match_title(&FixtureClient::new(), "  ", None, None).await;
+    assert!(result.is_err());
+}
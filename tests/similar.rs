@@ -0,0 +1,49 @@
+mod common;
+
+use bibtexter::http::FixtureClient;
+use bibtexter::similar::find_similar;
+use common::fixture;
+
+#[tokio::test]
+async fn recommendations_link_back_to_bibtex_api() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.semanticscholar.org/recommendations/v1/papers/forpaper/DOI:10.1145%2F3158154?fields=title,authors,year,externalIds&limit=5",
+        fixture("s2_recommendations.json"),
+    );
+
+    let papers = find_similar(&client, "DOI:10.1145/3158154").await.unwrap();
+
+    assert_eq!(papers.len(), 2);
+    assert_eq!(papers[0].authors, ["Ashish Vaswani", "Noam Shazeer"]);
+    assert_eq!(papers[0].doi.as_deref(), Some("10.48550/arXiv.1706.03762"));
+    assert_eq!(
+        papers[0].bibtex_url,
+        "/api/bibtex?url=https%3A%2F%2Fdoi.org%2F10.48550%2FarXiv.1706.03762"
+    );
+    assert_eq!(papers[1].doi, None);
+    assert_eq!(
+        papers[1].bibtex_url,
+        "/api/bibtex?url=https%3A%2F%2Fwww.semanticscholar.org%2Fpaper%2Fdf2b0e26d0599ce3e70df8a9da02e51594e0e992"
+    );
+}
+
+#[tokio::test]
+async fn url_ids_are_encoded_as_one_path_segment() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.semanticscholar.org/recommendations/v1/papers/forpaper/URL:https:%2F%2Fexample.com%2Fpaper%3Fid=5?fields=title,authors,year,externalIds&limit=5",
+        fixture("s2_recommendations.json"),
+    );
+
+    let papers = find_similar(&client, "URL:https://example.com/paper?id=5")
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), 2);
+}
+
+#[tokio::test]
+async fn unknown_paper_is_an_error() {
+    let client = FixtureClient::new();
+
+    assert!(find_similar(&client, "DOI:10.0000/missing").await.is_err());
+}
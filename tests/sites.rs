@@ -0,0 +1,295 @@
+mod common;
+
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::{europe_pmc, mendeley_data, nber};
+use common::{field, fixture};
+use url::Url;
+
+#[tokio::test]
+async fn figshare_article_is_cited_as_dataset() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.figshare.com/v2/articles/5616445",
+        fixture("figshare_article.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(
+        &client,
+        "https://figshare.com/articles/dataset/Global_soil_moisture_observations/5616445/2",
+    )
+    .await
+    .unwrap();
+
+    assert!(bibtex.starts_with("@misc{Garcia.Chen2017Global,"));
+    assert_eq!(field(&bibtex, "author"), Some("Maria Garcia and Wei Chen"));
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "version"), Some("2"));
+    assert_eq!(
+        field(&bibtex, "doi"),
+        Some("10.6084/m9.figshare.5616445.v2")
+    );
+    assert_eq!(field(&bibtex, "license"), Some("CC-BY-4.0"));
+    assert_eq!(
+        field(&bibtex, "keywords"),
+        Some("soil moisture, remote sensing")
+    );
+    assert_eq!(field(&bibtex, "publisher"), Some("figshare"));
+}
+
+#[tokio::test]
+async fn dryad_dataset_uses_doi_based_api() {
+    let client = FixtureClient::new().with_fixture(
+        "https://datadryad.org/api/v2/datasets/doi%3A10.5061%2Fdryad.2bvq83bn5",
+        fixture("dryad_dataset.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(
+        &client,
+        "https://datadryad.org/stash/dataset/doi:10.5061/dryad.2bvq83bn5",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(field(&bibtex, "author"), Some("Ana Silva and Tom Baker"));
+    assert_eq!(field(&bibtex, "year"), Some("2021"));
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "version"), Some("3"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.5061/dryad.2bvq83bn5"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Dryad"));
+}
+
+#[tokio::test]
+async fn mendeley_dataset_cites_the_version_in_the_url() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.mendeley.com/datasets/8gx2mk6x7w",
+        fixture("mendeley_dataset.json"),
+    );
+
+    let bibtex =
+        fetch_and_generate_bibtex(&client, "https://data.mendeley.com/datasets/8gx2mk6x7w/2")
+            .await
+            .unwrap();
+
+    assert!(bibtex.starts_with("@misc{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Soil microbial respiration under drought")
+    );
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Vogel, Lena and Iyer, Rahul")
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2022"));
+    assert_eq!(field(&bibtex, "type"), Some("Dataset"));
+    assert_eq!(field(&bibtex, "version"), Some("2"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.17632/8gx2mk6x7w.2"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Mendeley Data"));
+}
+
+#[test]
+fn mendeley_doi_follows_the_requested_version() {
+    let latest = Some("10.17632/8gx2mk6x7w.3".to_string());
+    assert_eq!(
+        mendeley_data::versioned_doi(latest.clone(), "8gx2mk6x7w", Some("1")).as_deref(),
+        Some("10.17632/8gx2mk6x7w.1")
+    );
+    assert_eq!(
+        mendeley_data::versioned_doi(latest.clone(), "8gx2mk6x7w", None),
+        latest
+    );
+    assert_eq!(
+        mendeley_data::versioned_doi(None, "8gx2mk6x7w", Some("4")).as_deref(),
+        Some("10.17632/8gx2mk6x7w.4")
+    );
+}
+
+#[tokio::test]
+async fn nber_working_paper_is_cited_as_techreport() {
+    let client = FixtureClient::new().with_fixture(
+        "https://www.nber.org/api/v1/working_paper/contentType/working_paper/id/w29000",
+        fixture("nber_paper.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(&client, "https://www.nber.org/papers/w29000")
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@techreport{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Minimum Wages and Teen Employment: New Evidence")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Ana Ruiz and David Park"));
+    assert_eq!(
+        field(&bibtex, "institution"),
+        Some("National Bureau of Economic Research")
+    );
+    assert_eq!(field(&bibtex, "type"), Some("NBER Working Paper"));
+    assert_eq!(field(&bibtex, "number"), Some("w29000"));
+    assert_eq!(field(&bibtex, "year"), Some("2021"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.3386/w29000"));
+    assert_eq!(
+        field(&bibtex, "url"),
+        Some("https://www.nber.org/papers/w29000")
+    );
+}
+
+#[test]
+fn nber_paper_numbers_come_from_the_path() {
+    let number = |url: &str| nber::paper_number(&Url::parse(url).unwrap());
+    assert_eq!(
+        number("https://www.nber.org/papers/w29000").as_deref(),
+        Some("w29000")
+    );
+    assert_eq!(
+        number("https://nber.org/papers/W29000?utm_source=x").as_deref(),
+        Some("w29000")
+    );
+    assert_eq!(number("https://www.nber.org/papers/t0250"), None);
+    assert_eq!(number("https://www.nber.org/people/jane_doe"), None);
+}
+
+#[tokio::test]
+async fn semantic_scholar_paper_uses_graph_api() {
+    let client = FixtureClient::new().with_fixture(
+        "https://api.semanticscholar.org/graph/v1/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776?fields=title,authors,year,venue,externalIds,publicationTypes,openAccessPdf",
+        fixture("s2_paper.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(
+        &client,
+        "https://www.semanticscholar.org/paper/Attention-is-All-you-Need-Vaswani-Shazeer/204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+    )
+    .await
+    .unwrap();
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "title"), Some("Attention is All you Need"));
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Ashish Vaswani and Noam M. Shazeer and Niki Parmar")
+    );
+    assert_eq!(
+        field(&bibtex, "journal"),
+        Some("Neural Information Processing Systems")
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2017"));
+    assert_eq!(
+        field(&bibtex, "pdf"),
+        Some("https://arxiv.org/pdf/1706.03762")
+    );
+}
+
+#[test]
+fn semantic_scholar_paper_id_needs_full_hash() {
+    use bibtexter::sites::semantic_scholar::paper_id;
+    let id = |url: &str| paper_id(&url::Url::parse(url).unwrap());
+
+    assert_eq!(
+        id("https://www.semanticscholar.org/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776")
+            .as_deref(),
+        Some("204e3073870fae3d05bcbc2f6a8e263d9b72e776")
+    );
+    assert_eq!(
+        id("https://www.semanticscholar.org/paper/Some-Title/1234"),
+        None
+    );
+    assert_eq!(
+        id("https://www.semanticscholar.org/search?q=attention"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn google_books_volume_is_cited_as_book() {
+    let client = FixtureClient::new().with_fixture(
+        "https://www.googleapis.com/books/v1/volumes/wrOQLV6xB-wC",
+        fixture("google_books_volume.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(
+        &client,
+        "https://books.google.com/books?id=wrOQLV6xB-wC&printsec=frontcover",
+    )
+    .await
+    .unwrap();
+
+    assert!(bibtex.starts_with("@book{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "title"), Some("The TeXbook"));
+    assert_eq!(field(&bibtex, "author"), Some("Donald Ervin Knuth"));
+    assert_eq!(field(&bibtex, "publisher"), Some("Addison-Wesley"));
+    assert_eq!(field(&bibtex, "year"), Some("1984"));
+    assert_eq!(field(&bibtex, "isbn"), Some("9780201134476"));
+    assert_eq!(field(&bibtex, "numpages"), Some("483"));
+    assert_eq!(field(&bibtex, "keywords"), Some("Computers, Mathematics"));
+}
+
+#[test]
+fn google_books_ids_are_found_on_all_url_forms() {
+    use bibtexter::sites::google_books::book_id;
+    let id = |url: &str| book_id(&url::Url::parse(url).unwrap());
+
+    assert_eq!(
+        id("https://books.google.com/books?id=wrOQLV6xB-wC").as_deref(),
+        Some("wrOQLV6xB-wC")
+    );
+    assert_eq!(
+        id("https://books.google.co.uk/books?hl=en&id=wrOQLV6xB-wC&pg=PA1").as_deref(),
+        Some("wrOQLV6xB-wC")
+    );
+    assert_eq!(
+        id("https://www.google.com/books/edition/The_TeXbook/wrOQLV6xB-wC?gbpv=1").as_deref(),
+        Some("wrOQLV6xB-wC")
+    );
+    assert_eq!(id("https://books.google.com/"), None);
+    assert_eq!(id("https://www.google.com/search?q=texbook"), None);
+}
+
+#[test]
+fn europe_pmc_urls_name_source_and_id() {
+    let id = |url: &str| europe_pmc::article_id(&Url::parse(url).unwrap());
+
+    assert_eq!(
+        id("https://europepmc.org/article/MED/31452104"),
+        Some(("MED".to_string(), "31452104".to_string()))
+    );
+    assert_eq!(
+        id("https://europepmc.org/article/pmc/pmc6711352"),
+        Some(("PMC".to_string(), "PMC6711352".to_string()))
+    );
+    assert_eq!(
+        id("https://europepmc.org/articles/PMC6711352/"),
+        Some(("PMC".to_string(), "PMC6711352".to_string()))
+    );
+    assert_eq!(id("https://europepmc.org/search?query=brain"), None);
+}
+
+#[tokio::test]
+async fn europe_pmc_article_keeps_pubmed_ids() {
+    let client = FixtureClient::new().with_fixture(
+        "https://www.ebi.ac.uk/europepmc/webservices/rest/search?query=ext_id:PMC6711352%20AND%20src:PMC&format=json&resultType=core",
+        fixture("europe_pmc_search.json"),
+    );
+
+    let bibtex = fetch_and_generate_bibtex(&client, "https://europepmc.org/article/PMC/PMC6711352")
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Single-cell transcriptomics of the ageing mouse brain")
+    );
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Ximerakis, Methodios and Tabula Muris Consortium")
+    );
+    assert_eq!(field(&bibtex, "journal"), Some("Nature communications"));
+    assert_eq!(field(&bibtex, "volume"), Some("10"));
+    assert_eq!(field(&bibtex, "number"), Some("1"));
+    assert_eq!(field(&bibtex, "pages"), Some("3814"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.1038/s41467-019-11786-6"));
+    assert_eq!(field(&bibtex, "pmid"), Some("31452104"));
+    assert_eq!(field(&bibtex, "pmcid"), Some("PMC6711352"));
+    assert_eq!(field(&bibtex, "orcid"), Some("0000-0002-1825-0097"));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use axum::http::HeaderMap;
+use bibtexter::error::AppError;
+use bibtexter::extract::ExtractionStrategy;
+use bibtexter::http::FixtureClient;
+use bibtexter::rate_limit::require_admin;
+use bibtexter::sites::known::KNOWN_SITES;
+use bibtexter::smoke::{random_smoke_test_url, run_smoke_test, smoke_test_urls};
+use common::fixture;
+
+#[test]
+fn smoke_tests_cover_every_known_site_and_a_doi() {
+    let urls = smoke_test_urls();
+    assert_eq!(urls.len(), KNOWN_SITES.len() + 1);
+    assert!(urls.iter().any(|url| url.starts_with("https://doi.org/")));
+    assert!(urls.contains(&random_smoke_test_url()));
+}
+
+#[tokio::test]
+async fn smoke_test_reports_strategy_and_title() {
+    let url = "https://arxiv.org/abs/1706.03762";
+    let client = FixtureClient::new().with_fixture(url, fixture("arxiv.html"));
+
+    let report = run_smoke_test(&client, url).await;
+
+    assert!(report.success);
+    assert_eq!(report.strategy, Some(ExtractionStrategy::MetaTags));
+    assert_eq!(report.title.as_deref(), Some("Attention Is All You Need"));
+    assert_eq!(report.error, None);
+}
+
+#[tokio::test]
+async fn smoke_test_reports_failures() {
+    let report = run_smoke_test(&FixtureClient::new(), "https://example.com/gone").await;
+
+    assert!(!report.success);
+    assert_eq!(report.strategy, None);
+    assert_eq!(report.error.as_deref(), Some("ExtractionError"));
+}
+
+#[test]
+fn admin_endpoints_need_the_admin_token() {
+    let headers = |token: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", token.parse().unwrap());
+        headers
+    };
+    assert!(matches!(
+        require_admin(&headers("secret")),
+        Err(AppError::Unauthorized)
+    ));
+
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_ADMIN_TOKEN", "secret") };
+    assert!(require_admin(&headers("secret")).is_ok());
+    assert!(require_admin(&headers("secre")).is_err());
+    assert!(require_admin(&headers("wrong!")).is_err());
+    assert!(require_admin(&HeaderMap::new()).is_err());
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use bibtexter::sites::ssrn::abstract_id;
+use common::{field, fixture};
+use url::Url;
+
+#[test]
+fn abstract_id_is_read_from_both_url_styles() {
+    let id = |url: &str| abstract_id(&Url::parse(url).unwrap());
+
+    assert_eq!(
+        id("https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4600000"),
+        Some("4600000".to_string())
+    );
+    assert_eq!(
+        id("https://ssrn.com/abstract=4712345"),
+        Some("4712345".to_string())
+    );
+    assert_eq!(id("https://www.ssrn.com/index.cfm/en/"), None);
+}
+
+#[tokio::test]
+async fn next_data_provides_the_full_record() {
+    let url = "https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4712345";
+    let client = FixtureClient::new().with_fixture(url, fixture("ssrn_next_data.html"));
+
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Algorithmic Pricing and Tacit Collusion")
+    );
+    assert_eq!(
+        field(&bibtex, "author"),
+        Some("Lena Fischer and Tomás Ruiz")
+    );
+    assert_eq!(field(&bibtex, "year"), Some("2024"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.2139/ssrn.4712345"));
+    assert_eq!(field(&bibtex, "publisher"), Some("SSRN"));
+    assert_eq!(
+        field(&bibtex, "keywords"),
+        Some("algorithmic pricing, collusion, antitrust")
+    );
+}
+
+#[tokio::test]
+async fn elsevier_api_fills_in_but_page_title_wins() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("ELSEVIER_API_KEY", "test-key") };
+    let url = "https://papers.ssrn.com/sol3/papers.cfm?abstract_id=4600000";
+    let client = FixtureClient::new()
+        .with_fixture(url, fixture("ssrn.html"))
+        .with_fixture(
+            "https://api.elsevier.com/content/article/ssrn:4600000?apiKey=test-key",
+            fixture("elsevier_ssrn.json"),
+        );
+
+    let bibtex = fetch_and_generate_bibtex(&client, url).await.unwrap();
+
+    assert_eq!(
+        field(&bibtex, "title"),
+        Some("Platform Governance and Antitrust")
+    );
+    assert_eq!(field(&bibtex, "author"), Some("Doe, Jane and Rossi, Maria"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.2139/ssrn.4600000"));
+}
@@ -0,0 +1,97 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use bibtexter::AppState;
+use bibtexter::api::stats_handler;
+use bibtexter::error::AppError;
+use bibtexter::extract::ExtractionStrategy;
+use bibtexter::stats::Stats;
+use std::time::Duration;
+
+#[test]
+fn empty_stats_report_zeroes() {
+    let stats = serde_json::to_value(Stats::default().snapshot()).unwrap();
+
+    assert_eq!(stats["total_requests"], 0);
+    assert_eq!(stats["error_rate"], 0.0);
+    assert_eq!(stats["strategies"].as_array().unwrap().len(), 4);
+    assert_eq!(stats["top_domains"], serde_json::json!([]));
+}
+
+#[test]
+fn requests_are_grouped_by_strategy_domain_and_error() {
+    let mut stats = Stats::default();
+    stats.record(
+        "https://arxiv.org/abs/1706.03762",
+        Ok(ExtractionStrategy::MetaTags),
+        Duration::from_millis(100),
+    );
+    stats.record(
+        "https://arxiv.org/abs/2005.14165",
+        Ok(ExtractionStrategy::MetaTags),
+        Duration::from_millis(300),
+    );
+    stats.record(
+        "https://doi.org/10.1145/3158154",
+        Ok(ExtractionStrategy::DoiContentNegotiation),
+        Duration::from_millis(50),
+    );
+    stats.record(
+        "https://example.com/missing",
+        Err(&AppError::ExtractionError("no title".into())),
+        Duration::from_millis(10),
+    );
+
+    let stats = serde_json::to_value(stats.snapshot()).unwrap();
+
+    assert_eq!(stats["total_requests"], 4);
+    assert_eq!(stats["error_rate"], 0.25);
+    let meta_tags = stats["strategies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|strategy| strategy["name"] == "meta_tags")
+        .unwrap();
+    assert_eq!(meta_tags["count"], 2);
+    assert_eq!(meta_tags["average_latency_ms"], 200.0);
+    assert_eq!(
+        stats["top_domains"][0],
+        serde_json::json!({ "domain": "arxiv.org", "count": 2 })
+    );
+    assert_eq!(
+        stats["errors"],
+        serde_json::json!([{ "variant": "ExtractionError", "count": 1, "rate": 0.25 }])
+    );
+}
+
+#[test]
+fn only_ten_domains_are_listed() {
+    let mut stats = Stats::default();
+    for i in 0..15 {
+        stats.record(
+            &format!("https://site{}.example.com/", i),
+            Ok(ExtractionStrategy::JsonLd),
+            Duration::ZERO,
+        );
+    }
+
+    let stats = serde_json::to_value(stats.snapshot()).unwrap();
+
+    assert_eq!(stats["top_domains"].as_array().unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn stats_endpoint_requires_the_admin_token() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_ADMIN_TOKEN", "secret") };
+    let state = AppState::new(reqwest::Client::new());
+
+    let response = stats_handler(State(state.clone()), HeaderMap::new())
+        .await
+        .into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-key", "secret".parse().unwrap());
+    assert!(stats_handler(State(state), headers).await.is_ok());
+}
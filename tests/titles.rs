@@ -0,0 +1,163 @@
+use bibtexter::titles::{fallback_title_from_url, normalize_title};
+use url::Url;
+
+#[test]
+fn site_name_suffixes_are_stripped() {
+    let cases = [
+        (
+            "Machine Learning Basics | Towards Data Science",
+            "Towards Data Science",
+            "Machine Learning Basics",
+        ),
+        (
+            "Attention Is All You Need - arXiv",
+            "arXiv",
+            "Attention Is All You Need",
+        ),
+        ("Release Notes :: Rust Blog", "Rust Blog", "Release Notes"),
+        (
+            "On Writing Well \u{2014} The Atlantic",
+            "The Atlantic",
+            "On Writing Well",
+        ),
+        (
+            "Climate Report \u{2013} BBC News",
+            "BBC News",
+            "Climate Report",
+        ),
+        (
+            "Understanding Ownership | ACM SIGPLAN",
+            "acm sigplan",
+            "Understanding Ownership",
+        ),
+    ];
+    for (raw, site, expected) in cases {
+        assert_eq!(normalize_title(raw, site), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn hosts_match_the_site_name_they_spell() {
+    let cases = [
+        (
+            "Machine Learning Basics | Towards Data Science",
+            "towardsdatascience.com",
+            "Machine Learning Basics",
+        ),
+        (
+            "Rust (programming language) - Wikipedia",
+            "en.wikipedia.org",
+            "Rust (programming language)",
+        ),
+        ("Why Rust? - Medium", "www.medium.com", "Why Rust?"),
+        ("A Post | GitHub Blog", "github.blog", "A Post"),
+    ];
+    for (raw, site, expected) in cases {
+        assert_eq!(normalize_title(raw, site), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn other_separated_parts_are_kept() {
+    let cases = [
+        (
+            "Rust: A Language | For Systems",
+            "Towards Data Science",
+            "Rust: A Language | For Systems",
+        ),
+        (
+            "Pre-trained Models - A Survey",
+            "arXiv",
+            "Pre-trained Models - A Survey",
+        ),
+        (
+            "Towards Data Science",
+            "Towards Data Science",
+            "Towards Data Science",
+        ),
+        (
+            "Deep Learning - Nature - Nature",
+            "Nature",
+            "Deep Learning - Nature",
+        ),
+        ("Plain Title", "", "Plain Title"),
+    ];
+    for (raw, site, expected) in cases {
+        assert_eq!(normalize_title(raw, site), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn document_type_prefixes_are_stripped() {
+    let cases = [
+        ("[PDF] Deep Residual Learning", "Deep Residual Learning"),
+        ("[pdf] Deep Residual Learning", "Deep Residual Learning"),
+        (
+            "(PDF) A Survey of Graph Networks",
+            "A Survey of Graph Networks",
+        ),
+        (
+            "[HTML] Open Science in Practice",
+            "Open Science in Practice",
+        ),
+        ("[PDF]", "[PDF]"),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(normalize_title(raw, "example.com"), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn single_case_titles_become_sentence_case() {
+    let cases = [
+        (
+            "DEEP RESIDUAL LEARNING FOR IMAGE RECOGNITION",
+            "Deep residual learning for image recognition",
+        ),
+        ("attention is all you need", "Attention is all you need"),
+        (
+            "ROBUST SPEECH: A STUDY OF NOISE",
+            "Robust speech: A study of noise",
+        ),
+        ("  graph   neural networks  ", "Graph neural networks"),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(normalize_title(raw, ""), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn mixed_case_and_single_word_titles_keep_their_casing() {
+    let cases = [
+        ("BERT", "BERT"),
+        ("Attention Is All You Need", "Attention Is All You Need"),
+        (
+            "iPhone Usage in NASA Missions",
+            "iPhone Usage in NASA Missions",
+        ),
+        ("GPT-4 Technical Report | OpenAI", "GPT-4 Technical Report"),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(normalize_title(raw, "OpenAI"), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn url_path_gives_a_fallback_title() {
+    let title = |url: &str| fallback_title_from_url(&Url::parse(url).unwrap());
+
+    assert_eq!(
+        title("https://example.com/blog/machine-learning-intro").as_deref(),
+        Some("Machine Learning Intro")
+    );
+    assert_eq!(
+        title("https://example.com/notes/graph_theory.html/").as_deref(),
+        Some("Graph Theory")
+    );
+    assert_eq!(
+        title("https://example.com/caf%C3%A9-culture").as_deref(),
+        Some("Café Culture")
+    );
+    assert_eq!(title("https://example.com/articles/12345"), None);
+    assert_eq!(title("https://example.com/"), None);
+}
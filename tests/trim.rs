@@ -0,0 +1,53 @@
+use bibtexter::extract::ArticleMetadata;
+use bibtexter::trim::{TrimConfig, trim_bibtex_fields, truncate_words};
+
+#[test]
+fn short_text_is_left_alone() {
+    assert_eq!(truncate_words("Attention Is All You Need", 300), None);
+}
+
+#[test]
+fn long_text_is_cut_at_a_word_boundary() {
+    let trimmed = truncate_words("The quick brown fox jumps over the lazy dog", 20).unwrap();
+
+    assert_eq!(trimmed, "The quick brown...");
+    assert!(trimmed.chars().count() <= 20);
+}
+
+#[test]
+fn trims_fields_over_their_limits() {
+    let config = TrimConfig {
+        max_title: 20,
+        max_abstract: 30,
+        max_keywords: 20,
+    };
+    let mut entry = ArticleMetadata {
+        title: "A title padded with leaked script content".into(),
+        abstract_text: Some("We study the effects of bundling on abstracts.".into()),
+        keywords: vec!["bundling".into(), "scrapers".into(), "abstracts".into()],
+        ..Default::default()
+    };
+
+    trim_bibtex_fields(&mut entry, &config);
+
+    assert_eq!(entry.title, "A title padded...");
+    assert_eq!(
+        entry.abstract_text.as_deref(),
+        Some("We study the effects of...")
+    );
+    assert_eq!(entry.keywords, vec!["bundling", "scrapers"]);
+}
+
+#[test]
+fn default_limits_keep_ordinary_entries_intact() {
+    let mut entry = ArticleMetadata {
+        title: "Attention Is All You Need".into(),
+        keywords: vec!["transformers".into()],
+        ..Default::default()
+    };
+
+    trim_bibtex_fields(&mut entry, &TrimConfig::default());
+
+    assert_eq!(entry.title, "Attention Is All You Need");
+    assert_eq!(entry.keywords, vec!["transformers"]);
+}
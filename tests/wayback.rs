@@ -0,0 +1,53 @@
+mod common;
+
+use bibtexter::extract::fetch_and_generate_bibtex;
+use bibtexter::http::FixtureClient;
+use bibtexter::wayback::find_snapshot;
+use common::{field, fixture};
+
+const DEAD_URL: &str = "https://blog.example.com/2019/retired-post";
+const AVAILABILITY_URL: &str = "https://archive.org/wayback/available?url=https%3A%2F%2Fblog.example.com%2F2019%2Fretired-post";
+const SNAPSHOT_URL: &str =
+    "https://web.archive.org/web/20190612083015/https://blog.example.com/2019/retired-post";
+
+fn archived_client() -> FixtureClient {
+    FixtureClient::new()
+        .with_fixture(AVAILABILITY_URL, fixture("wayback_available.json"))
+        .with_fixture(SNAPSHOT_URL, fixture("wayback_snapshot.html"))
+}
+
+#[tokio::test]
+async fn snapshot_url_is_built_from_timestamp() {
+    let snapshot = find_snapshot(&archived_client(), DEAD_URL).await;
+
+    assert_eq!(snapshot.as_deref(), Some(SNAPSHOT_URL));
+}
+
+#[tokio::test]
+async fn missing_snapshot_yields_none() {
+    let client = FixtureClient::new().with_fixture(
+        "https://archive.org/wayback/available?url=https%3A%2F%2Fblog.example.com%2Fnever-archived",
+        fixture("wayback_unavailable.json"),
+    );
+
+    let snapshot = find_snapshot(&client, "https://blog.example.com/never-archived").await;
+
+    assert_eq!(snapshot, None);
+}
+
+#[tokio::test]
+async fn dead_link_is_cited_from_snapshot_when_enabled() {
+    // Each integration test file is its own process, so this does not leak into other suites.
+    unsafe { std::env::set_var("BIBTEXTER_USE_WAYBACK", "true") };
+
+    let bibtex = fetch_and_generate_bibtex(&archived_client(), DEAD_URL)
+        .await
+        .expect("extraction should succeed");
+
+    assert_eq!(field(&bibtex, "title"), Some("Why We Retired Our Monolith"));
+    assert_eq!(field(&bibtex, "author"), Some("Priya Natarajan"));
+    assert_eq!(field(&bibtex, "publisher"), Some("blog.example.com"));
+    assert!(field(&bibtex, "note").unwrap().starts_with(
+        "Originally at \\url{https://blog.example.com/2019/retired-post}; archived at \\url{https://web.archive.org/web/20190612083015/https://blog.example.com/2019/retired-post}; Accessed: "
+    ));
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use bibtexter::bibtex::OutputMode;
+use bibtexter::http::FixtureClient;
+use bibtexter::wikidata::{bibtex_from_wikidata, parse_entity_id};
+use common::{field, fixture};
+
+#[test]
+fn parses_wikidata_item_ids() {
+    assert_eq!(parse_entity_id("Q42").as_deref(), Some("Q42"));
+    assert_eq!(parse_entity_id(" q42 ").as_deref(), Some("Q42"));
+    assert_eq!(parse_entity_id("wd:Q42").as_deref(), Some("Q42"));
+    assert_eq!(
+        parse_entity_id("https://www.wikidata.org/wiki/Q42").as_deref(),
+        Some("Q42")
+    );
+
+    assert_eq!(parse_entity_id("Q"), None);
+    assert_eq!(parse_entity_id("Q042"), None);
+    assert_eq!(parse_entity_id("P50"), None);
+}
+
+#[tokio::test]
+async fn wikidata_item_claims_become_article_fields() {
+    let client = FixtureClient::new()
+        .with_fixture(
+            "https://www.wikidata.org/wiki/Special:EntityData/Q27035000.json",
+            fixture("wikidata_entity.json"),
+        )
+        .with_fixture(
+            "https://www.wikidata.org/w/api.php?action=wbgetentities&ids=Q180445%7CQ90001&props=labels&languages=en&languagefallback=1&format=json",
+            fixture("wikidata_labels.json"),
+        );
+
+    let bibtex = bibtex_from_wikidata(&client, "Q27035000", OutputMode::Bibtex)
+        .await
+        .unwrap();
+
+    assert!(bibtex.starts_with("@article{"), "{}", bibtex);
+    assert_eq!(field(&bibtex, "title"), Some("An Atlas of Soil Moisture"));
+    assert_eq!(field(&bibtex, "author"), Some("Wei Chen and Maria Garcia"));
+    assert_eq!(field(&bibtex, "journal"), Some("Scientific Data"));
+    assert_eq!(field(&bibtex, "year"), Some("2017"));
+    assert_eq!(field(&bibtex, "volume"), Some("4"));
+    assert_eq!(field(&bibtex, "number"), Some("2"));
+    assert_eq!(field(&bibtex, "pages"), Some("101--118"));
+    assert_eq!(field(&bibtex, "doi"), Some("10.1038/SDATA.2017.82"));
+    assert_eq!(
+        field(&bibtex, "url"),
+        Some("https://www.wikidata.org/wiki/Q27035000")
+    );
+}
+
+#[tokio::test]
+async fn non_item_ids_are_rejected() {
+    assert!(
+        bibtex_from_wikidata(&FixtureClient::new(), "P50", OutputMode::Bibtex)
+            .await
+            .is_err()
+    );
+}